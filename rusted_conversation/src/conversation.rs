@@ -0,0 +1,300 @@
+use rusted_social_simulation::social::definition::registry::DefinitionRegistry;
+use rusted_social_simulation::social::definition::{Parameter, Parameters};
+use rusted_social_simulation::social::error::SocialError;
+use rusted_social_simulation::social::event::{HasDone, WasAddressedWith};
+use rusted_social_simulation::social::execution::{Address, ExecutionContext};
+use rusted_social_simulation::social::explanation::Explanation;
+use rusted_social_simulation::social::practice::participants::Participants;
+use rusted_social_simulation::social::practice::role::{Role, Target};
+use rusted_social_simulation::social::practice::staged::{StagedPractice, StagedPracticeTemplate};
+use rusted_social_simulation::social::practice::turn::{RoundRobin, TurnTakingPractice};
+use rusted_social_simulation::social::practice::Practice;
+use rusted_social_simulation::social::relationship::condition::RelationshipAtLeast;
+use rusted_social_simulation::social::relationship::effect::ModifyRelationship;
+use rusted_social_simulation::social::relationship::utility::RelationshipScaledUtility;
+use rusted_social_simulation::social::relationship::{Dimension, RelationshipContext};
+use rusted_social_simulation::social::selection::ArgMaxSelector;
+use rusted_social_simulation::social::simulation::Simulation;
+use rusted_social_simulation::social::utility::Utility;
+use rusted_social_simulation::social::world::condition::FlagIsSet;
+use rusted_social_simulation::social::world::effect::SetFlag;
+use rusted_social_simulation::social::world::World;
+
+pub type Context = ExecutionContext<World>;
+
+pub const PLAYER_ROLE: Role = Role::Character { id: 0 };
+pub const NPC_ROLE: Role = Role::Character { id: 1 };
+
+/// A conversation between the human player & a npc.
+///
+/// The player is controlled from outside the simulation, while the npc acts during its ticks.
+pub struct Conversation<'a> {
+    simulation: Simulation<'a, World>,
+    player: u32,
+    npc: u32,
+}
+
+impl<'a> Conversation<'a> {
+    /// Casts the player & the npc into the roles of the conversation's template.
+    ///
+    /// They take turns, starting with the player.
+    pub fn new(
+        template: &'a StagedPracticeTemplate<Context>,
+        player: u32,
+        npc: u32,
+    ) -> Result<Conversation<'a>, SocialError> {
+        let participants: Participants = vec![(PLAYER_ROLE, player), (NPC_ROLE, npc)]
+            .into_iter()
+            .collect();
+        let practice = StagedPractice::try_new(0, participants, template)?;
+        let policy = RoundRobin::new(vec![PLAYER_ROLE, NPC_ROLE]);
+        let practice = TurnTakingPractice::new(Box::new(practice), Box::new(policy));
+        let mut world = World::new();
+        world.add_entity(player);
+        world.add_entity(npc);
+        world
+            .get_relationships_mut()
+            .add_dimension("affinity", Dimension::try_new(-100, 100, false, 0)?);
+
+        let mut simulation = Simulation::new(world, Box::new(ArgMaxSelector), 0);
+        simulation.add_practice(Box::new(practice));
+        simulation.control(player);
+
+        Ok(Conversation {
+            simulation,
+            player,
+            npc,
+        })
+    }
+
+    /// Gets the name & utility of each action, that is currently available to the player.
+    pub fn get_player_options(&mut self) -> Vec<(String, Utility)> {
+        self.simulation.get_options(self.player)
+    }
+
+    /// Executes the player's available action with the index, if it is the player's turn.
+    pub fn choose(&mut self, index: usize) -> Option<String> {
+        let (name, _) = self.get_player_options().into_iter().nth(index)?;
+        self.simulation.act(self.player, &name).ok()?;
+        Some(name)
+    }
+
+    /// Simulates a tick, in which the npc executes the action with the highest utility, if it is its turn.
+    /// Without an available action the npc passes its turn on.
+    pub fn reply(&mut self) -> Option<String> {
+        let start = self.simulation.get_event_log().get_events().len();
+        self.simulation.step();
+
+        self.simulation.get_event_log().get_events()[start..]
+            .iter()
+            .find(|event| event.get_actor() == self.npc)
+            .map(|event| event.get_action().to_string())
+    }
+
+    /// Explains the availability & utility of each of the npc's actions, even if it isn't its turn.
+    pub fn explain_npc_options(&mut self) -> Vec<Explanation> {
+        self.simulation.explain(self.npc)
+    }
+
+    /// Gets the current stage of the conversation.
+    pub fn get_stage(&self) -> &str {
+        self.get_practice().get_stage().unwrap_or_default()
+    }
+
+    /// Did the conversation reach a terminal stage?
+    pub fn is_finished(&self) -> bool {
+        self.simulation.is_finished()
+    }
+
+    pub fn get_role_name(&self, entity: u32) -> &str {
+        let practice = self.get_practice();
+        practice
+            .get_template()
+            .get_role_name(practice.get_role(entity))
+    }
+
+    pub fn get_npc(&self) -> u32 {
+        self.npc
+    }
+
+    pub fn get_player(&self) -> u32 {
+        self.player
+    }
+
+    fn get_practice(&self) -> &dyn Practice<Context> {
+        self.simulation.get_practices()[0].as_ref()
+    }
+}
+
+/// Creates a registry with the domain-specific leaves used by the conversation templates.
+pub fn create_registry() -> DefinitionRegistry<Context> {
+    let mut registry = DefinitionRegistry::new();
+
+    registry.register_condition("has_flag", |parameters| {
+        Ok(Box::new(FlagIsSet::new(
+            get_target(parameters, "target")?,
+            parameters.get_text("flag")?,
+        )))
+    });
+    registry.register_condition("has_done", |parameters| {
+        Ok(Box::new(HasDone::new(
+            get_target(parameters, "target")?,
+            parameters.get_text("action")?,
+        )))
+    });
+    registry.register_condition("was_addressed_with", |parameters| {
+        Ok(Box::new(WasAddressedWith::new(
+            parameters.get_text("action")?,
+        )))
+    });
+    registry.register_condition("relationship_at_least", |parameters| {
+        Ok(Box::new(RelationshipAtLeast::new(
+            get_target(parameters, "from")?,
+            get_target(parameters, "to")?,
+            parameters.get_text("dimension")?,
+            parameters.get_integer("threshold")? as i32,
+        )))
+    });
+    registry.register_utility("relationship_utility", |parameters| {
+        Ok(Box::new(RelationshipScaledUtility::new(
+            get_target(parameters, "from")?,
+            get_target(parameters, "to")?,
+            parameters.get_text("dimension")?,
+            Utility::from_integer(parameters.get_integer("factor")? as i32),
+        )))
+    });
+    registry.register_effect("address", |parameters| {
+        Ok(Box::new(Address::new(get_target(parameters, "target")?)))
+    });
+    registry.register_effect("set_flag", |parameters| {
+        Ok(Box::new(SetFlag::new(
+            get_target(parameters, "target")?,
+            parameters.get_text("flag")?,
+            parameters.get_bool("value")?,
+        )))
+    });
+    registry.register_effect("modify_relationship", |parameters| {
+        Ok(Box::new(ModifyRelationship::new(
+            get_target(parameters, "from")?,
+            get_target(parameters, "to")?,
+            parameters.get_text("dimension")?,
+            parameters.get_integer("delta")? as i32,
+        )))
+    });
+
+    registry
+}
+
+/// Parses a target: "actor", "others", the key of a role or the id of a character role.
+fn get_target(parameters: &Parameters, name: &str) -> Result<Target, SocialError> {
+    match parameters.get(name) {
+        Some(Parameter::Text(text)) if text == "actor" => Ok(Target::Actor),
+        Some(Parameter::Text(text)) if text == "others" => Ok(Target::Others),
+        Some(Parameter::Text(text)) => text.parse().map(Target::Role),
+        Some(Parameter::Integer(id)) => Ok(Target::Role(Role::Character { id: *id as u32 })),
+        _ => Err(SocialError::InvalidParameter {
+            name: name.to_string(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusted_social_simulation::social::definition::PracticeTemplateDefinition;
+
+    fn load_template() -> StagedPracticeTemplate<Context> {
+        let text = include_str!("../resources/conversation.ron");
+        let definition = PracticeTemplateDefinition::from_ron(text).unwrap();
+        create_registry()
+            .create_staged_template(&definition)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_greetings() {
+        let template = load_template();
+        let mut conversation = Conversation::new(&template, 0, 1).unwrap();
+
+        assert_eq!(
+            conversation.get_player_options(),
+            vec![("Greet".to_string(), Utility::from_integer(10))]
+        );
+        assert_eq!(conversation.choose(0), Some("Greet".to_string()));
+        assert_eq!(conversation.get_stage(), "greeting");
+        assert_eq!(conversation.reply(), Some("Greet".to_string()));
+        assert_eq!(conversation.get_stage(), "small talk");
+        assert_eq!(conversation.get_player_options().len(), 4);
+        assert!(!conversation.is_finished());
+    }
+
+    #[test]
+    fn test_npc_leaves_after_insult() {
+        let template = load_template();
+        let mut conversation = Conversation::new(&template, 0, 1).unwrap();
+        conversation.choose(0);
+        conversation.reply();
+
+        assert_eq!(conversation.choose(1), Some("Insult".to_string()));
+        assert_eq!(conversation.reply(), Some("Say goodbye".to_string()));
+        assert_eq!(conversation.get_stage(), "farewell");
+        assert!(conversation.is_finished());
+        assert!(conversation.get_player_options().is_empty());
+    }
+
+    #[test]
+    fn test_npc_returns_compliment() {
+        let template = load_template();
+        let mut conversation = Conversation::new(&template, 0, 1).unwrap();
+        conversation.choose(0);
+        conversation.reply();
+
+        assert_eq!(conversation.choose(0), Some("Compliment".to_string()));
+        assert_eq!(conversation.reply(), Some("Compliment".to_string()));
+        assert!(!conversation.is_finished());
+    }
+
+    #[test]
+    fn test_explain_npc_options() {
+        let template = load_template();
+        let mut conversation = Conversation::new(&template, 0, 1).unwrap();
+        let explanations = conversation.explain_npc_options();
+
+        assert_eq!(explanations.len(), 1);
+        assert_eq!(
+            explanations[0].to_text(),
+            "Greet = 10\n  not = true\n    HasDone = false\n  fixed = 10\n"
+        );
+    }
+
+    #[test]
+    fn test_npc_answers_question() {
+        let template = load_template();
+        let mut conversation = Conversation::new(&template, 0, 1).unwrap();
+        conversation.choose(0);
+        conversation.reply();
+
+        assert_eq!(conversation.choose(2), Some("Ask a question".to_string()));
+        assert_eq!(conversation.reply(), Some("Answer".to_string()));
+    }
+
+    #[test]
+    fn test_turns() {
+        let template = load_template();
+        let mut conversation = Conversation::new(&template, 0, 1).unwrap();
+
+        assert_eq!(conversation.reply(), None);
+        assert_eq!(conversation.choose(0), Some("Greet".to_string()));
+        assert!(conversation.get_player_options().is_empty());
+        assert_eq!(conversation.choose(0), None);
+    }
+
+    #[test]
+    fn test_role_names() {
+        let template = load_template();
+        let conversation = Conversation::new(&template, 3, 4).unwrap();
+
+        assert_eq!(conversation.get_role_name(3), "Player");
+        assert_eq!(conversation.get_role_name(4), "Stranger");
+    }
+}
@@ -1,3 +1,101 @@
+use crate::conversation::{create_registry, Conversation};
+use rusted_social_simulation::social::definition::PracticeTemplateDefinition;
+use std::io::{self, BufRead, Write};
+use std::{env, fs, process};
+
+mod conversation;
+
+const DEFAULT_TEMPLATE: &str = include_str!("../resources/conversation.ron");
+
+const PLAYER: u32 = 0;
+const NPC: u32 = 1;
+
 fn main() {
+    let text = match env::args().nth(1) {
+        Some(path) => fs::read_to_string(&path).unwrap_or_else(|error| {
+            eprintln!("Couldn't read '{}': {}", path, error);
+            process::exit(1);
+        }),
+        None => DEFAULT_TEMPLATE.to_string(),
+    };
+
+    let template = PracticeTemplateDefinition::from_ron(&text)
+        .and_then(|definition| create_registry().create_staged_template(&definition))
+        .unwrap_or_else(|error| {
+            eprintln!("{}", error);
+            process::exit(1);
+        });
+
+    let mut conversation = Conversation::new(&template, PLAYER, NPC).unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        process::exit(1);
+    });
+
     println!("Conversation");
+    println!("Enter the number of an action, 'w' to explain the npc or 'q' to quit.");
+
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+
+    while !conversation.is_finished() {
+        let options = conversation.get_player_options();
+
+        if options.is_empty() {
+            println!("You have nothing to say.");
+            break;
+        }
+
+        println!();
+        println!("[{}]", conversation.get_stage());
+        for (index, (name, utility)) in options.iter().enumerate() {
+            println!("{}: {} (utility {})", index + 1, name, utility);
+        }
+        print!("> ");
+
+        if io::stdout().flush().is_err() {
+            // the output was closed, so nobody can see the conversation anymore
+            return;
+        }
+
+        let line = match lines.next() {
+            Some(Ok(line)) => line,
+            _ => break,
+        };
+        let input = line.trim();
+
+        if input == "q" {
+            break;
+        } else if input == "w" {
+            for explanation in conversation.explain_npc_options() {
+                print!("{}", explanation);
+            }
+            continue;
+        }
+
+        let index = match input.parse::<usize>() {
+            Ok(number) if number >= 1 && number <= options.len() => number - 1,
+            _ => {
+                println!("Invalid input '{}'!", input);
+                continue;
+            }
+        };
+
+        if let Some(name) = conversation.choose(index) {
+            let player = conversation.get_player();
+            println!("{}: {}", conversation.get_role_name(player), name);
+        }
+
+        if conversation.is_finished() {
+            break;
+        }
+
+        let npc = conversation.get_npc();
+        match conversation.reply() {
+            Some(name) => println!("{}: {}", conversation.get_role_name(npc), name),
+            None => println!("{} says nothing.", conversation.get_role_name(npc)),
+        }
+    }
+
+    println!();
+    println!("The conversation is over.");
 }
@@ -1,9 +1,10 @@
-use crate::social::condition::Condition;
-use crate::social::effect::Effect;
-use crate::social::utility::{Utility, UtilityRule};
+use crate::social::condition::{AndCondition, Condition, FnCondition, MockCondition};
+use crate::social::effect::{DoNothing, Effect, EffectVector, FnEffect};
+use crate::social::explanation::Explanation;
+use crate::social::utility::{FixedUtility, FnUtility, TotalUtility, Utility, UtilityRule};
 
 /// An action that can be executed in a social simulation.
-pub trait Action<T> {
+pub trait Action<T>: Send + Sync {
     /// Gets the name of the action.
     fn get_name(&self) -> &str;
 
@@ -15,6 +16,11 @@ pub trait Action<T> {
 
     /// Execute the action and change the current context.
     fn execute(&self, context: &mut T);
+
+    /// Explains the availability & the utility of the action with the current context.
+    fn explain(&self, context: &T) -> Explanation {
+        Explanation::utility(self.get_name(), self.get_utility(context), Vec::new())
+    }
 }
 
 /// A simple implementation of Action.
@@ -46,11 +52,11 @@ impl<T> Action<T> for SimpleAction<T> {
     ///
     /// ```
     ///# use rusted_social_simulation::social::condition::MockCondition;
-    ///# use rusted_social_simulation::social::utility::FixedUtility;
+    ///# use rusted_social_simulation::social::utility::{FixedUtility, Utility};
     ///# use rusted_social_simulation::social::effect::DoNothing;
     ///# use rusted_social_simulation::social::action::{Action, SimpleAction};
     /// let condition = Box::new(MockCondition::new(true));
-    /// let utility_rule = Box::new(FixedUtility::new(0));
+    /// let utility_rule = Box::new(FixedUtility::new(Utility::default()));
     /// let effect = Box::new(DoNothing);
     /// let action: SimpleAction<u32> = SimpleAction::new("action0".to_string(), condition, utility_rule, effect);
     ///
@@ -64,11 +70,11 @@ impl<T> Action<T> for SimpleAction<T> {
     ///
     /// ```
     ///# use rusted_social_simulation::social::condition::MockCondition;
-    ///# use rusted_social_simulation::social::utility::FixedUtility;
+    ///# use rusted_social_simulation::social::utility::{FixedUtility, Utility};
     ///# use rusted_social_simulation::social::effect::DoNothing;
     ///# use rusted_social_simulation::social::action::{Action, SimpleAction};
     /// let condition = Box::new(MockCondition::new(true));
-    /// let utility_rule = Box::new(FixedUtility::new(0));
+    /// let utility_rule = Box::new(FixedUtility::new(Utility::default()));
     /// let effect = Box::new(DoNothing);
     /// let action = SimpleAction::new("a".to_string(), condition, utility_rule, effect);
     ///
@@ -82,15 +88,15 @@ impl<T> Action<T> for SimpleAction<T> {
     ///
     /// ```
     ///# use rusted_social_simulation::social::condition::MockCondition;
-    ///# use rusted_social_simulation::social::utility::FixedUtility;
+    ///# use rusted_social_simulation::social::utility::{FixedUtility, Utility};
     ///# use rusted_social_simulation::social::effect::DoNothing;
     ///# use rusted_social_simulation::social::action::{Action, SimpleAction};
     /// let condition = Box::new(MockCondition::new(false));
-    /// let utility_rule = Box::new(FixedUtility::new(13));
+    /// let utility_rule = Box::new(FixedUtility::new(Utility::from_integer(13)));
     /// let effect = Box::new(DoNothing);
     /// let action = SimpleAction::new("a".to_string(), condition, utility_rule, effect);
     ///
-    /// assert_eq!(action.get_utility(&42), 13);
+    /// assert_eq!(action.get_utility(&42), Utility::from_integer(13));
     /// ```
     fn get_utility(&self, context: &T) -> Utility {
         self.utility_rule.calculate_utility(context)
@@ -100,11 +106,11 @@ impl<T> Action<T> for SimpleAction<T> {
     ///
     /// ```
     ///# use rusted_social_simulation::social::condition::MockCondition;
-    ///# use rusted_social_simulation::social::utility::FixedUtility;
+    ///# use rusted_social_simulation::social::utility::{FixedUtility, Utility};
     ///# use rusted_social_simulation::social::effect::{DoNothing, MockEffect};
     ///# use rusted_social_simulation::social::action::{Action, SimpleAction};
     /// let condition = Box::new(MockCondition::new(false));
-    /// let utility_rule = Box::new(FixedUtility::new(0));
+    /// let utility_rule = Box::new(FixedUtility::new(Utility::default()));
     /// let effect = Box::new(MockEffect::new(3));
     /// let action = SimpleAction::new("a".to_string(), condition, utility_rule, effect);
     /// let mut context = 42;
@@ -116,6 +122,144 @@ impl<T> Action<T> for SimpleAction<T> {
     fn execute(&self, context: &mut T) {
         self.effect.apply(context)
     }
+
+    /// Explains the action's condition & utility rule.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::condition::MockCondition;
+    ///# use rusted_social_simulation::social::utility::{FixedUtility, Utility};
+    ///# use rusted_social_simulation::social::effect::DoNothing;
+    ///# use rusted_social_simulation::social::action::{Action, SimpleAction};
+    /// let condition = Box::new(MockCondition::new(true));
+    /// let utility_rule = Box::new(FixedUtility::new(Utility::from_integer(13)));
+    /// let effect = Box::new(DoNothing);
+    /// let action = SimpleAction::new("a".to_string(), condition, utility_rule, effect);
+    ///
+    /// assert_eq!(action.explain(&42).to_text(), "a = 13\n  MockCondition = true\n  fixed = 13\n");
+    /// ```
+    fn explain(&self, context: &T) -> Explanation {
+        let condition = self.condition.explain(context);
+        let utility = self.utility_rule.explain(context);
+        let value = self.get_utility(context);
+        Explanation::utility(&self.name, value, vec![condition, utility])
+    }
+}
+
+/// Builds a [`SimpleAction`] step by step, e.g. from closures for prototyping.
+///
+/// Without a condition, utility rule or effect the action is always available,
+/// has an utility of 0 and does nothing.
+///
+/// ```
+///# use rusted_social_simulation::social::action::{Action, ActionBuilder};
+///# use rusted_social_simulation::social::utility::Utility;
+/// let action = ActionBuilder::new("double")
+///     .when(|context: &u32| *context < 100)
+///     .utility(|context| Utility::from_integer(*context as i32))
+///     .effect(|context| *context *= 2)
+///     .build();
+/// let mut context = 42;
+///
+/// assert!(action.is_available(&context));
+/// assert_eq!(action.get_utility(&context), Utility::from_integer(42));
+/// action.execute(&mut context);
+/// assert_eq!(context, 84);
+/// ```
+pub struct ActionBuilder<T> {
+    name: String,
+    conditions: Vec<Box<dyn Condition<T>>>,
+    utility_rules: Vec<Box<dyn UtilityRule<T>>>,
+    effects: Vec<Box<dyn Effect<T>>>,
+}
+
+impl<T: 'static> ActionBuilder<T> {
+    pub fn new(name: &str) -> ActionBuilder<T> {
+        ActionBuilder {
+            name: name.to_string(),
+            conditions: Vec::new(),
+            utility_rules: Vec::new(),
+            effects: Vec::new(),
+        }
+    }
+
+    /// Adds a condition closure. The action is only available, if all conditions are true.
+    pub fn when<F: Fn(&T) -> bool + Send + Sync + 'static>(self, condition: F) -> ActionBuilder<T> {
+        self.with_condition(Box::new(FnCondition::new(condition)))
+    }
+
+    /// Adds a condition. The action is only available, if all conditions are true.
+    pub fn with_condition(mut self, condition: Box<dyn Condition<T>>) -> ActionBuilder<T> {
+        self.conditions.push(condition);
+        self
+    }
+
+    /// Adds an utility closure. The utility of the action is the sum of all utility rules.
+    pub fn utility<F: Fn(&T) -> Utility + Send + Sync + 'static>(
+        self,
+        utility: F,
+    ) -> ActionBuilder<T> {
+        self.with_utility_rule(Box::new(FnUtility::new(utility)))
+    }
+
+    /// Adds an utility rule. The utility of the action is the sum of all utility rules.
+    pub fn with_utility_rule(mut self, utility_rule: Box<dyn UtilityRule<T>>) -> ActionBuilder<T> {
+        self.utility_rules.push(utility_rule);
+        self
+    }
+
+    /// Adds an effect closure. The effects are applied in the order they were added.
+    pub fn effect<F: Fn(&mut T) + Send + Sync + 'static>(self, effect: F) -> ActionBuilder<T> {
+        self.with_effect(Box::new(FnEffect::new(effect)))
+    }
+
+    /// Adds an effect. The effects are applied in the order they were added.
+    pub fn with_effect(mut self, effect: Box<dyn Effect<T>>) -> ActionBuilder<T> {
+        self.effects.push(effect);
+        self
+    }
+
+    /// Builds the action as a standard [`Action`] trait object.
+    pub fn build(self) -> Box<dyn Action<T>> {
+        Box::new(self.build_simple())
+    }
+
+    /// Builds the action. A single condition, utility rule or effect is used directly,
+    /// while multiple ones are combined.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::action::{Action, ActionBuilder};
+    ///# use rusted_social_simulation::social::condition::MockCondition;
+    ///# use rusted_social_simulation::social::utility::{FixedUtility, Utility};
+    /// let action = ActionBuilder::new("wave")
+    ///     .with_condition(Box::new(MockCondition::new(true)))
+    ///     .when(|context: &u32| *context > 10)
+    ///     .with_utility_rule(Box::new(FixedUtility::new(Utility::from_integer(3))))
+    ///     .build_simple();
+    ///
+    /// assert_eq!(
+    ///     action.explain(&42).to_text(),
+    ///     "wave = 3\n  and = true\n    MockCondition = true\n    FnCondition = true\n  fixed = 3\n"
+    /// );
+    /// ```
+    pub fn build_simple(mut self) -> SimpleAction<T> {
+        let condition: Box<dyn Condition<T>> = match self.conditions.len() {
+            0 => Box::new(MockCondition::new(true)),
+            1 => self.conditions.remove(0),
+            _ => Box::new(AndCondition::new(self.conditions)),
+        };
+        let utility_rule: Box<dyn UtilityRule<T>> = match self.utility_rules.len() {
+            0 => Box::new(FixedUtility::new(Utility::default())),
+            1 => self.utility_rules.remove(0),
+            _ => Box::new(TotalUtility::new(self.utility_rules)),
+        };
+        let effect: Box<dyn Effect<T>> = match self.effects.len() {
+            0 => Box::new(DoNothing),
+            1 => self.effects.remove(0),
+            _ => Box::new(EffectVector::new(self.effects)),
+        };
+
+        SimpleAction::new(self.name, condition, utility_rule, effect)
+    }
 }
 
 /// A mock action for testing.
@@ -158,12 +302,13 @@ impl<T> Action<T> for MockAction {
     ///
     /// ```
     ///# use rusted_social_simulation::social::action::{Action, MockAction};
+    ///# use rusted_social_simulation::social::utility::Utility;
     /// let action = MockAction::new("action0".to_string());
     ///
-    /// assert_eq!(action.get_utility(&42), 0);
+    /// assert_eq!(action.get_utility(&42), Utility::default());
     /// ```
     fn get_utility(&self, _context: &T) -> Utility {
-        0
+        Utility::default()
     }
 
     /// Does nothing.
@@ -1,6 +1,18 @@
+use crate::social::explanation::{get_type_name, Explanation, Outcome};
+use crate::social::practice::role::Target;
+use crate::social::practice::RoleContext;
+use crate::social::value::{Comparison, Value};
+
 /// A condition that can evaluate to true or false given a context.
-pub trait Condition<T> {
+pub trait Condition<T>: Send + Sync {
     fn evaluate(&self, context: &T) -> bool;
+
+    /// Explains the evaluation of the condition & its sub-conditions.
+    ///
+    /// Conditions without sub-conditions can rely on the default, which uses the name of the type.
+    fn explain(&self, context: &T) -> Explanation {
+        Explanation::condition(get_type_name::<Self>(), self.evaluate(context), Vec::new())
+    }
 }
 
 /// A condition that always evaluates to a fixed value.
@@ -27,6 +39,32 @@ impl<T> Condition<T> for MockCondition {
     }
 }
 
+/// A condition that evaluates a closure, e.g. for prototyping.
+pub struct FnCondition<F> {
+    function: F,
+}
+
+impl<F> FnCondition<F> {
+    pub fn new(function: F) -> FnCondition<F> {
+        FnCondition { function }
+    }
+}
+
+impl<T, F: Fn(&T) -> bool + Send + Sync> Condition<T> for FnCondition<F> {
+    /// Returns the result of the closure.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::condition::{Condition, FnCondition};
+    /// let is_even = FnCondition::new(|context: &u32| context % 2 == 0);
+    ///
+    /// assert!(is_even.evaluate(&42));
+    /// assert!(!is_even.evaluate(&43));
+    /// ```
+    fn evaluate(&self, context: &T) -> bool {
+        (self.function)(context)
+    }
+}
+
 /// A condition that negates the evaluation of another condition
 pub struct NotCondition<T> {
     condition: Box<dyn Condition<T>>,
@@ -52,6 +90,20 @@ impl<T> Condition<T> for NotCondition<T> {
     fn evaluate(&self, context: &T) -> bool {
         !self.condition.evaluate(context)
     }
+
+    /// Explains the negated condition.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::condition::*;
+    /// let not = NotCondition::new(Box::new(MockCondition::new(false)));
+    ///
+    /// assert_eq!(not.explain(&42).to_text(), "not = true\n  MockCondition = false\n");
+    /// ```
+    fn explain(&self, context: &T) -> Explanation {
+        let child = self.condition.explain(context);
+        let value = child.get_outcome() == Outcome::Condition(false);
+        Explanation::condition("not", value, vec![child])
+    }
 }
 
 /// A condition that evaluates to true, if all sub-conditions are true.
@@ -83,6 +135,15 @@ impl<T> Condition<T> for AndCondition<T> {
         }
         true
     }
+
+    /// Explains all sub-conditions, even the ones after the first false one.
+    fn explain(&self, context: &T) -> Explanation {
+        let children = explain_all(&self.conditions, context);
+        let value = children
+            .iter()
+            .all(|c| c.get_outcome() == Outcome::Condition(true));
+        Explanation::condition("and", value, children)
+    }
 }
 
 /// A condition that evaluates to true, if any sub-condition is true.
@@ -114,6 +175,387 @@ impl<T> Condition<T> for OrCondition<T> {
         }
         false
     }
+
+    /// Explains all sub-conditions, even the ones after the first true one.
+    fn explain(&self, context: &T) -> Explanation {
+        let children = explain_all(&self.conditions, context);
+        let value = children
+            .iter()
+            .any(|c| c.get_outcome() == Outcome::Condition(true));
+        Explanation::condition("or", value, children)
+    }
+}
+
+/// A condition that evaluates to true, if an odd number of sub-conditions is true.
+///
+/// For 2 sub-conditions it is true, if exactly one of them is true.
+pub struct XorCondition<T> {
+    conditions: Vec<Box<dyn Condition<T>>>,
+}
+
+impl<T> XorCondition<T> {
+    pub fn new(conditions: Vec<Box<dyn Condition<T>>>) -> XorCondition<T> {
+        XorCondition { conditions }
+    }
+}
+
+impl<T> Condition<T> for XorCondition<T> {
+    /// Returns true, if an odd number of sub-conditions is true. All sub-conditions are evaluated.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::condition::*;
+    /// let true0 = Box::new(MockCondition::new(true));
+    /// let true1 = Box::new(MockCondition::new(true));
+    ///
+    /// assert!(!XorCondition::new(vec![true0, true1]).evaluate(&42));
+    /// ```
+    fn evaluate(&self, context: &T) -> bool {
+        self.conditions
+            .iter()
+            .filter(|condition| condition.evaluate(context))
+            .count()
+            % 2
+            == 1
+    }
+
+    fn explain(&self, context: &T) -> Explanation {
+        let children = explain_all(&self.conditions, context);
+        let value = count_true(&children) % 2 == 1;
+        Explanation::condition("xor", value, children)
+    }
+}
+
+/// A condition that evaluates to true, if at least n sub-conditions are true.
+pub struct AtLeastCondition<T> {
+    n: usize,
+    conditions: Vec<Box<dyn Condition<T>>>,
+}
+
+impl<T> AtLeastCondition<T> {
+    pub fn new(n: usize, conditions: Vec<Box<dyn Condition<T>>>) -> AtLeastCondition<T> {
+        AtLeastCondition { n, conditions }
+    }
+}
+
+impl<T> Condition<T> for AtLeastCondition<T> {
+    /// Returns true, if at least n sub-conditions are true.
+    /// Stops as soon as the result is certain.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::condition::*;
+    /// let true0 = Box::new(MockCondition::new(true));
+    /// let false0 = Box::new(MockCondition::new(false));
+    /// let true1 = Box::new(MockCondition::new(true));
+    ///
+    /// assert!(AtLeastCondition::new(2, vec![true0, false0, true1]).evaluate(&42));
+    /// ```
+    fn evaluate(&self, context: &T) -> bool {
+        let total = self.conditions.len();
+        let mut count = 0;
+
+        for (i, condition) in self.conditions.iter().enumerate() {
+            if count >= self.n {
+                return true;
+            } else if count + total - i < self.n {
+                return false;
+            } else if condition.evaluate(context) {
+                count += 1;
+            }
+        }
+
+        count >= self.n
+    }
+
+    fn explain(&self, context: &T) -> Explanation {
+        let children = explain_all(&self.conditions, context);
+        let value = count_true(&children) >= self.n;
+        Explanation::condition(&format!("at least {}", self.n), value, children)
+    }
+}
+
+/// A condition that evaluates to true, if at most n sub-conditions are true.
+pub struct AtMostCondition<T> {
+    n: usize,
+    conditions: Vec<Box<dyn Condition<T>>>,
+}
+
+impl<T> AtMostCondition<T> {
+    pub fn new(n: usize, conditions: Vec<Box<dyn Condition<T>>>) -> AtMostCondition<T> {
+        AtMostCondition { n, conditions }
+    }
+}
+
+impl<T> Condition<T> for AtMostCondition<T> {
+    /// Returns true, if at most n sub-conditions are true.
+    /// Stops as soon as more than n sub-conditions are true.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::condition::*;
+    /// let true0 = Box::new(MockCondition::new(true));
+    /// let true1 = Box::new(MockCondition::new(true));
+    ///
+    /// assert!(!AtMostCondition::new(1, vec![true0, true1]).evaluate(&42));
+    /// ```
+    fn evaluate(&self, context: &T) -> bool {
+        let mut count = 0;
+
+        for condition in &self.conditions {
+            if condition.evaluate(context) {
+                count += 1;
+
+                if count > self.n {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+
+    fn explain(&self, context: &T) -> Explanation {
+        let children = explain_all(&self.conditions, context);
+        let value = count_true(&children) <= self.n;
+        Explanation::condition(&format!("at most {}", self.n), value, children)
+    }
+}
+
+/// A condition that evaluates to true, if exactly n sub-conditions are true.
+pub struct ExactlyCondition<T> {
+    n: usize,
+    conditions: Vec<Box<dyn Condition<T>>>,
+}
+
+impl<T> ExactlyCondition<T> {
+    pub fn new(n: usize, conditions: Vec<Box<dyn Condition<T>>>) -> ExactlyCondition<T> {
+        ExactlyCondition { n, conditions }
+    }
+}
+
+impl<T> Condition<T> for ExactlyCondition<T> {
+    /// Returns true, if exactly n sub-conditions are true.
+    /// Stops as soon as the result is certain to be false.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::condition::*;
+    /// let true0 = Box::new(MockCondition::new(true));
+    /// let false0 = Box::new(MockCondition::new(false));
+    ///
+    /// assert!(ExactlyCondition::new(1, vec![true0, false0]).evaluate(&42));
+    /// ```
+    fn evaluate(&self, context: &T) -> bool {
+        let total = self.conditions.len();
+        let mut count = 0;
+
+        for (i, condition) in self.conditions.iter().enumerate() {
+            if count + total - i < self.n {
+                return false;
+            } else if condition.evaluate(context) {
+                count += 1;
+
+                if count > self.n {
+                    return false;
+                }
+            }
+        }
+
+        count == self.n
+    }
+
+    fn explain(&self, context: &T) -> Explanation {
+        let children = explain_all(&self.conditions, context);
+        let value = count_true(&children) == self.n;
+        Explanation::condition(&format!("exactly {}", self.n), value, children)
+    }
+}
+
+/// A condition that evaluates to false, if the premise is true, but the conclusion isn't.
+pub struct ImpliesCondition<T> {
+    premise: Box<dyn Condition<T>>,
+    conclusion: Box<dyn Condition<T>>,
+}
+
+impl<T> ImpliesCondition<T> {
+    pub fn new(
+        premise: Box<dyn Condition<T>>,
+        conclusion: Box<dyn Condition<T>>,
+    ) -> ImpliesCondition<T> {
+        ImpliesCondition {
+            premise,
+            conclusion,
+        }
+    }
+}
+
+impl<T> Condition<T> for ImpliesCondition<T> {
+    /// Returns true, if the premise is false or the conclusion is true.
+    /// The conclusion is only evaluated, if the premise is true.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::condition::*;
+    /// let premise = Box::new(MockCondition::new(true));
+    /// let conclusion = Box::new(MockCondition::new(false));
+    ///
+    /// assert!(!ImpliesCondition::new(premise, conclusion).evaluate(&42));
+    /// ```
+    fn evaluate(&self, context: &T) -> bool {
+        !self.premise.evaluate(context) || self.conclusion.evaluate(context)
+    }
+
+    /// Explains the premise & the conclusion.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::condition::*;
+    /// let implies = ImpliesCondition::new(Box::new(MockCondition::new(false)), Box::new(MockCondition::new(false)));
+    ///
+    /// assert_eq!(implies.explain(&42).to_text(), "implies = true\n  MockCondition = false\n  MockCondition = false\n");
+    /// ```
+    fn explain(&self, context: &T) -> Explanation {
+        let premise = self.premise.explain(context);
+        let conclusion = self.conclusion.explain(context);
+        let value = premise.get_outcome() == Outcome::Condition(false)
+            || conclusion.get_outcome() == Outcome::Condition(true);
+        Explanation::condition("implies", value, vec![premise, conclusion])
+    }
+}
+
+/// Creates a condition for a single entity of a group.
+pub type ConditionFactory<T> = Box<dyn Fn(Target) -> Box<dyn Condition<T>> + Send + Sync>;
+
+/// A condition that is true, if a condition is true for any entity of a target.
+///
+/// E.g. *any listener greeted the speaker*.
+pub struct AnyEntity<T> {
+    target: Target,
+    factory: ConditionFactory<T>,
+}
+
+impl<T> AnyEntity<T> {
+    /// The factory creates the condition for each entity.
+    pub fn new(target: Target, factory: ConditionFactory<T>) -> AnyEntity<T> {
+        AnyEntity { target, factory }
+    }
+}
+
+impl<T: RoleContext> Condition<T> for AnyEntity<T> {
+    /// Returns true, if the condition is true for at least one entity.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::condition::{AllEntities, AnyEntity, Condition};
+    ///# use rusted_social_simulation::social::event::HasDone;
+    ///# use rusted_social_simulation::social::execution::ExecutionContext;
+    ///# use rusted_social_simulation::social::practice::role::{Role, Target};
+    /// let speaker = Role::Character { id: 0 };
+    /// let listener = Role::Character { id: 1 };
+    /// let participants = vec![(speaker, 10), (listener, 11), (listener, 12)];
+    /// let mut context = ExecutionContext::new(42);
+    /// context.bind(12, 5, participants.iter().copied().collect());
+    /// context.record("greet");
+    /// context.bind(10, 5, participants.into_iter().collect());
+    ///
+    /// let any = AnyEntity::new(Target::Role(listener), Box::new(|t| Box::new(HasDone::new(t, "greet"))));
+    /// let all = AllEntities::new(Target::Role(listener), Box::new(|t| Box::new(HasDone::new(t, "greet"))));
+    ///
+    /// assert!(any.evaluate(&context));
+    /// assert!(!all.evaluate(&context));
+    /// ```
+    fn evaluate(&self, context: &T) -> bool {
+        context
+            .resolve(self.target)
+            .into_iter()
+            .any(|entity| (self.factory)(Target::Entity(entity)).evaluate(context))
+    }
+}
+
+/// A condition that is true, if a condition is true for all entities of a target.
+///
+/// E.g. *all listeners greeted the speaker*.
+pub struct AllEntities<T> {
+    target: Target,
+    factory: ConditionFactory<T>,
+}
+
+impl<T> AllEntities<T> {
+    /// The factory creates the condition for each entity.
+    pub fn new(target: Target, factory: ConditionFactory<T>) -> AllEntities<T> {
+        AllEntities { target, factory }
+    }
+}
+
+impl<T: RoleContext> Condition<T> for AllEntities<T> {
+    /// Returns true, if the condition is true for all entities. False without entities.
+    fn evaluate(&self, context: &T) -> bool {
+        let entities = context.resolve(self.target);
+        !entities.is_empty()
+            && entities
+                .into_iter()
+                .all(|entity| (self.factory)(Target::Entity(entity)).evaluate(context))
+    }
+}
+
+/// A condition that compares 2 values, e.g. to check a threshold.
+pub struct CompareCondition<T> {
+    left: Value<T>,
+    comparison: Comparison,
+    right: Value<T>,
+}
+
+impl<T> CompareCondition<T> {
+    pub fn new(left: Value<T>, comparison: Comparison, right: Value<T>) -> CompareCondition<T> {
+        CompareCondition {
+            left,
+            comparison,
+            right,
+        }
+    }
+}
+
+impl<T> Condition<T> for CompareCondition<T> {
+    /// Returns the result of the comparison.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::condition::*;
+    ///# use rusted_social_simulation::social::utility::Utility;
+    ///# use rusted_social_simulation::social::value::{Comparison, Value};
+    /// let age = Value::getter(|age: &i32| Utility::from_integer(*age));
+    /// let is_adult = CompareCondition::new(age, Comparison::GreaterOrEqual, Value::Constant(Utility::from_integer(18)));
+    ///
+    /// assert!(is_adult.evaluate(&42));
+    /// assert!(!is_adult.evaluate(&17));
+    /// ```
+    fn evaluate(&self, context: &T) -> bool {
+        self.comparison
+            .compare(self.left.get_value(context), self.right.get_value(context))
+    }
+
+    /// Explains both values.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::condition::*;
+    ///# use rusted_social_simulation::social::utility::Utility;
+    ///# use rusted_social_simulation::social::value::{Comparison, Value};
+    /// let age = Value::getter(|age: &i32| Utility::from_integer(*age));
+    /// let condition = CompareCondition::new(age, Comparison::Less, Value::Constant(Utility::from_integer(18)));
+    ///
+    /// assert_eq!(condition.explain(&42).to_text(), "< = false\n  getter = 42\n  constant = 18\n");
+    /// ```
+    fn explain(&self, context: &T) -> Explanation {
+        Explanation::condition(
+            &self.comparison.to_string(),
+            self.evaluate(context),
+            vec![self.left.explain(context), self.right.explain(context)],
+        )
+    }
+}
+
+fn explain_all<T>(conditions: &[Box<dyn Condition<T>>], context: &T) -> Vec<Explanation> {
+    conditions.iter().map(|c| c.explain(context)).collect()
+}
+
+fn count_true(explanations: &[Explanation]) -> usize {
+    explanations
+        .iter()
+        .filter(|e| e.get_outcome() == Outcome::Condition(true))
+        .count()
 }
 
 #[cfg(test)]
@@ -122,7 +564,7 @@ mod tests {
 
     #[test]
     fn test_empty_and() {
-        assert_eq!(AndCondition::new(Vec::new()).evaluate(&42), true);
+        assert!(AndCondition::new(Vec::new()).evaluate(&42));
     }
 
     #[test]
@@ -139,7 +581,7 @@ mod tests {
 
     #[test]
     fn test_empty_or() {
-        assert_eq!(OrCondition::new(Vec::new()).evaluate(&42), false);
+        assert!(!OrCondition::new(Vec::new()).evaluate(&42));
     }
 
     #[test]
@@ -154,6 +596,122 @@ mod tests {
         assert_or(true, true, true, true);
     }
 
+    #[test]
+    fn test_xor() {
+        assert_xor(false, false, false, false);
+        assert_xor(true, false, false, true);
+        assert_xor(false, true, false, true);
+        assert_xor(true, true, false, false);
+        assert_xor(false, false, true, true);
+        assert_xor(true, false, true, false);
+        assert_xor(false, true, true, false);
+        assert_xor(true, true, true, true);
+    }
+
+    #[test]
+    fn test_at_least_two() {
+        assert_at_least(2, false, false, false, false);
+        assert_at_least(2, true, false, false, false);
+        assert_at_least(2, false, true, false, false);
+        assert_at_least(2, true, true, false, true);
+        assert_at_least(2, false, false, true, false);
+        assert_at_least(2, true, false, true, true);
+        assert_at_least(2, false, true, true, true);
+        assert_at_least(2, true, true, true, true);
+    }
+
+    #[test]
+    fn test_at_least_zero() {
+        assert!(AtLeastCondition::new(0, Vec::new()).evaluate(&42));
+        assert!(AtLeastCondition::new(0, vec![boxed(false)]).evaluate(&42));
+    }
+
+    #[test]
+    fn test_at_most_one() {
+        assert_at_most(1, false, false, false, true);
+        assert_at_most(1, true, false, false, true);
+        assert_at_most(1, false, true, false, true);
+        assert_at_most(1, true, true, false, false);
+        assert_at_most(1, false, false, true, true);
+        assert_at_most(1, true, false, true, false);
+        assert_at_most(1, false, true, true, false);
+        assert_at_most(1, true, true, true, false);
+    }
+
+    #[test]
+    fn test_exactly_one() {
+        assert_exactly(1, false, false, false, false);
+        assert_exactly(1, true, false, false, true);
+        assert_exactly(1, false, true, false, true);
+        assert_exactly(1, true, true, false, false);
+        assert_exactly(1, false, false, true, true);
+        assert_exactly(1, true, false, true, false);
+        assert_exactly(1, false, true, true, false);
+        assert_exactly(1, true, true, true, false);
+    }
+
+    #[test]
+    fn test_exactly_two() {
+        assert_exactly(2, false, false, false, false);
+        assert_exactly(2, true, false, false, false);
+        assert_exactly(2, false, true, false, false);
+        assert_exactly(2, true, true, false, true);
+        assert_exactly(2, false, false, true, false);
+        assert_exactly(2, true, false, true, true);
+        assert_exactly(2, false, true, true, true);
+        assert_exactly(2, true, true, true, false);
+    }
+
+    #[test]
+    fn test_implies() {
+        assert_implies(false, false, true);
+        assert_implies(true, false, false);
+        assert_implies(false, true, true);
+        assert_implies(true, true, true);
+    }
+
+    #[test]
+    fn test_short_circuit() {
+        assert!(AtLeastCondition::new(1, vec![boxed(true), Box::new(Unreachable)]).evaluate(&42));
+        assert!(
+            !AtLeastCondition::new(2, vec![boxed(false), boxed(false), Box::new(Unreachable)])
+                .evaluate(&42)
+        );
+        assert!(!AtMostCondition::new(0, vec![boxed(true), Box::new(Unreachable)]).evaluate(&42));
+        assert!(!ExactlyCondition::new(0, vec![boxed(true), Box::new(Unreachable)]).evaluate(&42));
+        assert!(ImpliesCondition::new(boxed(false), Box::new(Unreachable)).evaluate(&42));
+    }
+
+    #[test]
+    fn test_explain_at_least() {
+        let at_least = AtLeastCondition::new(2, vec![boxed(true), boxed(false)]);
+
+        assert_eq!(
+            at_least.explain(&42).to_text(),
+            "at least 2 = false\n  MockCondition = true\n  MockCondition = false\n"
+        );
+    }
+
+    #[test]
+    fn test_explain_and() {
+        let and = AndCondition::new(vec![boxed(false), boxed(true)]);
+
+        assert_eq!(
+            and.explain(&42).to_text(),
+            "and = false\n  MockCondition = false\n  MockCondition = true\n"
+        );
+    }
+
+    #[test]
+    fn test_explain_or() {
+        let or = OrCondition::new(vec![boxed(true), boxed(false)]);
+
+        assert_eq!(
+            or.explain(&42).to_text(),
+            "or = true\n  MockCondition = true\n  MockCondition = false\n"
+        );
+    }
+
     fn assert_and(value0: bool, value1: bool, value2: bool, result: bool) {
         let c0 = boxed(value0);
         let c1 = boxed(value1);
@@ -170,6 +728,53 @@ mod tests {
         assert_eq!(OrCondition::new(vec![c0, c1, c2]).evaluate(&42), result);
     }
 
+    fn assert_xor(value0: bool, value1: bool, value2: bool, result: bool) {
+        let conditions = create_conditions(value0, value1, value2);
+
+        assert_eq!(XorCondition::new(conditions).evaluate(&42), result);
+    }
+
+    fn assert_at_least(n: usize, value0: bool, value1: bool, value2: bool, result: bool) {
+        let conditions = create_conditions(value0, value1, value2);
+
+        assert_eq!(AtLeastCondition::new(n, conditions).evaluate(&42), result);
+    }
+
+    fn assert_at_most(n: usize, value0: bool, value1: bool, value2: bool, result: bool) {
+        let conditions = create_conditions(value0, value1, value2);
+
+        assert_eq!(AtMostCondition::new(n, conditions).evaluate(&42), result);
+    }
+
+    fn assert_exactly(n: usize, value0: bool, value1: bool, value2: bool, result: bool) {
+        let conditions = create_conditions(value0, value1, value2);
+
+        assert_eq!(ExactlyCondition::new(n, conditions).evaluate(&42), result);
+    }
+
+    fn assert_implies(premise: bool, conclusion: bool, result: bool) {
+        let implies = ImpliesCondition::new(boxed(premise), boxed(conclusion));
+
+        assert_eq!(implies.evaluate(&42), result);
+        assert_eq!(
+            implies.explain(&42).get_outcome(),
+            Outcome::Condition(result)
+        );
+    }
+
+    fn create_conditions(value0: bool, value1: bool, value2: bool) -> Vec<Box<dyn Condition<i32>>> {
+        vec![boxed(value0), boxed(value1), boxed(value2)]
+    }
+
+    /// A condition that fails the test, if it is evaluated.
+    struct Unreachable;
+
+    impl Condition<i32> for Unreachable {
+        fn evaluate(&self, _: &i32) -> bool {
+            panic!("The condition should not be evaluated!")
+        }
+    }
+
     fn boxed(value: bool) -> Box<MockCondition> {
         Box::new(MockCondition::new(value))
     }
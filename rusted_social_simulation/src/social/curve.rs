@@ -0,0 +1,325 @@
+use crate::social::explanation::Explanation;
+use crate::social::utility::{Utility, UtilityRule};
+use crate::social::value::Value;
+use serde::{Deserialize, Serialize};
+
+/// A curve, that maps an input value to an output value.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(from = "CurveData")]
+pub enum Curve {
+    /// `slope * x + offset`
+    Linear { slope: f32, offset: f32 },
+    /// `a * x² + b * x + c`
+    Quadratic { a: f32, b: f32, c: f32 },
+    /// An s-shaped curve from 0 to 1, that is 0.5 at the midpoint.
+    Logistic { midpoint: f32, steepness: f32 },
+    /// `base^x`, which grows for a base above 1 & decays for a base between 0 & 1.
+    Exponential { base: f32 },
+    /// Interpolates linearly between points, that are sorted by their input.
+    /// Inputs outside of the points use the output of the nearest point.
+    /// Use [`Curve::piecewise`] to sort them.
+    Piecewise(Vec<(f32, f32)>),
+}
+
+/// The serialized curve, whose points may not be sorted yet.
+#[derive(Deserialize)]
+enum CurveData {
+    Linear { slope: f32, offset: f32 },
+    Quadratic { a: f32, b: f32, c: f32 },
+    Logistic { midpoint: f32, steepness: f32 },
+    Exponential { base: f32 },
+    Piecewise(Vec<(f32, f32)>),
+}
+
+impl From<CurveData> for Curve {
+    fn from(data: CurveData) -> Self {
+        match data {
+            CurveData::Linear { slope, offset } => Curve::Linear { slope, offset },
+            CurveData::Quadratic { a, b, c } => Curve::Quadratic { a, b, c },
+            CurveData::Logistic {
+                midpoint,
+                steepness,
+            } => Curve::Logistic {
+                midpoint,
+                steepness,
+            },
+            CurveData::Exponential { base } => Curve::Exponential { base },
+            CurveData::Piecewise(points) => Curve::piecewise(points),
+        }
+    }
+}
+
+impl Curve {
+    /// Creates a piecewise curve & sorts the points by their input.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::curve::Curve;
+    /// let curve = Curve::piecewise(vec![(10.0, 5.0), (0.0, 0.0)]);
+    ///
+    /// assert_eq!(curve, Curve::Piecewise(vec![(0.0, 0.0), (10.0, 5.0)]));
+    /// assert_eq!(curve.evaluate(4.0), 2.0);
+    /// ```
+    pub fn piecewise(mut points: Vec<(f32, f32)>) -> Curve {
+        points.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+        Curve::Piecewise(points)
+    }
+
+    /// Maps the input to the output.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::curve::Curve;
+    /// assert_eq!(Curve::Linear { slope: 2.0, offset: 1.0 }.evaluate(3.0), 7.0);
+    /// assert_eq!(Curve::Quadratic { a: 1.0, b: 0.0, c: -1.0 }.evaluate(3.0), 8.0);
+    /// assert_eq!(Curve::Logistic { midpoint: 5.0, steepness: 1.0 }.evaluate(5.0), 0.5);
+    /// assert_eq!(Curve::Exponential { base: 2.0 }.evaluate(3.0), 8.0);
+    /// assert_eq!(Curve::Piecewise(vec![(0.0, 0.0), (10.0, 5.0)]).evaluate(4.0), 2.0);
+    /// ```
+    pub fn evaluate(&self, x: f32) -> f32 {
+        match self {
+            Curve::Linear { slope, offset } => slope * x + offset,
+            Curve::Quadratic { a, b, c } => a * x * x + b * x + c,
+            Curve::Logistic {
+                midpoint,
+                steepness,
+            } => 1.0 / (1.0 + (-steepness * (x - midpoint)).exp()),
+            Curve::Exponential { base } => base.powf(x),
+            Curve::Piecewise(points) => interpolate(points, x),
+        }
+    }
+}
+
+fn interpolate(points: &[(f32, f32)], x: f32) -> f32 {
+    let index = points.partition_point(|(point_x, _)| *point_x <= x);
+
+    match (
+        index.checked_sub(1).map(|i| points[i]),
+        points.get(index).copied(),
+    ) {
+        (Some((x0, y0)), Some((x1, y1))) => y0 + (y1 - y0) * (x - x0) / (x1 - x0),
+        (Some((_, y)), None) | (None, Some((_, y))) => y,
+        (None, None) => 0.0,
+    }
+}
+
+/// Maps an input value to an utility with a curve, whose output is scaled & clamped.
+///
+/// ```
+///# use rusted_social_simulation::social::curve::{Curve, ResponseCurve};
+///# use rusted_social_simulation::social::utility::Utility;
+/// let mut response = ResponseCurve::new(Curve::Logistic { midpoint: 50.0, steepness: 0.1 });
+/// response.set_scale(100.0);
+/// response.set_clamp(Utility::from_integer(10), Utility::from_integer(90));
+///
+/// assert_eq!(response.get_utility(Utility::from_integer(0)), Utility::from_integer(10));
+/// assert_eq!(response.get_utility(Utility::from_integer(50)), Utility::from_integer(50));
+/// assert_eq!(response.get_utility(Utility::from_integer(60)).to_f32().round(), 73.0);
+/// assert_eq!(response.get_utility(Utility::from_integer(100)), Utility::from_integer(90));
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ResponseCurve {
+    curve: Curve,
+    #[serde(default = "default_scale")]
+    scale: f32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    clamp: Option<(Utility, Utility)>,
+}
+
+fn default_scale() -> f32 {
+    1.0
+}
+
+impl ResponseCurve {
+    /// Creates a response curve without scaling & clamping.
+    pub fn new(curve: Curve) -> ResponseCurve {
+        ResponseCurve {
+            curve,
+            scale: default_scale(),
+            clamp: None,
+        }
+    }
+
+    pub fn get_curve(&self) -> &Curve {
+        &self.curve
+    }
+
+    pub fn get_scale(&self) -> f32 {
+        self.scale
+    }
+
+    /// Multiplies the output of the curve, e.g. to map a logistic curve from 0 to 100.
+    pub fn set_scale(&mut self, scale: f32) {
+        self.scale = scale;
+    }
+
+    pub fn get_clamp(&self) -> Option<(Utility, Utility)> {
+        self.clamp
+    }
+
+    /// Limits the utility to a range.
+    pub fn set_clamp(&mut self, min: Utility, max: Utility) {
+        self.clamp = Some((min, max));
+    }
+
+    /// Gets the scaled & clamped output of the curve for the input.
+    pub fn get_utility(&self, input: Utility) -> Utility {
+        let output = self.curve.evaluate(input.to_f32()) * self.scale;
+        let utility = Utility::from_f32(output);
+
+        match self.clamp {
+            Some((min, _)) if utility < min => min,
+            Some((_, max)) if utility > max => max,
+            _ => utility,
+        }
+    }
+}
+
+/// An utility rule, that maps an input value with a [`ResponseCurve`].
+pub struct CurveUtility<T> {
+    input: Value<T>,
+    response: ResponseCurve,
+}
+
+impl<T> CurveUtility<T> {
+    pub fn new(input: Value<T>, response: ResponseCurve) -> CurveUtility<T> {
+        CurveUtility { input, response }
+    }
+}
+
+impl<T> UtilityRule<T> for CurveUtility<T> {
+    /// Returns the utility of the response curve for the input value.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::curve::{Curve, CurveUtility, ResponseCurve};
+    ///# use rusted_social_simulation::social::utility::{Utility, UtilityRule};
+    ///# use rusted_social_simulation::social::value::Value;
+    /// let curve = Curve::Linear { slope: -1.0, offset: 100.0 };
+    /// let hunger = Value::getter(|hunger: &i32| Utility::from_integer(*hunger));
+    /// let rule = CurveUtility::new(hunger, ResponseCurve::new(curve));
+    ///
+    /// assert_eq!(rule.calculate_utility(&30), Utility::from_integer(70));
+    /// ```
+    fn calculate_utility(&self, context: &T) -> Utility {
+        self.response.get_utility(self.input.get_value(context))
+    }
+
+    /// Explains the input value.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::curve::{Curve, CurveUtility, ResponseCurve};
+    ///# use rusted_social_simulation::social::utility::{Utility, UtilityRule};
+    ///# use rusted_social_simulation::social::value::Value;
+    /// let input = Value::Constant(Utility::from_integer(3));
+    /// let rule = CurveUtility::new(input, ResponseCurve::new(Curve::Exponential { base: 2.0 }));
+    ///
+    /// assert_eq!(rule.explain(&42).to_text(), "curve = 8\n  constant = 3\n");
+    /// ```
+    fn explain(&self, context: &T) -> Explanation {
+        let input = self.input.explain(context);
+        Explanation::utility("curve", self.calculate_utility(context), vec![input])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_piecewise() {
+        let curve = Curve::Piecewise(vec![(0.0, 10.0), (10.0, 20.0), (20.0, 0.0)]);
+
+        assert_eq!(curve.evaluate(-5.0), 10.0);
+        assert_eq!(curve.evaluate(0.0), 10.0);
+        assert_eq!(curve.evaluate(5.0), 15.0);
+        assert_eq!(curve.evaluate(10.0), 20.0);
+        assert_eq!(curve.evaluate(15.0), 10.0);
+        assert_eq!(curve.evaluate(20.0), 0.0);
+        assert_eq!(curve.evaluate(25.0), 0.0);
+    }
+
+    #[test]
+    fn test_deserialize_unsorted_piecewise() {
+        let curve: Curve = ron::from_str("Piecewise([(20, 0), (0, 10), (10, 20)])").unwrap();
+
+        assert_eq!(
+            curve,
+            Curve::Piecewise(vec![(0.0, 10.0), (10.0, 20.0), (20.0, 0.0)])
+        );
+        assert_eq!(curve.evaluate(5.0), 15.0);
+        assert_eq!(curve.evaluate(15.0), 10.0);
+    }
+
+    #[test]
+    fn test_piecewise_without_points() {
+        assert_eq!(Curve::Piecewise(Vec::new()).evaluate(5.0), 0.0);
+    }
+
+    #[test]
+    fn test_logistic() {
+        let curve = Curve::Logistic {
+            midpoint: 0.0,
+            steepness: 2.0,
+        };
+
+        assert!(curve.evaluate(-10.0) < 0.001);
+        assert!(curve.evaluate(10.0) > 0.999);
+        assert!(curve.evaluate(1.0) > curve.evaluate(0.5));
+    }
+
+    #[test]
+    fn test_exponential_decay() {
+        let response = ResponseCurve::new(Curve::Exponential { base: 0.5 });
+
+        assert_eq!(
+            response.get_utility(Utility::from_integer(-2)),
+            Utility::from_integer(4)
+        );
+        assert_eq!(
+            response.get_utility(Utility::default()),
+            Utility::from_integer(1)
+        );
+        assert_eq!(
+            response.get_utility(Utility::from_integer(10)),
+            Utility::from_f32(0.5f32.powi(10))
+        );
+    }
+
+    #[test]
+    fn test_scale_and_clamp() {
+        let mut response = ResponseCurve::new(Curve::Quadratic {
+            a: 1.0,
+            b: 0.0,
+            c: 0.0,
+        });
+        response.set_scale(-0.5);
+        response.set_clamp(Utility::from_integer(-20), Utility::default());
+
+        assert_eq!(
+            response.get_utility(Utility::from_integer(3)),
+            Utility::from_f32(-4.5)
+        );
+        assert_eq!(
+            response.get_utility(Utility::from_integer(10)),
+            Utility::from_integer(-20)
+        );
+    }
+
+    #[test]
+    fn test_ron() {
+        let text = "(curve: Piecewise([(0, 0), (10, 50)]), clamp: Some((0, 40)))";
+        let response: ResponseCurve = ron::from_str(text).unwrap();
+
+        assert_eq!(response.get_scale(), 1.0);
+        assert_eq!(
+            response.get_utility(Utility::from_integer(5)),
+            Utility::from_integer(25)
+        );
+        assert_eq!(
+            response.get_utility(Utility::from_integer(9)),
+            Utility::from_integer(40)
+        );
+        assert_eq!(
+            ron::from_str::<ResponseCurve>(&ron::to_string(&response).unwrap()).unwrap(),
+            response
+        );
+    }
+}
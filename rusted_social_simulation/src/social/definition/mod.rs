@@ -0,0 +1,337 @@
+use crate::social::curve::ResponseCurve;
+use crate::social::error::SocialError;
+use crate::social::practice::role::{Cardinality, Role};
+use crate::social::utility::Utility;
+use crate::social::value::{Comparison, Operator};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+pub mod registry;
+
+/// The definition of a practice template, e.g. loaded from a file.
+///
+/// The optional stages are only used by [`registry::DefinitionRegistry::create_staged_template`].
+///
+/// ```
+///# use rusted_social_simulation::social::definition::PracticeTemplateDefinition;
+///# use rusted_social_simulation::social::practice::role::Role;
+/// let definition = PracticeTemplateDefinition::from_ron(r#"(
+///     id: 3,
+///     name: "greeting",
+///     roles: [
+///         (role: "character:0", name: "Greeter", actions: [(name: "wave")]),
+///         (role: "character:1", name: "Greeted"),
+///     ],
+/// )"#).unwrap();
+///
+/// assert_eq!(definition.id, 3);
+/// assert_eq!(definition.roles[0].role, Role::Character { id: 0 });
+/// assert_eq!(definition.roles[0].actions[0].name, "wave");
+/// assert!(definition.roles[1].actions.is_empty());
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PracticeTemplateDefinition {
+    pub id: u32,
+    pub name: String,
+    pub roles: Vec<RoleDefinition>,
+    #[serde(default)]
+    pub stages: Vec<StageDefinition>,
+}
+
+impl PracticeTemplateDefinition {
+    /// Parses a definition in the RON format.
+    pub fn from_ron(text: &str) -> Result<PracticeTemplateDefinition, SocialError> {
+        ron::de::from_str(text).map_err(|error| SocialError::InvalidDefinition {
+            message: error.to_string(),
+        })
+    }
+
+    /// Parses a definition in the JSON format.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::definition::PracticeTemplateDefinition;
+    /// let definition = PracticeTemplateDefinition::from_json(r#"{
+    ///     "id": 3,
+    ///     "name": "greeting",
+    ///     "roles": [{ "role": "character:0", "name": "Greeter" }]
+    /// }"#).unwrap();
+    ///
+    /// assert_eq!(definition.name, "greeting");
+    /// ```
+    pub fn from_json(text: &str) -> Result<PracticeTemplateDefinition, SocialError> {
+        serde_json::from_str(text).map_err(|error| SocialError::InvalidDefinition {
+            message: error.to_string(),
+        })
+    }
+}
+
+/// The definition of a role in a practice template & its actions.
+///
+/// By default a role needs a single entity.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RoleDefinition {
+    pub role: Role,
+    pub name: String,
+    #[serde(default)]
+    pub cardinality: Cardinality,
+    #[serde(default)]
+    pub actions: Vec<ActionDefinition>,
+}
+
+/// The definition of a stage of a practice.
+///
+/// The actions are referenced by their names & must be defined by the roles.
+/// The first stage is the initial one.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StageDefinition {
+    pub name: String,
+    #[serde(default)]
+    pub actions: Vec<String>,
+    #[serde(default)]
+    pub transitions: Vec<TransitionDefinition>,
+    #[serde(default)]
+    pub is_terminal: bool,
+}
+
+/// The definition of a transition to another stage.
+///
+/// Without a condition the transition is always taken.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TransitionDefinition {
+    pub target: String,
+    #[serde(default = "default_condition")]
+    pub condition: ConditionDefinition,
+}
+
+/// The definition of an action.
+///
+/// Without a condition, utility or effect the action is always available,
+/// has an utility of 0 and does nothing.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ActionDefinition {
+    pub name: String,
+    #[serde(default = "default_condition")]
+    pub condition: ConditionDefinition,
+    #[serde(default = "default_utility")]
+    pub utility: UtilityDefinition,
+    #[serde(default = "default_effect")]
+    pub effect: EffectDefinition,
+}
+
+fn default_condition() -> ConditionDefinition {
+    ConditionDefinition::Constant(true)
+}
+
+fn default_utility() -> UtilityDefinition {
+    UtilityDefinition::Fixed(Utility::default())
+}
+
+fn default_effect() -> EffectDefinition {
+    EffectDefinition::Nothing
+}
+
+/// The definition of a condition.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ConditionDefinition {
+    Constant(bool),
+    Not(Box<ConditionDefinition>),
+    And(Vec<ConditionDefinition>),
+    Or(Vec<ConditionDefinition>),
+    Xor(Vec<ConditionDefinition>),
+    AtLeast {
+        n: usize,
+        conditions: Vec<ConditionDefinition>,
+    },
+    AtMost {
+        n: usize,
+        conditions: Vec<ConditionDefinition>,
+    },
+    Exactly {
+        n: usize,
+        conditions: Vec<ConditionDefinition>,
+    },
+    Implies {
+        premise: Box<ConditionDefinition>,
+        conclusion: Box<ConditionDefinition>,
+    },
+    /// Compares 2 values, e.g. `Compare(left: Custom(name: "money"), comparison: GreaterOrEqual, right: Constant(10))`.
+    Compare {
+        left: Box<ValueDefinition>,
+        comparison: Comparison,
+        right: Box<ValueDefinition>,
+    },
+    /// A domain-specific condition created by a registered constructor.
+    Custom {
+        name: String,
+        #[serde(default)]
+        parameters: Parameters,
+    },
+}
+
+/// The definition of an utility rule.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum UtilityDefinition {
+    Fixed(Utility),
+    Conditional {
+        condition: ConditionDefinition,
+        utility: Utility,
+    },
+    Total(Vec<UtilityDefinition>),
+    Max(Vec<UtilityDefinition>),
+    Min(Vec<UtilityDefinition>),
+    Product {
+        rules: Vec<UtilityDefinition>,
+        #[serde(default)]
+        compensation: bool,
+    },
+    WeightedAverage(Vec<(f32, UtilityDefinition)>),
+    Normalized {
+        rule: Box<UtilityDefinition>,
+        min: Utility,
+        max: Utility,
+    },
+    /// Maps an input value with a response curve,
+    /// e.g. `Curve(input: Custom(name: "hunger"), response: (curve: Linear(slope: 2, offset: 0), clamp: Some((0, 100))))`.
+    Curve {
+        input: Box<ValueDefinition>,
+        response: ResponseCurve,
+    },
+    /// A domain-specific utility rule created by a registered constructor.
+    Custom {
+        name: String,
+        #[serde(default)]
+        parameters: Parameters,
+    },
+}
+
+/// The definition of a value, that can be compared.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ValueDefinition {
+    Constant(Utility),
+    Utility(UtilityDefinition),
+    Arithmetic {
+        left: Box<ValueDefinition>,
+        operator: Operator,
+        right: Box<ValueDefinition>,
+    },
+    /// A domain-specific getter created by a registered constructor.
+    Custom {
+        name: String,
+        #[serde(default)]
+        parameters: Parameters,
+    },
+}
+
+/// The definition of an effect.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum EffectDefinition {
+    Nothing,
+    Vector(Vec<EffectDefinition>),
+    /// A domain-specific effect created by a registered constructor.
+    Custom {
+        name: String,
+        #[serde(default)]
+        parameters: Parameters,
+    },
+}
+
+/// A parameter of a domain-specific condition, utility rule or effect.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Parameter {
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    Text(String),
+}
+
+/// The named parameters of a domain-specific condition, utility rule or effect.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Parameters {
+    parameters: BTreeMap<String, Parameter>,
+}
+
+impl Parameters {
+    pub fn new(parameters: BTreeMap<String, Parameter>) -> Parameters {
+        Parameters { parameters }
+    }
+
+    /// Gets a parameter.
+    pub fn get(&self, name: &str) -> Option<&Parameter> {
+        self.parameters.get(name)
+    }
+
+    /// Gets a boolean parameter.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::definition::{Parameter, Parameters};
+    ///# use rusted_social_simulation::social::error::SocialError;
+    /// let parameters = Parameters::new(vec![("a".to_string(), Parameter::Bool(true))].into_iter().collect());
+    ///
+    /// assert_eq!(parameters.get_bool("a"), Ok(true));
+    /// assert_eq!(parameters.get_bool("b"), Err(SocialError::InvalidParameter { name: "b".to_string() }));
+    /// ```
+    pub fn get_bool(&self, name: &str) -> Result<bool, SocialError> {
+        match self.parameters.get(name) {
+            Some(Parameter::Bool(value)) => Ok(*value),
+            _ => Err(invalid(name)),
+        }
+    }
+
+    /// Gets an integer parameter.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::definition::{Parameter, Parameters};
+    ///# use rusted_social_simulation::social::error::SocialError;
+    /// let parameters = Parameters::new(vec![("a".to_string(), Parameter::Integer(5))].into_iter().collect());
+    ///
+    /// assert_eq!(parameters.get_integer("a"), Ok(5));
+    /// ```
+    pub fn get_integer(&self, name: &str) -> Result<i64, SocialError> {
+        match self.parameters.get(name) {
+            Some(Parameter::Integer(value)) => Ok(*value),
+            _ => Err(invalid(name)),
+        }
+    }
+
+    /// Gets a floating-point parameter. Integers are converted.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::definition::{Parameter, Parameters};
+    ///# use rusted_social_simulation::social::error::SocialError;
+    /// let parameters = Parameters::new(vec![("a".to_string(), Parameter::Integer(5))].into_iter().collect());
+    ///
+    /// assert_eq!(parameters.get_float("a"), Ok(5.0));
+    /// ```
+    pub fn get_float(&self, name: &str) -> Result<f64, SocialError> {
+        match self.parameters.get(name) {
+            Some(Parameter::Float(value)) => Ok(*value),
+            Some(Parameter::Integer(value)) => Ok(*value as f64),
+            _ => Err(invalid(name)),
+        }
+    }
+
+    /// Gets a text parameter.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::definition::{Parameter, Parameters};
+    ///# use rusted_social_simulation::social::error::SocialError;
+    /// let parameters = Parameters::new(vec![("a".to_string(), Parameter::Text("b".to_string()))].into_iter().collect());
+    ///
+    /// assert_eq!(parameters.get_text("a"), Ok("b"));
+    /// assert_eq!(parameters.get_integer("a"), Err(SocialError::InvalidParameter { name: "a".to_string() }));
+    /// ```
+    pub fn get_text(&self, name: &str) -> Result<&str, SocialError> {
+        match self.parameters.get(name) {
+            Some(Parameter::Text(value)) => Ok(value),
+            _ => Err(invalid(name)),
+        }
+    }
+}
+
+fn invalid(name: &str) -> SocialError {
+    SocialError::InvalidParameter {
+        name: name.to_string(),
+    }
+}
@@ -0,0 +1,790 @@
+use crate::social::action::{Action, SimpleAction};
+use crate::social::condition::{
+    AndCondition, AtLeastCondition, AtMostCondition, CompareCondition, Condition, ExactlyCondition,
+    ImpliesCondition, MockCondition, NotCondition, OrCondition, XorCondition,
+};
+use crate::social::curve::CurveUtility;
+use crate::social::definition::*;
+use crate::social::effect::{DoNothing, Effect, EffectVector};
+use crate::social::error::SocialError;
+use crate::social::practice::role::Role;
+use crate::social::practice::simple::SimplePracticeTemplate;
+use crate::social::practice::staged::{Stage, StagedPracticeTemplate, Transition};
+use crate::social::practice::RoleActions;
+use crate::social::utility::{
+    ConditionalUtility, FixedUtility, MaxUtility, MinUtility, NormalizedUtility, ProductUtility,
+    TotalUtility, UtilityRule, WeightedAverageUtility,
+};
+use crate::social::value::{Getter, Value};
+use std::collections::HashMap;
+
+type ConditionConstructor<T> =
+    Box<dyn Fn(&Parameters) -> Result<Box<dyn Condition<T>>, SocialError>>;
+type UtilityConstructor<T> =
+    Box<dyn Fn(&Parameters) -> Result<Box<dyn UtilityRule<T>>, SocialError>>;
+type ValueConstructor<T> = Box<dyn Fn(&Parameters) -> Result<Getter<T>, SocialError>>;
+type EffectConstructor<T> = Box<dyn Fn(&Parameters) -> Result<Box<dyn Effect<T>>, SocialError>>;
+
+/// Creates practice templates & their actions from definitions.
+///
+/// The generic parts like [`AndCondition`] are created directly,
+/// while the domain-specific leaves are created by registered constructors.
+pub struct DefinitionRegistry<T> {
+    conditions: HashMap<String, ConditionConstructor<T>>,
+    utilities: HashMap<String, UtilityConstructor<T>>,
+    values: HashMap<String, ValueConstructor<T>>,
+    effects: HashMap<String, EffectConstructor<T>>,
+}
+
+impl<T: 'static> DefinitionRegistry<T> {
+    pub fn new() -> DefinitionRegistry<T> {
+        DefinitionRegistry {
+            conditions: HashMap::new(),
+            utilities: HashMap::new(),
+            values: HashMap::new(),
+            effects: HashMap::new(),
+        }
+    }
+
+    /// Registers the constructor of a domain-specific condition.
+    pub fn register_condition<F>(&mut self, name: &str, constructor: F)
+    where
+        F: Fn(&Parameters) -> Result<Box<dyn Condition<T>>, SocialError> + 'static,
+    {
+        self.conditions
+            .insert(name.to_string(), Box::new(constructor));
+    }
+
+    /// Registers the constructor of a domain-specific utility rule.
+    pub fn register_utility<F>(&mut self, name: &str, constructor: F)
+    where
+        F: Fn(&Parameters) -> Result<Box<dyn UtilityRule<T>>, SocialError> + 'static,
+    {
+        self.utilities
+            .insert(name.to_string(), Box::new(constructor));
+    }
+
+    /// Registers the constructor of a domain-specific getter of a value.
+    pub fn register_value<F>(&mut self, name: &str, constructor: F)
+    where
+        F: Fn(&Parameters) -> Result<Getter<T>, SocialError> + 'static,
+    {
+        self.values.insert(name.to_string(), Box::new(constructor));
+    }
+
+    /// Registers the constructor of a domain-specific effect.
+    pub fn register_effect<F>(&mut self, name: &str, constructor: F)
+    where
+        F: Fn(&Parameters) -> Result<Box<dyn Effect<T>>, SocialError> + 'static,
+    {
+        self.effects.insert(name.to_string(), Box::new(constructor));
+    }
+
+    /// Creates a condition from its definition.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::definition::ConditionDefinition;
+    ///# use rusted_social_simulation::social::definition::registry::DefinitionRegistry;
+    ///# use rusted_social_simulation::social::error::SocialError;
+    /// let registry: DefinitionRegistry<u32> = DefinitionRegistry::new();
+    /// let definition = ConditionDefinition::Not(Box::new(ConditionDefinition::Constant(false)));
+    /// let unknown = ConditionDefinition::Custom { name: "unknown".to_string(), parameters: Default::default() };
+    ///
+    /// assert!(registry.create_condition(&definition).unwrap().evaluate(&42));
+    /// assert_eq!(
+    ///     registry.create_condition(&unknown).err(),
+    ///     Some(SocialError::UnknownConstructor { name: "unknown".to_string() })
+    /// );
+    /// ```
+    pub fn create_condition(
+        &self,
+        definition: &ConditionDefinition,
+    ) -> Result<Box<dyn Condition<T>>, SocialError> {
+        Ok(match definition {
+            ConditionDefinition::Constant(value) => Box::new(MockCondition::new(*value)),
+            ConditionDefinition::Not(condition) => {
+                Box::new(NotCondition::new(self.create_condition(condition)?))
+            }
+            ConditionDefinition::And(conditions) => {
+                Box::new(AndCondition::new(self.create_conditions(conditions)?))
+            }
+            ConditionDefinition::Or(conditions) => {
+                Box::new(OrCondition::new(self.create_conditions(conditions)?))
+            }
+            ConditionDefinition::Xor(conditions) => {
+                Box::new(XorCondition::new(self.create_conditions(conditions)?))
+            }
+            ConditionDefinition::AtLeast { n, conditions } => Box::new(AtLeastCondition::new(
+                *n,
+                self.create_conditions(conditions)?,
+            )),
+            ConditionDefinition::AtMost { n, conditions } => Box::new(AtMostCondition::new(
+                *n,
+                self.create_conditions(conditions)?,
+            )),
+            ConditionDefinition::Exactly { n, conditions } => Box::new(ExactlyCondition::new(
+                *n,
+                self.create_conditions(conditions)?,
+            )),
+            ConditionDefinition::Implies {
+                premise,
+                conclusion,
+            } => Box::new(ImpliesCondition::new(
+                self.create_condition(premise)?,
+                self.create_condition(conclusion)?,
+            )),
+            ConditionDefinition::Compare {
+                left,
+                comparison,
+                right,
+            } => Box::new(CompareCondition::new(
+                self.create_value(left)?,
+                *comparison,
+                self.create_value(right)?,
+            )),
+            ConditionDefinition::Custom { name, parameters } => {
+                let constructor = self.conditions.get(name).ok_or_else(|| unknown(name))?;
+                constructor(parameters)?
+            }
+        })
+    }
+
+    fn create_conditions(
+        &self,
+        definitions: &[ConditionDefinition],
+    ) -> Result<Vec<Box<dyn Condition<T>>>, SocialError> {
+        definitions
+            .iter()
+            .map(|definition| self.create_condition(definition))
+            .collect()
+    }
+
+    /// Creates an utility rule from its definition.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::definition::UtilityDefinition;
+    ///# use rusted_social_simulation::social::definition::registry::DefinitionRegistry;
+    ///# use rusted_social_simulation::social::utility::Utility;
+    /// let registry: DefinitionRegistry<u32> = DefinitionRegistry::new();
+    /// let definition = UtilityDefinition::Total(vec![
+    ///     UtilityDefinition::Fixed(Utility::from_integer(3)),
+    ///     UtilityDefinition::Fixed(Utility::from_integer(4)),
+    /// ]);
+    ///
+    /// assert_eq!(registry.create_utility(&definition).unwrap().calculate_utility(&42), Utility::from_integer(7));
+    /// ```
+    pub fn create_utility(
+        &self,
+        definition: &UtilityDefinition,
+    ) -> Result<Box<dyn UtilityRule<T>>, SocialError> {
+        Ok(match definition {
+            UtilityDefinition::Fixed(utility) => Box::new(FixedUtility::new(*utility)),
+            UtilityDefinition::Conditional { condition, utility } => Box::new(
+                ConditionalUtility::new(self.create_condition(condition)?, *utility),
+            ),
+            UtilityDefinition::Total(rules) => {
+                Box::new(TotalUtility::new(self.create_utilities(rules)?))
+            }
+            UtilityDefinition::Max(rules) => {
+                Box::new(MaxUtility::new(self.create_utilities(rules)?))
+            }
+            UtilityDefinition::Min(rules) => {
+                Box::new(MinUtility::new(self.create_utilities(rules)?))
+            }
+            UtilityDefinition::Product {
+                rules,
+                compensation,
+            } => {
+                let mut product = ProductUtility::new(self.create_utilities(rules)?);
+                product.set_compensation(*compensation);
+                Box::new(product)
+            }
+            UtilityDefinition::WeightedAverage(rules) => {
+                let rules: Result<Vec<_>, SocialError> = rules
+                    .iter()
+                    .map(|(weight, rule)| Ok((*weight, self.create_utility(rule)?)))
+                    .collect();
+                Box::new(WeightedAverageUtility::new(rules?))
+            }
+            UtilityDefinition::Normalized { rule, min, max } => Box::new(NormalizedUtility::new(
+                self.create_utility(rule)?,
+                *min,
+                *max,
+            )),
+            UtilityDefinition::Curve { input, response } => Box::new(CurveUtility::new(
+                self.create_value(input)?,
+                response.clone(),
+            )),
+            UtilityDefinition::Custom { name, parameters } => {
+                let constructor = self.utilities.get(name).ok_or_else(|| unknown(name))?;
+                constructor(parameters)?
+            }
+        })
+    }
+
+    fn create_utilities(
+        &self,
+        definitions: &[UtilityDefinition],
+    ) -> Result<Vec<Box<dyn UtilityRule<T>>>, SocialError> {
+        definitions
+            .iter()
+            .map(|definition| self.create_utility(definition))
+            .collect()
+    }
+
+    /// Creates a value from its definition.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::definition::ValueDefinition;
+    ///# use rusted_social_simulation::social::definition::registry::DefinitionRegistry;
+    ///# use rusted_social_simulation::social::utility::Utility;
+    /// let mut registry: DefinitionRegistry<u32> = DefinitionRegistry::new();
+    /// registry.register_value("context", |_| Ok(Box::new(|context: &u32| Utility::from_integer(*context as i32))));
+    /// let definition: ValueDefinition =
+    ///     ron::from_str("Arithmetic(left: Custom(name: \"context\"), operator: Subtract, right: Constant(2))").unwrap();
+    ///
+    /// assert_eq!(registry.create_value(&definition).unwrap().get_value(&42), Utility::from_integer(40));
+    /// ```
+    pub fn create_value(&self, definition: &ValueDefinition) -> Result<Value<T>, SocialError> {
+        Ok(match definition {
+            ValueDefinition::Constant(value) => Value::Constant(*value),
+            ValueDefinition::Utility(utility) => Value::Utility(self.create_utility(utility)?),
+            ValueDefinition::Arithmetic {
+                left,
+                operator,
+                right,
+            } => Value::arithmetic(
+                self.create_value(left)?,
+                *operator,
+                self.create_value(right)?,
+            ),
+            ValueDefinition::Custom { name, parameters } => {
+                let constructor = self.values.get(name).ok_or_else(|| unknown(name))?;
+                Value::Getter(constructor(parameters)?)
+            }
+        })
+    }
+
+    /// Creates an effect from its definition.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::definition::{EffectDefinition, Parameters};
+    ///# use rusted_social_simulation::social::definition::registry::DefinitionRegistry;
+    ///# use rusted_social_simulation::social::effect::MockEffect;
+    /// let mut registry: DefinitionRegistry<u32> = DefinitionRegistry::new();
+    /// registry.register_effect("add", |parameters| {
+    ///     Ok(Box::new(MockEffect::new(parameters.get_integer("value")? as u32)))
+    /// });
+    /// let definition = EffectDefinition::Custom {
+    ///     name: "add".to_string(),
+    ///     parameters: serde_json::from_str(r#"{ "value": 3 }"#).unwrap(),
+    /// };
+    /// let mut context = 42;
+    ///
+    /// registry.create_effect(&definition).unwrap().apply(&mut context);
+    ///
+    /// assert_eq!(context, 45);
+    /// ```
+    pub fn create_effect(
+        &self,
+        definition: &EffectDefinition,
+    ) -> Result<Box<dyn Effect<T>>, SocialError> {
+        Ok(match definition {
+            EffectDefinition::Nothing => Box::new(DoNothing),
+            EffectDefinition::Vector(effects) => {
+                let effects: Result<Vec<Box<dyn Effect<T>>>, SocialError> = effects
+                    .iter()
+                    .map(|definition| self.create_effect(definition))
+                    .collect();
+                Box::new(EffectVector::new(effects?))
+            }
+            EffectDefinition::Custom { name, parameters } => {
+                let constructor = self.effects.get(name).ok_or_else(|| unknown(name))?;
+                constructor(parameters)?
+            }
+        })
+    }
+
+    /// Creates an action from its definition.
+    pub fn create_action(
+        &self,
+        definition: &ActionDefinition,
+    ) -> Result<Box<dyn Action<T>>, SocialError> {
+        Ok(Box::new(SimpleAction::new(
+            definition.name.clone(),
+            self.create_condition(&definition.condition)?,
+            self.create_utility(&definition.utility)?,
+            self.create_effect(&definition.effect)?,
+        )))
+    }
+
+    /// Creates a practice template from its definition.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::definition::PracticeTemplateDefinition;
+    ///# use rusted_social_simulation::social::definition::registry::DefinitionRegistry;
+    ///# use rusted_social_simulation::social::practice::role::{Cardinality, Role};
+    ///# use rusted_social_simulation::social::practice::PracticeTemplate;
+    ///# use rusted_social_simulation::social::utility::Utility;
+    /// let definition = PracticeTemplateDefinition::from_ron(r#"(
+    ///     id: 3,
+    ///     name: "greeting",
+    ///     roles: [
+    ///         (role: "character:0", name: "Greeter", actions: [(name: "wave", utility: Fixed(5))]),
+    ///         (role: "character:1", name: "Greeted", cardinality: (min: 1, max: None)),
+    ///     ],
+    /// )"#).unwrap();
+    /// let registry: DefinitionRegistry<u32> = DefinitionRegistry::new();
+    ///
+    /// let template = registry.create_template(&definition).unwrap();
+    ///
+    /// assert_eq!(template.get_name(), "greeting");
+    /// assert_eq!(template.get_role_name(Role::Character { id: 1 }), "Greeted");
+    /// assert_eq!(template.get_cardinality(Role::Character { id: 1 }), Cardinality::new(1, None));
+    /// assert_eq!(template.get_actions(Role::Character { id: 0 })[0].get_utility(&42), Utility::from_integer(5));
+    /// ```
+    pub fn create_template(
+        &self,
+        definition: &PracticeTemplateDefinition,
+    ) -> Result<SimplePracticeTemplate<T>, SocialError> {
+        let role_names = get_role_names(definition)?;
+        let mut actions = HashMap::new();
+
+        for role_definition in &definition.roles {
+            let role_actions: Result<Vec<Box<dyn Action<T>>>, SocialError> = role_definition
+                .actions
+                .iter()
+                .map(|action| self.create_action(action))
+                .collect();
+            actions.insert(role_definition.role, role_actions?);
+        }
+
+        let mut template = SimplePracticeTemplate::new(
+            definition.id,
+            definition.name.clone(),
+            role_names,
+            actions,
+        );
+
+        for role_definition in &definition.roles {
+            template.set_cardinality(role_definition.role, role_definition.cardinality);
+        }
+
+        Ok(template)
+    }
+
+    /// Creates a practice template with stages from its definition.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::definition::PracticeTemplateDefinition;
+    ///# use rusted_social_simulation::social::definition::registry::DefinitionRegistry;
+    ///# use rusted_social_simulation::social::practice::role::Role;
+    ///# use rusted_social_simulation::social::practice::PracticeTemplate;
+    ///# use rusted_social_simulation::social::utility::Utility;
+    /// let definition = PracticeTemplateDefinition::from_ron(r#"(
+    ///     id: 3,
+    ///     name: "greeting",
+    ///     roles: [(role: "character:0", name: "Greeter", actions: [(name: "wave"), (name: "leave")])],
+    ///     stages: [
+    ///         (name: "start", actions: ["wave"], transitions: [(target: "end", condition: Constant(true))]),
+    ///         (name: "end", is_terminal: true),
+    ///     ],
+    /// )"#).unwrap();
+    /// let registry: DefinitionRegistry<u32> = DefinitionRegistry::new();
+    ///
+    /// let template = registry.create_staged_template(&definition).unwrap();
+    ///
+    /// assert_eq!(template.get_stages().len(), 2);
+    /// assert_eq!(template.get_stages()[0].get_actions(Role::Character { id: 0 })[0].get_name(), "wave");
+    /// assert!(template.get_stages()[1].is_terminal());
+    /// ```
+    pub fn create_staged_template(
+        &self,
+        definition: &PracticeTemplateDefinition,
+    ) -> Result<StagedPracticeTemplate<T>, SocialError> {
+        let role_names = get_role_names(definition)?;
+        let mut stages = Vec::new();
+
+        for stage_definition in &definition.stages {
+            if stage_definition.is_terminal {
+                if !stage_definition.actions.is_empty() || !stage_definition.transitions.is_empty()
+                {
+                    return Err(SocialError::InvalidDefinition {
+                        message: format!(
+                            "Terminal stage '{}' can't have actions or transitions!",
+                            stage_definition.name
+                        ),
+                    });
+                }
+
+                stages.push(Stage::terminal(&stage_definition.name));
+                continue;
+            }
+
+            let actions = self.create_stage_actions(definition, stage_definition)?;
+            let mut transitions = Vec::new();
+
+            for transition in &stage_definition.transitions {
+                let condition = self.create_condition(&transition.condition)?;
+                transitions.push(Transition::new(&transition.target, condition));
+            }
+
+            stages.push(Stage::new(&stage_definition.name, actions, transitions));
+        }
+
+        let mut template = StagedPracticeTemplate::try_new(
+            definition.id,
+            definition.name.clone(),
+            role_names,
+            stages,
+        )?;
+
+        for role_definition in &definition.roles {
+            template.set_cardinality(role_definition.role, role_definition.cardinality);
+        }
+
+        Ok(template)
+    }
+
+    fn create_stage_actions(
+        &self,
+        definition: &PracticeTemplateDefinition,
+        stage_definition: &StageDefinition,
+    ) -> Result<RoleActions<T>, SocialError> {
+        let mut actions = HashMap::new();
+
+        for name in &stage_definition.actions {
+            let mut is_found = false;
+
+            for role_definition in &definition.roles {
+                for action in role_definition.actions.iter().filter(|a| &a.name == name) {
+                    actions
+                        .entry(role_definition.role)
+                        .or_insert_with(Vec::new)
+                        .push(self.create_action(action)?);
+                    is_found = true;
+                }
+            }
+
+            if !is_found {
+                return Err(SocialError::InvalidDefinition {
+                    message: format!(
+                        "Stage '{}' has the unknown action '{}'!",
+                        stage_definition.name, name
+                    ),
+                });
+            }
+        }
+
+        Ok(actions)
+    }
+}
+
+/// Gets the names of all roles & checks that no role is defined twice.
+fn get_role_names(
+    definition: &PracticeTemplateDefinition,
+) -> Result<HashMap<Role, String>, SocialError> {
+    let mut role_names = HashMap::new();
+
+    for role_definition in &definition.roles {
+        let role = role_definition.role;
+
+        if role_names
+            .insert(role, role_definition.name.clone())
+            .is_some()
+        {
+            return Err(SocialError::DuplicateRole {
+                template: definition.name.clone(),
+                role,
+            });
+        }
+    }
+
+    Ok(role_names)
+}
+
+impl<T: 'static> Default for DefinitionRegistry<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn unknown(name: &str) -> SocialError {
+    SocialError::UnknownConstructor {
+        name: name.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::social::effect::MockEffect;
+    use crate::social::practice::role::Role;
+    use crate::social::practice::PracticeTemplate;
+    use crate::social::utility::Utility;
+
+    const TEMPLATE: &str = r#"(
+        id: 7,
+        name: "trade",
+        roles: [
+            (
+                role: "character:0",
+                name: "Buyer",
+                actions: [
+                    (
+                        name: "buy",
+                        condition: And([Constant(true), Custom(name: "has_money", parameters: { "amount": 10 })]),
+                        utility: Conditional(condition: Not(Constant(false)), utility: 20),
+                        effect: Vector([Custom(name: "add", parameters: { "value": 2 }), Nothing]),
+                    ),
+                ],
+            ),
+            (role: "character:1", name: "Seller"),
+        ],
+    )"#;
+
+    #[test]
+    fn test_create_template_with_custom_leaves() {
+        let definition = PracticeTemplateDefinition::from_ron(TEMPLATE).unwrap();
+        let template = create_registry().create_template(&definition).unwrap();
+        let actions = template.get_actions(Role::Character { id: 0 });
+        let buy = actions[0];
+        let mut context = 10;
+
+        assert_eq!(template.get_id(), 7);
+        assert!(buy.is_available(&10));
+        assert!(!buy.is_available(&9));
+        assert_eq!(buy.get_utility(&10), Utility::from_integer(20));
+
+        buy.execute(&mut context);
+
+        assert_eq!(context, 12);
+    }
+
+    #[test]
+    fn test_json_and_ron_are_equal() {
+        let ron_definition = PracticeTemplateDefinition::from_ron(TEMPLATE).unwrap();
+        let json = serde_json::to_string(&ron_definition).unwrap();
+
+        assert_eq!(
+            PracticeTemplateDefinition::from_json(&json).unwrap(),
+            ron_definition
+        );
+    }
+
+    #[test]
+    fn test_invalid_parameter() {
+        let definition = ConditionDefinition::Custom {
+            name: "has_money".to_string(),
+            parameters: Parameters::default(),
+        };
+
+        assert_eq!(
+            create_registry().create_condition(&definition).err(),
+            Some(SocialError::InvalidParameter {
+                name: "amount".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_duplicate_role() {
+        let text = r#"(id: 1, name: "a", roles: [(role: "character:0", name: "b"), (role: "character:0", name: "c")])"#;
+        let definition = PracticeTemplateDefinition::from_ron(text).unwrap();
+
+        assert_eq!(
+            create_registry().create_template(&definition).err(),
+            Some(SocialError::DuplicateRole {
+                template: "a".to_string(),
+                role: Role::Character { id: 0 }
+            })
+        );
+    }
+
+    #[test]
+    fn test_stage_with_unknown_action() {
+        let text = r#"(id: 1, name: "a", roles: [], stages: [(name: "start", actions: ["wave"])])"#;
+        let definition = PracticeTemplateDefinition::from_ron(text).unwrap();
+
+        assert_eq!(
+            create_registry().create_staged_template(&definition).err(),
+            Some(SocialError::InvalidDefinition {
+                message: "Stage 'start' has the unknown action 'wave'!".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_terminal_stage_with_actions() {
+        let text = r#"(
+            id: 1,
+            name: "a",
+            roles: [(role: "character:0", name: "b", actions: [(name: "wave")])],
+            stages: [(name: "end", actions: ["wave"], is_terminal: true)],
+        )"#;
+        let definition = PracticeTemplateDefinition::from_ron(text).unwrap();
+
+        assert_eq!(
+            create_registry().create_staged_template(&definition).err(),
+            Some(SocialError::InvalidDefinition {
+                message: "Terminal stage 'end' can't have actions or transitions!".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_terminal_stage_with_transitions() {
+        let text = r#"(
+            id: 1,
+            name: "a",
+            roles: [],
+            stages: [(name: "end", transitions: [(target: "end")], is_terminal: true)],
+        )"#;
+        let definition = PracticeTemplateDefinition::from_ron(text).unwrap();
+
+        assert!(matches!(
+            create_registry().create_staged_template(&definition),
+            Err(SocialError::InvalidDefinition { .. })
+        ));
+    }
+
+    #[test]
+    fn test_template_without_stages() {
+        let text = r#"(id: 1, name: "a", roles: [])"#;
+        let definition = PracticeTemplateDefinition::from_ron(text).unwrap();
+
+        assert_eq!(
+            create_registry().create_staged_template(&definition).err(),
+            Some(SocialError::MissingStages {
+                template: "a".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_invalid_text() {
+        assert!(matches!(
+            PracticeTemplateDefinition::from_ron("(id: 1"),
+            Err(SocialError::InvalidDefinition { .. })
+        ));
+    }
+
+    #[test]
+    fn test_compare_values() {
+        let text = r#"Compare(
+            left: Arithmetic(left: Custom(name: "money"), operator: Multiply, right: Constant(2)),
+            comparison: Greater,
+            right: Utility(Total([Fixed(5), Fixed(10)])),
+        )"#;
+        let definition: ConditionDefinition = ron::from_str(text).unwrap();
+        let condition = create_registry().create_condition(&definition).unwrap();
+
+        assert!(!condition.evaluate(&7));
+        assert!(!condition.evaluate(&0));
+        assert!(condition.evaluate(&8));
+    }
+
+    #[test]
+    fn test_curve_utility() {
+        let text = r#"Curve(
+            input: Custom(name: "money"),
+            response: (curve: Piecewise([(0, 0), (10, 5), (20, 25)]), scale: 2, clamp: Some((0, 40))),
+        )"#;
+        let definition: UtilityDefinition = ron::from_str(text).unwrap();
+        let rule = create_registry().create_utility(&definition).unwrap();
+
+        assert_eq!(rule.calculate_utility(&5), Utility::from_integer(5));
+        assert_eq!(rule.calculate_utility(&15), Utility::from_integer(30));
+        assert_eq!(rule.calculate_utility(&99), Utility::from_integer(40));
+    }
+
+    #[test]
+    fn test_multiplicative_utility() {
+        let text = r#"Product(rules: [
+            Normalized(rule: Fixed(15), min: 10, max: 30),
+            WeightedAverage([(1, Fixed(1)), (1, Min([Fixed(0), Fixed(0.5)]))]),
+        ])"#;
+        let definition: UtilityDefinition = ron::from_str(text).unwrap();
+        let rule = create_registry().create_utility(&definition).unwrap();
+
+        assert_eq!(rule.calculate_utility(&0), Utility::from_normalized(0.125));
+    }
+
+    #[test]
+    fn test_ranking_mixes_fixed_and_normalized_utilities() {
+        let texts = [
+            r#"Fixed(1)"#,
+            r#"Total([
+                Product(rules: [
+                    Normalized(rule: Curve(input: Custom(name: "money"), response: (curve: Linear(slope: 1, offset: 0))), min: 0, max: 10),
+                    Fixed(0.5),
+                ]),
+                Fixed(0.5),
+            ])"#,
+            r#"Curve(input: Custom(name: "money"), response: (curve: Linear(slope: 0.25, offset: 0)))"#,
+        ];
+        let registry = create_registry();
+        let mut ranking: Vec<(usize, Utility)> = texts
+            .iter()
+            .map(|text| {
+                let definition: UtilityDefinition = ron::from_str(text).unwrap();
+                registry.create_utility(&definition).unwrap()
+            })
+            .map(|rule| rule.calculate_utility(&6))
+            .enumerate()
+            .collect();
+        ranking.sort_by_key(|(_, utility)| std::cmp::Reverse(*utility));
+
+        assert_eq!(
+            ranking,
+            vec![
+                (2, Utility::from_normalized(1.5)),
+                (0, Utility::ONE),
+                (1, Utility::from_normalized(0.8)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_counting_conditions() {
+        let text = r#"AtLeast(n: 2, conditions: [
+            Custom(name: "has_money", parameters: { "amount": 10 }),
+            Exactly(n: 1, conditions: [Constant(true), Constant(false)]),
+            Implies(premise: Constant(true), conclusion: Xor([Constant(true), Constant(true)])),
+        ])"#;
+        let definition: ConditionDefinition = ron::from_str(text).unwrap();
+        let condition = create_registry().create_condition(&definition).unwrap();
+
+        assert!(condition.evaluate(&10));
+        assert!(!condition.evaluate(&9));
+    }
+
+    struct HasMoney {
+        amount: u32,
+    }
+
+    impl Condition<u32> for HasMoney {
+        fn evaluate(&self, context: &u32) -> bool {
+            *context >= self.amount
+        }
+    }
+
+    fn create_registry() -> DefinitionRegistry<u32> {
+        let mut registry = DefinitionRegistry::new();
+        registry.register_condition("has_money", |parameters| {
+            let amount = parameters.get_integer("amount")? as u32;
+            Ok(Box::new(HasMoney { amount }))
+        });
+        registry.register_value("money", |_| {
+            Ok(Box::new(|context: &u32| {
+                Utility::from_integer(*context as i32)
+            }))
+        });
+        registry.register_effect("add", |parameters| {
+            Ok(Box::new(MockEffect::new(
+                parameters.get_integer("value")? as u32
+            )))
+        });
+        registry
+    }
+}
@@ -1,5 +1,5 @@
 /// An effect that can modify the context.
-pub trait Effect<T> {
+pub trait Effect<T>: Send + Sync {
     fn apply(&self, context: &mut T);
 }
 
@@ -44,6 +44,33 @@ impl Effect<u32> for MockEffect {
     }
 }
 
+/// An effect that changes the context with a closure, e.g. for prototyping.
+pub struct FnEffect<F> {
+    function: F,
+}
+
+impl<F> FnEffect<F> {
+    pub fn new(function: F) -> FnEffect<F> {
+        FnEffect { function }
+    }
+}
+
+impl<T, F: Fn(&mut T) + Send + Sync> Effect<T> for FnEffect<F> {
+    /// Calls the closure.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::effect::{Effect, FnEffect};
+    /// let mut context = 42;
+    ///
+    /// FnEffect::new(|context: &mut u32| *context *= 2).apply(&mut context);
+    ///
+    /// assert_eq!(context, 84);
+    /// ```
+    fn apply(&self, context: &mut T) {
+        (self.function)(context)
+    }
+}
+
 /// An effect that consists of multiple sub-effects.
 pub struct EffectVector<T> {
     effects: Vec<Box<dyn Effect<T>>>,
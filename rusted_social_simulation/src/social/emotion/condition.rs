@@ -0,0 +1,126 @@
+use crate::social::condition::Condition;
+use crate::social::emotion::{get_intensities, get_moods, EmotionContext, MoodDimension};
+use crate::social::practice::role::Target;
+use crate::social::practice::RoleContext;
+
+/// A condition that checks, if the emotions of a target toward another target have at least a certain intensity.
+///
+/// E.g. the listener is at least a bit angry at the speaker.
+pub struct EmotionAtLeast {
+    from: Target,
+    to: Target,
+    emotion: String,
+    threshold: i32,
+}
+
+impl EmotionAtLeast {
+    pub fn new(from: Target, to: Target, emotion: &str, threshold: i32) -> EmotionAtLeast {
+        EmotionAtLeast {
+            from,
+            to,
+            emotion: emotion.to_string(),
+            threshold,
+        }
+    }
+}
+
+impl<T: EmotionContext + RoleContext> Condition<T> for EmotionAtLeast {
+    /// Returns true, if all emotions are greater or equal to the threshold.
+    /// Returns false, if a target refers to no entity.
+    fn evaluate(&self, context: &T) -> bool {
+        let values = get_intensities(context, self.from, self.to, &self.emotion);
+        !values.is_empty() && values.iter().all(|value| *value >= self.threshold)
+    }
+}
+
+/// A condition that checks, if a dimension of the moods of a target has at least a certain value.
+pub struct MoodAtLeast {
+    target: Target,
+    dimension: MoodDimension,
+    threshold: i32,
+}
+
+impl MoodAtLeast {
+    pub fn new(target: Target, dimension: MoodDimension, threshold: i32) -> MoodAtLeast {
+        MoodAtLeast {
+            target,
+            dimension,
+            threshold,
+        }
+    }
+}
+
+impl<T: EmotionContext + RoleContext> Condition<T> for MoodAtLeast {
+    /// Returns true, if all moods are greater or equal to the threshold.
+    /// Returns false, if the target refers to no entity.
+    fn evaluate(&self, context: &T) -> bool {
+        let values = get_moods(context, self.target, self.dimension);
+        !values.is_empty() && values.iter().all(|value| *value >= self.threshold)
+    }
+}
+
+/// A condition that checks, if a dimension of the moods of a target has at most a certain value.
+///
+/// E.g. the speaker is in a bad mood.
+pub struct MoodAtMost {
+    target: Target,
+    dimension: MoodDimension,
+    threshold: i32,
+}
+
+impl MoodAtMost {
+    pub fn new(target: Target, dimension: MoodDimension, threshold: i32) -> MoodAtMost {
+        MoodAtMost {
+            target,
+            dimension,
+            threshold,
+        }
+    }
+}
+
+impl<T: EmotionContext + RoleContext> Condition<T> for MoodAtMost {
+    /// Returns true, if all moods are less or equal to the threshold.
+    /// Returns false, if the target refers to no entity.
+    fn evaluate(&self, context: &T) -> bool {
+        let values = get_moods(context, self.target, self.dimension);
+        !values.is_empty() && values.iter().all(|value| *value <= self.threshold)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::social::emotion::tests::{create_context, LISTENER, SPEAKER};
+    use crate::social::practice::role::Role;
+
+    #[test]
+    fn test_emotion_at_least() {
+        let context = create_context();
+
+        assert!(EmotionAtLeast::new(LISTENER, SPEAKER, "anger", 40).evaluate(&context));
+        assert!(!EmotionAtLeast::new(LISTENER, SPEAKER, "anger", 41).evaluate(&context));
+        assert!(!EmotionAtLeast::new(SPEAKER, LISTENER, "anger", 1).evaluate(&context));
+        assert!(!EmotionAtLeast::new(Target::Others, SPEAKER, "anger", 1).evaluate(&context));
+    }
+
+    #[test]
+    fn test_mood() {
+        let context = create_context();
+        let pleasure = MoodDimension::Pleasure;
+
+        assert!(MoodAtMost::new(LISTENER, pleasure, -20).evaluate(&context));
+        assert!(!MoodAtMost::new(LISTENER, pleasure, -21).evaluate(&context));
+        assert!(MoodAtLeast::new(SPEAKER, pleasure, 0).evaluate(&context));
+        assert!(!MoodAtLeast::new(Target::Others, pleasure, 0).evaluate(&context));
+        assert!(MoodAtLeast::new(LISTENER, MoodDimension::Arousal, 24).evaluate(&context));
+    }
+
+    #[test]
+    fn test_unknown_role() {
+        let context = create_context();
+        let unknown = Target::Role(Role::Character { id: 99 });
+
+        assert!(!EmotionAtLeast::new(unknown, SPEAKER, "anger", 0).evaluate(&context));
+        assert!(!MoodAtMost::new(unknown, MoodDimension::Pleasure, 100).evaluate(&context));
+    }
+}
@@ -0,0 +1,100 @@
+use crate::social::effect::Effect;
+use crate::social::emotion::EmotionContext;
+use crate::social::practice::role::Target;
+use crate::social::practice::RoleContext;
+use crate::social::relationship::get_pairs;
+
+/// An effect that lets the entities of a target appraise an action & feel an emotion toward another target.
+///
+/// E.g. the others get angry at the actor, after it insulted them.
+pub struct TriggerEmotion {
+    from: Target,
+    to: Target,
+    emotion: String,
+    intensity: i32,
+}
+
+impl TriggerEmotion {
+    pub fn new(from: Target, to: Target, emotion: &str, intensity: i32) -> TriggerEmotion {
+        TriggerEmotion {
+            from,
+            to,
+            emotion: emotion.to_string(),
+            intensity,
+        }
+    }
+}
+
+impl<T: EmotionContext + RoleContext> Effect<T> for TriggerEmotion {
+    /// Triggers the emotion for each pair of entities. Does nothing, if a target refers to no entity.
+    fn apply(&self, context: &mut T) {
+        for (from, to) in get_pairs(context, self.from, self.to) {
+            context
+                .get_emotions_mut()
+                .trigger(from, &self.emotion, to, self.intensity);
+        }
+    }
+}
+
+/// An effect that lets all emotions & moods decay, e.g. once per tick.
+pub struct DecayEmotions;
+
+impl<T: EmotionContext> Effect<T> for DecayEmotions {
+    /// Moves all emotions & moods towards 0.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::effect::Effect;
+    ///# use rusted_social_simulation::social::emotion::{EmotionType, Emotions, Mood};
+    ///# use rusted_social_simulation::social::emotion::effect::DecayEmotions;
+    /// let mut emotions = Emotions::new();
+    /// emotions.add_type("anger", EmotionType::try_new(Mood::new(-50, 60, 30), 10).unwrap());
+    /// emotions.trigger(1, "anger", 2, 30);
+    ///
+    /// DecayEmotions.apply(&mut emotions);
+    ///
+    /// assert_eq!(emotions.get(1, "anger", 2), 20);
+    /// ```
+    fn apply(&self, context: &mut T) {
+        context.get_emotions_mut().decay();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::social::emotion::tests::{create_context, LISTENER, SPEAKER};
+    use crate::social::emotion::Mood;
+    use crate::social::practice::role::Role;
+
+    #[test]
+    fn test_trigger() {
+        let mut context = create_context();
+
+        TriggerEmotion::new(LISTENER, SPEAKER, "anger", 30).apply(&mut context);
+
+        assert_eq!(context.get_emotions().get(11, "anger", 10), 70);
+        assert_eq!(context.get_emotions().get(10, "anger", 11), 0);
+        assert_eq!(context.get_emotions().get_mood(11), Mood::new(-35, 42, 21));
+    }
+
+    #[test]
+    fn test_others_appraise_actor() {
+        let mut context = create_context();
+
+        TriggerEmotion::new(Target::Others, Target::Actor, "anger", 50).apply(&mut context);
+
+        assert_eq!(context.get_emotions().get(11, "anger", 10), 90);
+        assert_eq!(context.get_emotions().get(12, "anger", 10), 50);
+        assert_eq!(context.get_emotions().get(10, "anger", 10), 0);
+    }
+
+    #[test]
+    fn test_unknown_role() {
+        let mut context = create_context();
+        let unknown = Target::Role(Role::Character { id: 99 });
+
+        TriggerEmotion::new(unknown, SPEAKER, "anger", 25).apply(&mut context);
+
+        assert_eq!(context.get_emotions().get(11, "anger", 10), 40);
+    }
+}
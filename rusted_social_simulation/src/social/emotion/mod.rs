@@ -0,0 +1,442 @@
+use crate::social::error::SocialError;
+use crate::social::practice::role::Target;
+use crate::social::practice::RoleContext;
+use crate::social::relationship::get_pairs;
+use serde::{de, Deserialize, Deserializer, Serialize};
+use std::collections::BTreeMap;
+
+pub mod condition;
+pub mod effect;
+pub mod utility;
+
+/// The highest intensity of an emotion.
+pub const MAX_INTENSITY: i32 = 100;
+/// The bounds of each dimension of a mood.
+pub const MAX_MOOD: i32 = 100;
+
+/// A dimension of the PAD model.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum MoodDimension {
+    Pleasure,
+    Arousal,
+    Dominance,
+}
+
+/// The longer lasting emotional state of an entity in the PAD model.
+///
+/// Each dimension is between -[`MAX_MOOD`] & [`MAX_MOOD`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Mood {
+    pleasure: i32,
+    arousal: i32,
+    dominance: i32,
+}
+
+impl Mood {
+    pub fn new(pleasure: i32, arousal: i32, dominance: i32) -> Mood {
+        Mood {
+            pleasure: pleasure.clamp(-MAX_MOOD, MAX_MOOD),
+            arousal: arousal.clamp(-MAX_MOOD, MAX_MOOD),
+            dominance: dominance.clamp(-MAX_MOOD, MAX_MOOD),
+        }
+    }
+
+    pub fn get(&self, dimension: MoodDimension) -> i32 {
+        match dimension {
+            MoodDimension::Pleasure => self.pleasure,
+            MoodDimension::Arousal => self.arousal,
+            MoodDimension::Dominance => self.dominance,
+        }
+    }
+
+    /// Moves the mood towards another one, scaled by a factor between 0 & 1.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::emotion::Mood;
+    /// let mood = Mood::new(10, 0, -90);
+    ///
+    /// assert_eq!(mood.shift(Mood::new(-40, 20, -50), 0.5), Mood::new(-10, 10, -100));
+    /// ```
+    pub fn shift(&self, other: Mood, factor: f32) -> Mood {
+        let shift = |value: i32, other: i32| value + (other as f32 * factor).round() as i32;
+
+        Mood::new(
+            shift(self.pleasure, other.pleasure),
+            shift(self.arousal, other.arousal),
+            shift(self.dominance, other.dominance),
+        )
+    }
+
+    /// Moves each dimension towards 0.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::emotion::Mood;
+    /// assert_eq!(Mood::new(10, -2, 0).decay(3), Mood::new(7, 0, 0));
+    /// ```
+    pub fn decay(&self, decay: i32) -> Mood {
+        Mood::new(
+            decay_value(self.pleasure, decay),
+            decay_value(self.arousal, decay),
+            decay_value(self.dominance, decay),
+        )
+    }
+}
+
+/// Checks, that a decay isn't negative, because the value would grow without bound otherwise.
+fn validate_decay(decay: i32) -> Result<i32, SocialError> {
+    if decay < 0 {
+        return Err(SocialError::NegativeDecay { decay });
+    }
+
+    Ok(decay)
+}
+
+fn deserialize_decay<'de, D: Deserializer<'de>>(deserializer: D) -> Result<i32, D::Error> {
+    validate_decay(i32::deserialize(deserializer)?).map_err(de::Error::custom)
+}
+
+fn decay_value(value: i32, decay: i32) -> i32 {
+    if value > 0 {
+        (value - decay).max(0)
+    } else {
+        (value + decay).min(0)
+    }
+}
+
+/// A type of emotion, like anger, gratitude or fear.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct EmotionType {
+    mood: Mood,
+    #[serde(deserialize_with = "deserialize_decay")]
+    decay: i32,
+}
+
+impl EmotionType {
+    /// Creates an emotion type, if the decay isn't negative.
+    ///
+    /// An emotion with the highest intensity shifts the mood of the entity feeling it by the mood of the type.
+    /// Its intensity moves towards 0 by decay per tick.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::emotion::{EmotionType, Mood};
+    ///# use rusted_social_simulation::social::error::SocialError;
+    /// assert!(EmotionType::try_new(Mood::new(-50, 60, 30), 10).is_ok());
+    /// assert_eq!(
+    ///     EmotionType::try_new(Mood::new(-50, 60, 30), -1),
+    ///     Err(SocialError::NegativeDecay { decay: -1 })
+    /// );
+    /// ```
+    pub fn try_new(mood: Mood, decay: i32) -> Result<EmotionType, SocialError> {
+        Ok(EmotionType {
+            mood,
+            decay: validate_decay(decay)?,
+        })
+    }
+}
+
+/// The emotions & the mood of an entity.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+struct EmotionalState {
+    /// The intensity of each emotion toward each target.
+    emotions: BTreeMap<String, BTreeMap<u32, i32>>,
+    mood: Mood,
+}
+
+/// Stores the emotions of entities toward other entities & their moods.
+///
+/// Emotions are appraisals of events, e.g. anger toward the one who insulted.
+/// They are between 0 & [`MAX_INTENSITY`] and shift the mood, when they are triggered.
+/// Both decay over time.
+/// Emotions that were never triggered are 0.
+/// Emotion types that were not added use [`EmotionType::default`], which neither shifts the mood nor decays.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Emotions {
+    types: BTreeMap<String, EmotionType>,
+    #[serde(deserialize_with = "deserialize_decay")]
+    mood_decay: i32,
+    states: BTreeMap<u32, EmotionalState>,
+}
+
+impl Emotions {
+    pub fn new() -> Emotions {
+        Emotions::default()
+    }
+
+    /// Adds or replaces an emotion type.
+    pub fn add_type(&mut self, name: &str, emotion_type: EmotionType) {
+        self.types.insert(name.to_string(), emotion_type);
+    }
+
+    /// Sets, how much the mood moves towards neutral per tick, if it isn't negative.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::emotion::Emotions;
+    ///# use rusted_social_simulation::social::error::SocialError;
+    /// let mut emotions = Emotions::new();
+    ///
+    /// assert_eq!(emotions.set_mood_decay(5), Ok(()));
+    /// assert_eq!(emotions.set_mood_decay(-5), Err(SocialError::NegativeDecay { decay: -5 }));
+    /// ```
+    pub fn set_mood_decay(&mut self, decay: i32) -> Result<(), SocialError> {
+        self.mood_decay = validate_decay(decay)?;
+        Ok(())
+    }
+
+    /// Gets the intensity of an emotion of an entity toward a target.
+    pub fn get(&self, entity: u32, emotion: &str, target: u32) -> i32 {
+        self.states
+            .get(&entity)
+            .and_then(|state| state.emotions.get(emotion))
+            .and_then(|targets| targets.get(&target))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Gets the mood of an entity.
+    pub fn get_mood(&self, entity: u32) -> Mood {
+        self.states
+            .get(&entity)
+            .map(|state| state.mood)
+            .unwrap_or_default()
+    }
+
+    /// Sets the mood of an entity, e.g. to initialize it.
+    pub fn set_mood(&mut self, entity: u32, mood: Mood) {
+        self.states.entry(entity).or_default().mood = mood;
+    }
+
+    /// Triggers an emotion of an entity toward a target.
+    ///
+    /// Its intensity is added to the existing one & the mood is shifted by the actual change.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::emotion::{EmotionType, Emotions, Mood};
+    /// let mut emotions = Emotions::new();
+    /// emotions.add_type("anger", EmotionType::try_new(Mood::new(-50, 60, 30), 10).unwrap());
+    ///
+    /// emotions.trigger(1, "anger", 2, 60);
+    /// emotions.trigger(1, "anger", 2, 60);
+    ///
+    /// assert_eq!(emotions.get(1, "anger", 2), 100);
+    /// assert_eq!(emotions.get(2, "anger", 1), 0);
+    /// assert_eq!(emotions.get_mood(1), Mood::new(-50, 60, 30));
+    ///
+    /// emotions.trigger(1, "anger", 2, 60);
+    ///
+    /// assert_eq!(emotions.get_mood(1), Mood::new(-50, 60, 30));
+    /// ```
+    pub fn trigger(&mut self, entity: u32, emotion: &str, target: u32, intensity: i32) {
+        let intensity = intensity.clamp(0, MAX_INTENSITY);
+        let mood = self.get_type(emotion).mood;
+        let state = self.states.entry(entity).or_default();
+        let value = state
+            .emotions
+            .entry(emotion.to_string())
+            .or_default()
+            .entry(target)
+            .or_default();
+
+        let old = *value;
+        *value = (old + intensity).min(MAX_INTENSITY);
+        let delta = *value - old;
+        state.mood = state.mood.shift(mood, delta as f32 / MAX_INTENSITY as f32);
+    }
+
+    /// Moves all emotions & moods towards 0.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::emotion::{EmotionType, Emotions, Mood};
+    /// let mut emotions = Emotions::new();
+    /// emotions.add_type("fear", EmotionType::try_new(Mood::new(-60, 60, -60), 15).unwrap());
+    /// emotions.set_mood_decay(5).unwrap();
+    /// emotions.trigger(1, "fear", 2, 50);
+    ///
+    /// emotions.decay();
+    ///
+    /// assert_eq!(emotions.get(1, "fear", 2), 35);
+    /// assert_eq!(emotions.get_mood(1), Mood::new(-25, 25, -25));
+    /// ```
+    pub fn decay(&mut self) {
+        let default = EmotionType::default();
+
+        for state in self.states.values_mut() {
+            for (name, targets) in state.emotions.iter_mut() {
+                let decay = self.types.get(name).unwrap_or(&default).decay;
+
+                for value in targets.values_mut() {
+                    *value = decay_value(*value, decay);
+                }
+
+                targets.retain(|_target, value| *value != 0);
+            }
+
+            state.emotions.retain(|_name, targets| !targets.is_empty());
+            state.mood = state.mood.decay(self.mood_decay);
+        }
+
+        self.states
+            .retain(|_entity, state| !state.emotions.is_empty() || state.mood != Mood::default());
+    }
+
+    /// Removes the emotions & the mood of an entity and the emotions of others toward it.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::emotion::Emotions;
+    /// let mut emotions = Emotions::new();
+    /// emotions.trigger(1, "anger", 2, 60);
+    /// emotions.trigger(2, "fear", 1, 30);
+    /// emotions.trigger(2, "fear", 3, 40);
+    ///
+    /// emotions.remove(1);
+    ///
+    /// assert_eq!(emotions.get(1, "anger", 2), 0);
+    /// assert_eq!(emotions.get(2, "fear", 1), 0);
+    /// assert_eq!(emotions.get(2, "fear", 3), 40);
+    /// ```
+    pub fn remove(&mut self, entity: u32) {
+        self.states.remove(&entity);
+
+        for state in self.states.values_mut() {
+            for targets in state.emotions.values_mut() {
+                targets.remove(&entity);
+            }
+
+            state.emotions.retain(|_name, targets| !targets.is_empty());
+        }
+    }
+
+    fn get_type(&self, emotion: &str) -> EmotionType {
+        self.types.get(emotion).cloned().unwrap_or_default()
+    }
+}
+
+/// A context that contains the emotions of entities.
+pub trait EmotionContext {
+    fn get_emotions(&self) -> &Emotions;
+
+    fn get_emotions_mut(&mut self) -> &mut Emotions;
+}
+
+impl EmotionContext for Emotions {
+    fn get_emotions(&self) -> &Emotions {
+        self
+    }
+
+    fn get_emotions_mut(&mut self) -> &mut Emotions {
+        self
+    }
+}
+
+/// Gets the emotions of the first target toward the second one.
+fn get_intensities<T: EmotionContext + RoleContext>(
+    context: &T,
+    from: Target,
+    to: Target,
+    emotion: &str,
+) -> Vec<i32> {
+    get_pairs(context, from, to)
+        .into_iter()
+        .map(|(from, to)| context.get_emotions().get(from, emotion, to))
+        .collect()
+}
+
+/// Gets a dimension of the moods of a target.
+fn get_moods<T: EmotionContext + RoleContext>(
+    context: &T,
+    target: Target,
+    dimension: MoodDimension,
+) -> Vec<i32> {
+    context
+        .resolve(target)
+        .into_iter()
+        .map(|entity| context.get_emotions().get_mood(entity).get(dimension))
+        .collect()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::social::execution::ExecutionContext;
+    use crate::social::practice::role::Role;
+
+    pub const SPEAKER: Target = Target::Role(Role::Character { id: 0 });
+    pub const LISTENER: Target = Target::Role(Role::Character { id: 1 });
+
+    /// Creates a context for testing with a speaker (entity 10) & 2 listeners (entity 11 & 12).
+    /// The speaker is the actor & the first listener is angry at the speaker.
+    pub fn create_context() -> ExecutionContext<Emotions> {
+        let mut emotions = Emotions::new();
+        emotions.add_type("anger", create_type(Mood::new(-50, 60, 30), 10));
+        emotions.add_type("gratitude", create_type(Mood::new(40, 20, -20), 5));
+        emotions.trigger(11, "anger", 10, 40);
+        emotions.trigger(12, "gratitude", 10, 20);
+
+        let participants = vec![
+            (Role::Character { id: 0 }, 10),
+            (Role::Character { id: 1 }, 11),
+            (Role::Character { id: 2 }, 12),
+        ];
+        let mut context = ExecutionContext::new(emotions);
+        context.bind(10, 0, participants.into_iter().collect());
+        context
+    }
+
+    fn create_type(mood: Mood, decay: i32) -> EmotionType {
+        EmotionType::try_new(mood, decay).unwrap()
+    }
+
+    #[test]
+    fn test_unknown_type() {
+        let mut emotions = Emotions::new();
+
+        emotions.trigger(1, "awe", 2, 120);
+        emotions.decay();
+
+        assert_eq!(emotions.get(1, "awe", 2), 100);
+        assert_eq!(emotions.get_mood(1), Mood::default());
+    }
+
+    #[test]
+    fn test_negative_intensity_is_ignored() {
+        let mut emotions = Emotions::new();
+        emotions.trigger(1, "joy", 2, 30);
+
+        emotions.trigger(1, "joy", 2, -50);
+
+        assert_eq!(emotions.get(1, "joy", 2), 30);
+    }
+
+    #[test]
+    fn test_decay_removes_neutral_states() {
+        let mut emotions = Emotions::new();
+        emotions.add_type("joy", create_type(Mood::new(20, 0, 0), 50));
+        emotions.set_mood_decay(20).unwrap();
+        emotions.trigger(1, "joy", 2, 40);
+
+        emotions.decay();
+
+        assert_eq!(
+            emotions,
+            Emotions {
+                types: emotions.types.clone(),
+                mood_decay: 20,
+                states: BTreeMap::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_deserialize_negative_decay() {
+        assert!(ron::from_str::<EmotionType>(
+            "(mood: (pleasure: 0, arousal: 0, dominance: 0), decay: -1)"
+        )
+        .is_err());
+        assert_eq!(
+            ron::from_str::<EmotionType>(
+                "(mood: (pleasure: 10, arousal: 0, dominance: 0), decay: 2)"
+            ),
+            Ok(create_type(Mood::new(10, 0, 0), 2))
+        );
+        assert!(ron::from_str::<Emotions>("(types: {}, mood_decay: -3, states: {})").is_err());
+    }
+}
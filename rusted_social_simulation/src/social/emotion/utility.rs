@@ -0,0 +1,172 @@
+use crate::social::emotion::{get_intensities, get_moods, EmotionContext, MoodDimension};
+use crate::social::practice::role::Target;
+use crate::social::practice::RoleContext;
+use crate::social::utility::{Utility, UtilityRule};
+
+/// An utility rule that scales the emotions of a target toward another target.
+///
+/// E.g. a negative factor for the anger of the actor toward the others makes compliments less likely.
+pub struct EmotionScaledUtility {
+    from: Target,
+    to: Target,
+    emotion: String,
+    factor: Utility,
+}
+
+impl EmotionScaledUtility {
+    pub fn new(from: Target, to: Target, emotion: &str, factor: Utility) -> EmotionScaledUtility {
+        EmotionScaledUtility {
+            from,
+            to,
+            emotion: emotion.to_string(),
+            factor,
+        }
+    }
+}
+
+impl<T: EmotionContext + RoleContext> UtilityRule<T> for EmotionScaledUtility {
+    /// Returns the sum of the emotions multiplied with a factor.
+    /// Returns 0, if a target refers to no entity. The result saturates instead of overflowing.
+    fn calculate_utility(&self, context: &T) -> Utility {
+        get_intensities(context, self.from, self.to, &self.emotion)
+            .iter()
+            .map(|value| Utility::from_integer(*value).saturating_mul(self.factor))
+            .fold(Utility::default(), |sum, utility| {
+                sum.saturating_add(utility)
+            })
+    }
+}
+
+/// An utility rule that scales a dimension of the moods of a target.
+pub struct MoodScaledUtility {
+    target: Target,
+    dimension: MoodDimension,
+    factor: Utility,
+}
+
+impl MoodScaledUtility {
+    pub fn new(target: Target, dimension: MoodDimension, factor: Utility) -> MoodScaledUtility {
+        MoodScaledUtility {
+            target,
+            dimension,
+            factor,
+        }
+    }
+}
+
+impl<T: EmotionContext + RoleContext> UtilityRule<T> for MoodScaledUtility {
+    /// Returns the sum of the moods multiplied with a factor.
+    /// Returns 0, if the target refers to no entity. The result saturates instead of overflowing.
+    fn calculate_utility(&self, context: &T) -> Utility {
+        get_moods(context, self.target, self.dimension)
+            .iter()
+            .map(|value| Utility::from_integer(*value).saturating_mul(self.factor))
+            .fold(Utility::default(), |sum, utility| {
+                sum.saturating_add(utility)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::social::effect::Effect;
+    use crate::social::emotion::effect::TriggerEmotion;
+    use crate::social::emotion::tests::{create_context, LISTENER, SPEAKER};
+    use crate::social::practice::role::Role;
+    use crate::social::utility::{FixedUtility, TotalUtility};
+
+    #[test]
+    fn test_emotion_scaled() {
+        let context = create_context();
+
+        assert_eq!(
+            EmotionScaledUtility::new(LISTENER, SPEAKER, "anger", Utility::from_integer(-2))
+                .calculate_utility(&context),
+            Utility::from_integer(-80)
+        );
+        assert_eq!(
+            EmotionScaledUtility::new(
+                Target::Others,
+                SPEAKER,
+                "gratitude",
+                Utility::from_integer(1)
+            )
+            .calculate_utility(&context),
+            Utility::from_integer(20)
+        );
+    }
+
+    #[test]
+    fn test_mood_scaled() {
+        let context = create_context();
+
+        assert_eq!(
+            MoodScaledUtility::new(
+                Target::Others,
+                MoodDimension::Pleasure,
+                Utility::from_integer(2)
+            )
+            .calculate_utility(&context),
+            Utility::from_integer(-24)
+        );
+    }
+
+    #[test]
+    fn test_saturate() {
+        let context = create_context();
+
+        assert_eq!(
+            EmotionScaledUtility::new(LISTENER, SPEAKER, "anger", Utility::MAX)
+                .calculate_utility(&context),
+            Utility::MAX
+        );
+        assert_eq!(
+            MoodScaledUtility::new(LISTENER, MoodDimension::Pleasure, Utility::MAX)
+                .calculate_utility(&context),
+            Utility::MIN
+        );
+    }
+
+    #[test]
+    fn test_unknown_role() {
+        let context = create_context();
+        let unknown = Target::Role(Role::Character { id: 99 });
+        let factor = Utility::from_integer(2);
+
+        assert_eq!(
+            EmotionScaledUtility::new(unknown, SPEAKER, "anger", factor)
+                .calculate_utility(&context),
+            Utility::default()
+        );
+        assert_eq!(
+            MoodScaledUtility::new(unknown, MoodDimension::Pleasure, factor)
+                .calculate_utility(&context),
+            Utility::default()
+        );
+    }
+
+    /// The speaker insults the listener, who is then less likely to compliment back.
+    #[test]
+    fn test_insulted_is_less_likely_to_compliment() {
+        let mut context = create_context();
+        let compliment = TotalUtility::new(vec![
+            Box::new(FixedUtility::new(Utility::from_integer(50))),
+            Box::new(EmotionScaledUtility::new(
+                LISTENER,
+                SPEAKER,
+                "anger",
+                Utility::from_integer(-1),
+            )),
+        ]);
+        let before = compliment.calculate_utility(&context);
+
+        TriggerEmotion::new(LISTENER, SPEAKER, "anger", 30).apply(&mut context);
+
+        assert_eq!(before, Utility::from_integer(10));
+        assert_eq!(
+            compliment.calculate_utility(&context),
+            Utility::from_integer(-20)
+        );
+    }
+}
@@ -0,0 +1,173 @@
+use crate::social::practice::role::Role;
+use core::fmt;
+
+/// The errors of a social simulation.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SocialError {
+    /// A practice template doesn't have the role.
+    UnknownRole { template: String, role: Role },
+    /// An entity doesn't participate in a practice.
+    UnknownEntity { practice: u32, entity: u32 },
+    /// An entity has more than one role in a practice.
+    DuplicateEntity { practice: u32, entity: u32 },
+    /// There is no practice template with the id.
+    MissingTemplate { id: u32 },
+    /// A practice template defines the role more than once.
+    DuplicateRole { template: String, role: Role },
+    /// No constructor was registered for the name.
+    UnknownConstructor { name: String },
+    /// A parameter is missing or has the wrong type.
+    InvalidParameter { name: String },
+    /// A definition couldn't be parsed.
+    InvalidDefinition { message: String },
+    /// A practice template with stages doesn't have any.
+    MissingStages { template: String },
+    /// A practice template doesn't have the stage.
+    UnknownStage { template: String, stage: String },
+    /// A practice template defines the stage more than once.
+    DuplicateStage { template: String, stage: String },
+    /// An entity tried to act, although it isn't its turn.
+    OutOfTurn { practice: u32, entity: u32 },
+    /// The simulation already simulated some ticks.
+    AlreadyStarted { tick: u32 },
+    /// Another practice template has the same id.
+    DuplicateTemplate { id: u32 },
+    /// No available entity can fill the role of a practice template.
+    UncastableRole { template: String, role: Role },
+    /// The key of a role couldn't be parsed.
+    InvalidRoleKey { key: String },
+    /// The snapshot of a practice doesn't contain a turn policy.
+    MissingTurnPolicy { practice: u32 },
+    /// The minimum of a range is greater than its maximum.
+    InvalidBounds { min: i32, max: i32 },
+    /// A value decays by a negative amount & would grow without bound.
+    NegativeDecay { decay: i32 },
+    /// A role of a practice has too few or too many entities.
+    InvalidCardinality {
+        template: String,
+        role: Role,
+        count: usize,
+    },
+    /// A snapshot references another practice template.
+    TemplateMismatch { expected: u32, actual: u32 },
+    /// An entity has no available action with the name, e.g. because it isn't its turn.
+    UnavailableAction { entity: u32, action: String },
+    /// A practice doesn't have the id, that was generated for it.
+    PracticeIdMismatch { expected: u32, actual: u32 },
+}
+
+impl fmt::Display for SocialError {
+    /// Describes the error.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::error::SocialError;
+    /// let error = SocialError::MissingTemplate { id: 3 };
+    ///
+    /// assert_eq!(error.to_string(), "PracticeTemplate 3 doesn't exist!");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SocialError::UnknownRole { template, role } => write!(
+                f,
+                "PracticeTemplate '{}' doesn't have the role {}!",
+                template, role
+            ),
+            SocialError::UnknownEntity { practice, entity } => write!(
+                f,
+                "Practice {} doesn't have a role for entity {}!",
+                practice, entity
+            ),
+            SocialError::DuplicateEntity { practice, entity } => write!(
+                f,
+                "Entity {} has multiple roles in practice {}!",
+                entity, practice
+            ),
+            SocialError::MissingTemplate { id } => {
+                write!(f, "PracticeTemplate {} doesn't exist!", id)
+            }
+            SocialError::DuplicateRole { template, role } => write!(
+                f,
+                "PracticeTemplate '{}' has the role {} multiple times!",
+                template, role
+            ),
+            SocialError::UnknownConstructor { name } => {
+                write!(f, "No constructor for '{}' is registered!", name)
+            }
+            SocialError::InvalidParameter { name } => {
+                write!(f, "Parameter '{}' is missing or invalid!", name)
+            }
+            SocialError::InvalidDefinition { message } => {
+                write!(f, "Invalid definition: {}", message)
+            }
+            SocialError::MissingStages { template } => {
+                write!(f, "PracticeTemplate '{}' has no stages!", template)
+            }
+            SocialError::UnknownStage { template, stage } => write!(
+                f,
+                "PracticeTemplate '{}' doesn't have the stage '{}'!",
+                template, stage
+            ),
+            SocialError::DuplicateStage { template, stage } => write!(
+                f,
+                "PracticeTemplate '{}' has the stage '{}' multiple times!",
+                template, stage
+            ),
+            SocialError::OutOfTurn { practice, entity } => write!(
+                f,
+                "It isn't the turn of entity {} in practice {}!",
+                entity, practice
+            ),
+            SocialError::AlreadyStarted { tick } => {
+                write!(f, "The simulation already started & is at tick {}!", tick)
+            }
+            SocialError::DuplicateTemplate { id } => {
+                write!(f, "PracticeTemplate {} exists already!", id)
+            }
+            SocialError::UncastableRole { template, role } => write!(
+                f,
+                "PracticeTemplate '{}' has no available entity for the role {}!",
+                template, role
+            ),
+            SocialError::InvalidRoleKey { key } => write!(f, "'{}' isn't a valid role key!", key),
+            SocialError::MissingTurnPolicy { practice } => {
+                write!(
+                    f,
+                    "The snapshot of practice {} has no turn policy!",
+                    practice
+                )
+            }
+            SocialError::InvalidBounds { min, max } => {
+                write!(
+                    f,
+                    "The minimum {} is greater than the maximum {}!",
+                    min, max
+                )
+            }
+            SocialError::NegativeDecay { decay } => write!(f, "The decay {} is negative!", decay),
+            SocialError::InvalidCardinality {
+                template,
+                role,
+                count,
+            } => write!(
+                f,
+                "PracticeTemplate '{}' doesn't allow {} entities with the role {}!",
+                template, count, role
+            ),
+            SocialError::TemplateMismatch { expected, actual } => write!(
+                f,
+                "Expected PracticeTemplate {} instead of {}!",
+                expected, actual
+            ),
+            SocialError::UnavailableAction { entity, action } => write!(
+                f,
+                "Action '{}' isn't available to entity {}!",
+                action, entity
+            ),
+            SocialError::PracticeIdMismatch { expected, actual } => {
+                write!(f, "Expected practice {} instead of {}!", expected, actual)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SocialError {}
@@ -0,0 +1,391 @@
+use crate::social::condition::Condition;
+use crate::social::practice::role::{Role, Target};
+use crate::social::practice::RoleContext;
+use serde::{Deserialize, Serialize};
+
+/// An action that was executed.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Event {
+    tick: u32,
+    practice: u32,
+    actor: u32,
+    role: Role,
+    action: String,
+    targets: Vec<u32>,
+}
+
+impl Event {
+    pub fn new(
+        tick: u32,
+        practice: u32,
+        actor: u32,
+        role: Role,
+        action: &str,
+        targets: Vec<u32>,
+    ) -> Event {
+        Event {
+            tick,
+            practice,
+            actor,
+            role,
+            action: action.to_string(),
+            targets,
+        }
+    }
+
+    /// Gets the tick the action was executed in.
+    pub fn get_tick(&self) -> u32 {
+        self.tick
+    }
+
+    /// Gets the practice the action was executed in.
+    pub fn get_practice(&self) -> u32 {
+        self.practice
+    }
+
+    /// Gets the entity that executed the action.
+    pub fn get_actor(&self) -> u32 {
+        self.actor
+    }
+
+    /// Gets the role of the actor in the practice.
+    pub fn get_role(&self) -> Role {
+        self.role
+    }
+
+    /// Gets the name of the action.
+    pub fn get_action(&self) -> &str {
+        &self.action
+    }
+
+    /// Gets the entities the actor addressed.
+    pub fn get_targets(&self) -> &[u32] {
+        &self.targets
+    }
+}
+
+/// An append-only log of the executed actions.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct EventLog {
+    events: Vec<Event>,
+}
+
+impl EventLog {
+    pub fn new() -> EventLog {
+        EventLog::default()
+    }
+
+    /// Appends an event.
+    pub fn add(&mut self, event: Event) {
+        self.events.push(event);
+    }
+
+    /// Gets all events from the oldest to the newest.
+    pub fn get_events(&self) -> &[Event] {
+        &self.events
+    }
+
+    /// Gets the last events of a practice from the oldest to the newest.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::event::{Event, EventLog};
+    ///# use rusted_social_simulation::social::practice::role::Role;
+    ///# let questioner = Role::Character { id: 0 };
+    ///# let answerer = Role::Character { id: 1 };
+    ///# let mut log = EventLog::new();
+    ///# log.add(Event::new(0, 1, 10, questioner, "greet", vec![11]));
+    ///# log.add(Event::new(1, 2, 12, questioner, "greet", vec![]));
+    ///# log.add(Event::new(1, 1, 10, questioner, "ask", vec![11]));
+    ///# log.add(Event::new(2, 1, 11, answerer, "answer", vec![10]));
+    ///
+    /// let events = log.get_last(1, 2);
+    ///
+    /// assert_eq!(events.len(), 2);
+    /// assert_eq!(events[0].get_action(), "ask");
+    /// assert_eq!(events[1].get_action(), "answer");
+    /// assert!(log.get_last(99, 2).is_empty());
+    /// ```
+    pub fn get_last(&self, practice: u32, count: usize) -> Vec<&Event> {
+        let mut events: Vec<&Event> = self
+            .events
+            .iter()
+            .rev()
+            .filter(|event| event.practice == practice)
+            .take(count)
+            .collect();
+        events.reverse();
+        events
+    }
+
+    /// Gets the newest event of a practice.
+    pub fn get_last_event(&self, practice: u32) -> Option<&Event> {
+        self.events
+            .iter()
+            .rev()
+            .find(|event| event.practice == practice)
+    }
+
+    /// Has the entity ever executed the action?
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::event::{Event, EventLog};
+    ///# use rusted_social_simulation::social::practice::role::Role;
+    ///# let questioner = Role::Character { id: 0 };
+    ///# let answerer = Role::Character { id: 1 };
+    ///# let mut log = EventLog::new();
+    ///# log.add(Event::new(0, 1, 10, questioner, "greet", vec![11]));
+    ///# log.add(Event::new(1, 2, 12, questioner, "greet", vec![]));
+    ///# log.add(Event::new(1, 1, 10, questioner, "ask", vec![11]));
+    ///# log.add(Event::new(2, 1, 11, answerer, "answer", vec![10]));
+    ///
+    /// assert!(log.has_done(10, "greet"));
+    /// assert!(!log.has_done(11, "greet"));
+    /// ```
+    pub fn has_done(&self, entity: u32, action: &str) -> bool {
+        self.events
+            .iter()
+            .any(|event| event.actor == entity && event.action == action)
+    }
+
+    /// Gets the number of ticks since the entity executed the action the last time.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::event::{Event, EventLog};
+    ///# use rusted_social_simulation::social::practice::role::Role;
+    ///# let questioner = Role::Character { id: 0 };
+    ///# let answerer = Role::Character { id: 1 };
+    ///# let mut log = EventLog::new();
+    ///# log.add(Event::new(0, 1, 10, questioner, "greet", vec![11]));
+    ///# log.add(Event::new(1, 2, 12, questioner, "greet", vec![]));
+    ///# log.add(Event::new(1, 1, 10, questioner, "ask", vec![11]));
+    ///# log.add(Event::new(2, 1, 11, answerer, "answer", vec![10]));
+    ///
+    /// assert_eq!(log.get_ticks_since(10, "greet", 5), Some(5));
+    /// assert_eq!(log.get_ticks_since(11, "answer", 5), Some(3));
+    /// assert_eq!(log.get_ticks_since(11, "greet", 5), None);
+    /// ```
+    pub fn get_ticks_since(&self, entity: u32, action: &str, tick: u32) -> Option<u32> {
+        self.events
+            .iter()
+            .rev()
+            .find(|event| event.actor == entity && event.action == action)
+            .map(|event| tick.saturating_sub(event.tick))
+    }
+}
+
+/// A context that knows the history of executed actions.
+pub trait EventContext {
+    /// Gets the log of all executed actions.
+    fn get_event_log(&self) -> &EventLog;
+
+    /// Gets the current tick.
+    fn get_tick(&self) -> u32;
+
+    /// Gets the current practice.
+    fn get_current_practice(&self) -> Option<u32>;
+}
+
+/// A condition that checks, if the entities of a target have ever executed an action.
+///
+/// For example, *not greeting twice* is the negation of it.
+pub struct HasDone {
+    target: Target,
+    action: String,
+}
+
+impl HasDone {
+    pub fn new(target: Target, action: &str) -> HasDone {
+        HasDone {
+            target,
+            action: action.to_string(),
+        }
+    }
+}
+
+impl<T: EventContext + RoleContext> Condition<T> for HasDone {
+    /// Returns true, if all entities of the target executed the action. False without entities.
+    fn evaluate(&self, context: &T) -> bool {
+        let log = context.get_event_log();
+        all_entities(context, self.target, |entity| {
+            log.has_done(entity, &self.action)
+        })
+    }
+}
+
+/// A condition that checks, if the entities of a target executed an action during the last ticks.
+///
+/// The negation of it can be used as cooldown.
+pub struct DoneWithin {
+    target: Target,
+    action: String,
+    ticks: u32,
+}
+
+impl DoneWithin {
+    pub fn new(target: Target, action: &str, ticks: u32) -> DoneWithin {
+        DoneWithin {
+            target,
+            action: action.to_string(),
+            ticks,
+        }
+    }
+}
+
+impl<T: EventContext + RoleContext> Condition<T> for DoneWithin {
+    /// Returns true, if all entities of the target executed the action at most the number of ticks ago.
+    fn evaluate(&self, context: &T) -> bool {
+        let log = context.get_event_log();
+        let tick = context.get_tick();
+        all_entities(context, self.target, |entity| {
+            log.get_ticks_since(entity, &self.action, tick)
+                .is_some_and(|ticks| ticks <= self.ticks)
+        })
+    }
+}
+
+/// A condition that checks, if the newest action of the current practice addressed the actor.
+///
+/// For example, to *reply to a question*.
+pub struct WasAddressedWith {
+    action: String,
+}
+
+impl WasAddressedWith {
+    pub fn new(action: &str) -> WasAddressedWith {
+        WasAddressedWith {
+            action: action.to_string(),
+        }
+    }
+}
+
+impl<T: EventContext + RoleContext> Condition<T> for WasAddressedWith {
+    /// Returns true, if the newest action of the current practice has the name & targeted the actor.
+    fn evaluate(&self, context: &T) -> bool {
+        let actor = match context.get_actor() {
+            Some(actor) => actor,
+            None => return false,
+        };
+
+        context
+            .get_current_practice()
+            .and_then(|practice| context.get_event_log().get_last_event(practice))
+            .is_some_and(|event| event.action == self.action && event.targets.contains(&actor))
+    }
+}
+
+fn all_entities<T: RoleContext>(
+    context: &T,
+    target: Target,
+    predicate: impl Fn(u32) -> bool,
+) -> bool {
+    let entities = context.resolve(target);
+    !entities.is_empty() && entities.into_iter().all(predicate)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    pub const QUESTIONER: Role = Role::Character { id: 0 };
+    pub const ANSWERER: Role = Role::Character { id: 1 };
+
+    /// Create an EventLog for testing.
+    ///
+    /// In practice 1 entity 10 greets & asks entity 11, which answers.
+    pub fn create_test_log() -> EventLog {
+        let mut log = EventLog::new();
+        log.add(Event::new(0, 1, 10, QUESTIONER, "greet", vec![11]));
+        log.add(Event::new(1, 2, 12, QUESTIONER, "greet", vec![]));
+        log.add(Event::new(1, 1, 10, QUESTIONER, "ask", vec![11]));
+        log.add(Event::new(2, 1, 11, ANSWERER, "answer", vec![10]));
+        log
+    }
+
+    /// A context with an event log, in which entity 10 acts in practice 1 at tick 4.
+    pub struct TestContext {
+        pub log: EventLog,
+        pub actor: Option<u32>,
+        pub participants: HashMap<Role, u32>,
+    }
+
+    impl TestContext {
+        pub fn new() -> TestContext {
+            TestContext {
+                log: create_test_log(),
+                actor: Some(10),
+                participants: vec![(QUESTIONER, 10), (ANSWERER, 11)].into_iter().collect(),
+            }
+        }
+    }
+
+    impl EventContext for TestContext {
+        fn get_event_log(&self) -> &EventLog {
+            &self.log
+        }
+
+        fn get_tick(&self) -> u32 {
+            4
+        }
+
+        fn get_current_practice(&self) -> Option<u32> {
+            Some(1)
+        }
+    }
+
+    impl RoleContext for TestContext {
+        fn get_actor(&self) -> Option<u32> {
+            self.actor
+        }
+
+        fn get_entity(&self, role: Role) -> Option<u32> {
+            self.participants.get(&role).copied()
+        }
+
+        fn get_participants(&self) -> Vec<u32> {
+            let mut entities: Vec<u32> = self.participants.values().copied().collect();
+            entities.sort_unstable();
+            entities
+        }
+    }
+
+    #[test]
+    fn test_has_done() {
+        let context = TestContext::new();
+
+        assert!(HasDone::new(Target::Actor, "greet").evaluate(&context));
+        assert!(!HasDone::new(Target::Others, "greet").evaluate(&context));
+        assert!(HasDone::new(Target::Role(ANSWERER), "answer").evaluate(&context));
+    }
+
+    #[test]
+    fn test_done_within() {
+        let context = TestContext::new();
+
+        assert!(DoneWithin::new(Target::Actor, "ask", 3).evaluate(&context));
+        assert!(!DoneWithin::new(Target::Actor, "ask", 2).evaluate(&context));
+        assert!(!DoneWithin::new(Target::Actor, "answer", 10).evaluate(&context));
+    }
+
+    #[test]
+    fn test_was_addressed_with() {
+        let mut context = TestContext::new();
+
+        assert!(WasAddressedWith::new("answer").evaluate(&context));
+        assert!(!WasAddressedWith::new("ask").evaluate(&context));
+
+        context.actor = Some(11);
+        assert!(!WasAddressedWith::new("answer").evaluate(&context));
+
+        context.actor = None;
+        assert!(!WasAddressedWith::new("answer").evaluate(&context));
+    }
+
+    #[test]
+    fn test_empty_target() {
+        let context = TestContext::new();
+        let unknown = Target::Role(Role::Character { id: 99 });
+
+        assert!(!HasDone::new(unknown, "greet").evaluate(&context));
+    }
+}
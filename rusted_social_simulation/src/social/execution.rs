@@ -0,0 +1,378 @@
+use crate::social::condition::Condition;
+use crate::social::effect::Effect;
+use crate::social::emotion::{EmotionContext, Emotions};
+use crate::social::event::{Event, EventContext, EventLog};
+use crate::social::explanation::Explanation;
+use crate::social::personality::{Personalities, PersonalityContext};
+use crate::social::practice::participants::Participants;
+use crate::social::practice::role::{Role, Target};
+use crate::social::practice::RoleContext;
+use crate::social::relationship::{RelationshipContext, Relationships};
+use crate::social::utility::{Utility, UtilityRule};
+
+/// Wraps a context together with the entity that acts & the practice it acts in.
+///
+/// This allows rules to refer to other entities by their role in the practice.
+/// It also keeps the log of executed actions, so rules can refer to the history.
+pub struct ExecutionContext<T> {
+    context: T,
+    actor: Option<u32>,
+    practice: Option<u32>,
+    participants: Participants,
+    addressed: Vec<u32>,
+    events: EventLog,
+    tick: u32,
+}
+
+impl<T> ExecutionContext<T> {
+    /// Wraps a context without binding it to an actor & practice.
+    pub fn new(context: T) -> ExecutionContext<T> {
+        ExecutionContext {
+            context,
+            actor: None,
+            practice: None,
+            participants: Participants::new(),
+            addressed: Vec::new(),
+            events: EventLog::new(),
+            tick: 0,
+        }
+    }
+
+    /// Wraps a context together with the log of previously executed actions, e.g. to restore a snapshot.
+    pub fn with_events(context: T, events: EventLog) -> ExecutionContext<T> {
+        ExecutionContext {
+            events,
+            ..ExecutionContext::new(context)
+        }
+    }
+
+    /// Binds the context to the entity that acts & the practice it acts in.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::execution::ExecutionContext;
+    ///# use rusted_social_simulation::social::practice::role::{Role, Target};
+    ///# use rusted_social_simulation::social::practice::RoleContext;
+    /// let speaker = Role::Character { id: 0 };
+    /// let listener = Role::Character { id: 1 };
+    /// let mut context = ExecutionContext::new(42);
+    ///
+    /// context.bind(10, 5, vec![(speaker, 10), (listener, 11)].into_iter().collect());
+    ///
+    /// assert_eq!(context.get_actor(), Some(10));
+    /// assert_eq!(context.get_practice(), Some(5));
+    /// assert_eq!(context.get_entity(listener), Some(11));
+    /// assert_eq!(context.resolve(Target::Others), vec![11]);
+    /// ```
+    pub fn bind(&mut self, actor: u32, practice: u32, participants: Participants) {
+        self.actor = Some(actor);
+        self.practice = Some(practice);
+        self.participants = participants;
+        self.addressed.clear();
+    }
+
+    /// Binds the context to an actor without a practice, e.g. to check if it can fill a role.
+    pub fn bind_actor(&mut self, actor: u32) {
+        self.actor = Some(actor);
+        self.practice = None;
+        self.participants = Participants::new();
+        self.addressed.clear();
+    }
+
+    /// Binds the context to a practice without an actor, e.g. to evaluate the practice's transitions.
+    pub fn bind_practice(&mut self, practice: u32, participants: Participants) {
+        self.actor = None;
+        self.practice = Some(practice);
+        self.participants = participants;
+        self.addressed.clear();
+    }
+
+    /// Removes the binding to the actor & practice.
+    pub fn unbind(&mut self) {
+        self.actor = None;
+        self.practice = None;
+        self.participants = Participants::new();
+        self.addressed.clear();
+    }
+
+    /// Remembers that the actor addressed an entity, e.g. to let it reply.
+    pub fn address(&mut self, entity: u32) {
+        if !self.addressed.contains(&entity) {
+            self.addressed.push(entity);
+        }
+    }
+
+    /// Gets & forgets the entities the actor addressed since it was bound.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::execution::ExecutionContext;
+    /// let mut context = ExecutionContext::new(42);
+    ///
+    /// context.address(11);
+    /// context.address(11);
+    ///
+    /// assert_eq!(context.take_addressed(), vec![11]);
+    /// assert!(context.take_addressed().is_empty());
+    /// ```
+    pub fn take_addressed(&mut self) -> Vec<u32> {
+        std::mem::take(&mut self.addressed)
+    }
+
+    /// Gets the practice the actor acts in.
+    pub fn get_practice(&self) -> Option<u32> {
+        self.practice
+    }
+
+    /// Sets the current tick, which is used for new events.
+    pub fn set_tick(&mut self, tick: u32) {
+        self.tick = tick;
+    }
+
+    /// Records, that the bound actor executed an action in its role & practice.
+    /// The entities it addressed are the targets of the event.
+    ///
+    /// Does nothing, if the context isn't bound to an actor with a role.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::event::EventContext;
+    ///# use rusted_social_simulation::social::execution::ExecutionContext;
+    ///# use rusted_social_simulation::social::practice::role::Role;
+    /// let speaker = Role::Character { id: 0 };
+    /// let listener = Role::Character { id: 1 };
+    /// let mut context = ExecutionContext::new(42);
+    /// context.bind(10, 5, vec![(speaker, 10), (listener, 11)].into_iter().collect());
+    /// context.set_tick(3);
+    /// context.address(11);
+    ///
+    /// context.record("greet");
+    ///
+    /// let event = &context.get_event_log().get_events()[0];
+    /// assert_eq!(event.get_tick(), 3);
+    /// assert_eq!(event.get_practice(), 5);
+    /// assert_eq!(event.get_actor(), 10);
+    /// assert_eq!(event.get_role(), speaker);
+    /// assert_eq!(event.get_action(), "greet");
+    /// assert_eq!(event.get_targets(), &[11]);
+    /// ```
+    pub fn record(&mut self, action: &str) {
+        let (actor, practice) = match (self.actor, self.practice) {
+            (Some(actor), Some(practice)) => (actor, practice),
+            _ => return,
+        };
+        if let Some(role) = self.participants.get_role(actor) {
+            let targets = self.addressed.clone();
+            self.events.add(Event::new(
+                self.tick, practice, actor, role, action, targets,
+            ));
+        }
+    }
+
+    /// Gets the wrapped context.
+    pub fn get_context(&self) -> &T {
+        &self.context
+    }
+
+    /// Gets the wrapped context to modify it.
+    pub fn get_context_mut(&mut self) -> &mut T {
+        &mut self.context
+    }
+
+    /// Unwraps the context.
+    pub fn into_context(self) -> T {
+        self.context
+    }
+}
+
+impl<T> RoleContext for ExecutionContext<T> {
+    fn get_actor(&self) -> Option<u32> {
+        self.actor
+    }
+
+    fn get_entity(&self, role: Role) -> Option<u32> {
+        self.participants.get_entity(role)
+    }
+
+    fn get_entities_of(&self, role: Role) -> Vec<u32> {
+        self.participants.get_entities(role).to_vec()
+    }
+
+    fn get_participants(&self) -> Vec<u32> {
+        self.participants.get_all()
+    }
+}
+
+impl<T> EventContext for ExecutionContext<T> {
+    fn get_event_log(&self) -> &EventLog {
+        &self.events
+    }
+
+    fn get_tick(&self) -> u32 {
+        self.tick
+    }
+
+    fn get_current_practice(&self) -> Option<u32> {
+        self.practice
+    }
+}
+
+impl<T: EmotionContext> EmotionContext for ExecutionContext<T> {
+    fn get_emotions(&self) -> &Emotions {
+        self.context.get_emotions()
+    }
+
+    fn get_emotions_mut(&mut self) -> &mut Emotions {
+        self.context.get_emotions_mut()
+    }
+}
+
+impl<T: PersonalityContext> PersonalityContext for ExecutionContext<T> {
+    fn get_personalities(&self) -> &Personalities {
+        self.context.get_personalities()
+    }
+}
+
+impl<T: RelationshipContext> RelationshipContext for ExecutionContext<T> {
+    fn get_relationships(&self) -> &Relationships {
+        self.context.get_relationships()
+    }
+
+    fn get_relationships_mut(&mut self) -> &mut Relationships {
+        self.context.get_relationships_mut()
+    }
+}
+
+/// Adapts a rule for the wrapped context, so it can be used with an [`ExecutionContext`].
+pub struct Unbound<R> {
+    rule: R,
+}
+
+impl<R> Unbound<R> {
+    pub fn new(rule: R) -> Unbound<R> {
+        Unbound { rule }
+    }
+}
+
+impl<T, R: Condition<T>> Condition<ExecutionContext<T>> for Unbound<R> {
+    /// Evaluates the condition with the wrapped context.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::condition::{Condition, MockCondition};
+    ///# use rusted_social_simulation::social::execution::{ExecutionContext, Unbound};
+    /// let context = ExecutionContext::new(42);
+    ///
+    /// assert!(Unbound::new(MockCondition::new(true)).evaluate(&context));
+    /// ```
+    fn evaluate(&self, context: &ExecutionContext<T>) -> bool {
+        self.rule.evaluate(&context.context)
+    }
+
+    fn explain(&self, context: &ExecutionContext<T>) -> Explanation {
+        self.rule.explain(&context.context)
+    }
+}
+
+impl<T, R: UtilityRule<T>> UtilityRule<ExecutionContext<T>> for Unbound<R> {
+    /// Calculates the utility with the wrapped context.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::execution::{ExecutionContext, Unbound};
+    ///# use rusted_social_simulation::social::utility::{FixedUtility, Utility, UtilityRule};
+    /// let context = ExecutionContext::new(42);
+    /// let rule = Unbound::new(FixedUtility::new(Utility::from_integer(7)));
+    ///
+    /// assert_eq!(rule.calculate_utility(&context), Utility::from_integer(7));
+    /// ```
+    fn calculate_utility(&self, context: &ExecutionContext<T>) -> Utility {
+        self.rule.calculate_utility(&context.context)
+    }
+
+    fn explain(&self, context: &ExecutionContext<T>) -> Explanation {
+        self.rule.explain(&context.context)
+    }
+}
+
+impl<T, R: Effect<T>> Effect<ExecutionContext<T>> for Unbound<R> {
+    /// Applies the effect to the wrapped context.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::effect::{Effect, MockEffect};
+    ///# use rusted_social_simulation::social::execution::{ExecutionContext, Unbound};
+    /// let mut context = ExecutionContext::new(42);
+    ///
+    /// Unbound::new(MockEffect::new(3)).apply(&mut context);
+    ///
+    /// assert_eq!(*context.get_context(), 45);
+    /// ```
+    fn apply(&self, context: &mut ExecutionContext<T>) {
+        self.rule.apply(&mut context.context)
+    }
+}
+
+/// An effect that lets the actor address the entities of a target, e.g. to ask them a question.
+///
+/// Turn policies like [`crate::social::practice::turn::AddressedReplies`] use it to decide who acts next.
+pub struct Address {
+    target: Target,
+}
+
+impl Address {
+    pub fn new(target: Target) -> Address {
+        Address { target }
+    }
+}
+
+impl<T> Effect<ExecutionContext<T>> for Address {
+    /// Addresses all entities of the target.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::effect::Effect;
+    ///# use rusted_social_simulation::social::execution::{Address, ExecutionContext};
+    ///# use rusted_social_simulation::social::practice::role::{Role, Target};
+    /// let speaker = Role::Character { id: 0 };
+    /// let listener = Role::Character { id: 1 };
+    /// let mut context = ExecutionContext::new(42);
+    /// context.bind(10, 5, vec![(speaker, 10), (listener, 11)].into_iter().collect());
+    ///
+    /// Address::new(Target::Others).apply(&mut context);
+    ///
+    /// assert_eq!(context.take_addressed(), vec![11]);
+    /// ```
+    fn apply(&self, context: &mut ExecutionContext<T>) {
+        for entity in context.resolve(self.target) {
+            context.address(entity);
+        }
+    }
+}
+
+/// A condition that checks, if the actor has a certain role in the current practice.
+pub struct ActorHasRole {
+    role: Role,
+}
+
+impl ActorHasRole {
+    pub fn new(role: Role) -> ActorHasRole {
+        ActorHasRole { role }
+    }
+}
+
+impl<T: RoleContext> Condition<T> for ActorHasRole {
+    /// Returns true, if the actor has the role.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::condition::Condition;
+    ///# use rusted_social_simulation::social::execution::{ActorHasRole, ExecutionContext};
+    ///# use rusted_social_simulation::social::practice::role::Role;
+    /// let speaker = Role::Character { id: 0 };
+    /// let listener = Role::Character { id: 1 };
+    /// let mut context = ExecutionContext::new(42);
+    /// context.bind(10, 5, vec![(speaker, 10), (listener, 11)].into_iter().collect());
+    ///
+    /// assert!(ActorHasRole::new(speaker).evaluate(&context));
+    /// assert!(!ActorHasRole::new(listener).evaluate(&context));
+    /// ```
+    fn evaluate(&self, context: &T) -> bool {
+        match context.get_actor() {
+            Some(actor) => context.get_entities_of(self.role).contains(&actor),
+            None => false,
+        }
+    }
+}
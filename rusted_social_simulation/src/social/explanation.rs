@@ -0,0 +1,154 @@
+use crate::social::utility::Utility;
+use serde::Serialize;
+use std::fmt;
+
+/// The result of an explained rule.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum Outcome {
+    Condition(bool),
+    Utility(Utility),
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Condition(value) => write!(f, "{}", value),
+            Outcome::Utility(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// A tree that explains how a condition or utility rule reached its result.
+///
+/// Each node contains the result of one rule & the explanations of its sub-rules.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Explanation {
+    description: String,
+    outcome: Outcome,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    children: Vec<Explanation>,
+}
+
+impl Explanation {
+    pub fn new(description: &str, outcome: Outcome, children: Vec<Explanation>) -> Explanation {
+        Explanation {
+            description: description.to_string(),
+            outcome,
+            children,
+        }
+    }
+
+    /// Explains a condition.
+    pub fn condition(description: &str, value: bool, children: Vec<Explanation>) -> Explanation {
+        Explanation::new(description, Outcome::Condition(value), children)
+    }
+
+    /// Explains an utility rule.
+    pub fn utility(description: &str, value: Utility, children: Vec<Explanation>) -> Explanation {
+        Explanation::new(description, Outcome::Utility(value), children)
+    }
+
+    pub fn get_description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn get_outcome(&self) -> Outcome {
+        self.outcome
+    }
+
+    pub fn get_children(&self) -> &[Explanation] {
+        &self.children
+    }
+
+    /// Renders the tree as indented text with one rule per line.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::explanation::Explanation;
+    ///# use rusted_social_simulation::social::utility::Utility;
+    /// let explanation = Explanation::utility("total", Utility::from_integer(14), vec![
+    ///     Explanation::utility("fixed", Utility::from_integer(9), Vec::new()),
+    ///     Explanation::condition("is_friend", true, Vec::new()),
+    /// ]);
+    ///
+    /// assert_eq!(explanation.to_text(), "total = 14\n  fixed = 9\n  is_friend = true\n");
+    /// ```
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        self.write_text(&mut text, 0);
+        text
+    }
+
+    /// Renders the tree as JSON.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::explanation::Explanation;
+    ///# use rusted_social_simulation::social::utility::Utility;
+    /// let explanation = Explanation::utility("conditional", Utility::default(), vec![
+    ///     Explanation::condition("is_friend", false, Vec::new()),
+    /// ]);
+    ///
+    /// assert_eq!(
+    ///     explanation.to_json(),
+    ///     r#"{"description":"conditional","outcome":0.0,"children":[{"description":"is_friend","outcome":false}]}"#
+    /// );
+    /// ```
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("An explanation is always serializable")
+    }
+
+    fn write_text(&self, text: &mut String, depth: usize) {
+        text.push_str(&"  ".repeat(depth));
+        text.push_str(&format!("{} = {}\n", self.description, self.outcome));
+
+        for child in &self.children {
+            child.write_text(text, depth + 1);
+        }
+    }
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_text())
+    }
+}
+
+/// Gets the name of a type without its path & generic parameters,
+/// e.g. *MockCondition* for *rusted_social_simulation::social::condition::MockCondition*.
+///
+/// Used as description by rules that don't provide their own explanation.
+pub fn get_type_name<T: ?Sized>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    let name = name.split('<').next().unwrap_or(name);
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nested_text() {
+        let five = Utility::from_integer(5);
+        let explanation = Explanation::utility(
+            "max",
+            five,
+            vec![Explanation::utility(
+                "conditional",
+                five,
+                vec![Explanation::condition("not", true, Vec::new())],
+            )],
+        );
+
+        assert_eq!(
+            explanation.to_text(),
+            "max = 5\n  conditional = 5\n    not = true\n"
+        );
+    }
+
+    #[test]
+    fn test_get_type_name() {
+        assert_eq!(get_type_name::<Explanation>(), "Explanation");
+        assert_eq!(get_type_name::<Vec<u32>>(), "Vec");
+    }
+}
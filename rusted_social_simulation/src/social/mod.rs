@@ -1,5 +1,21 @@
 pub mod action;
 pub mod condition;
+pub mod curve;
+pub mod definition;
 pub mod effect;
+pub mod emotion;
+pub mod error;
+pub mod event;
+pub mod execution;
+pub mod explanation;
+pub mod personality;
 pub mod practice;
+pub mod relationship;
+pub mod replay;
+pub mod selection;
+pub mod simulation;
+pub mod snapshot;
 pub mod utility;
+pub mod value;
+#[cfg(feature = "world")]
+pub mod world;
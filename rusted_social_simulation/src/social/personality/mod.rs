@@ -0,0 +1,134 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+pub mod utility;
+
+/// The lowest value of a trait.
+pub const MIN_TRAIT: i32 = -100;
+/// The highest value of a trait.
+pub const MAX_TRAIT: i32 = 100;
+
+/// The traits of the Big Five model.
+pub const OPENNESS: &str = "openness";
+pub const CONSCIENTIOUSNESS: &str = "conscientiousness";
+pub const EXTRAVERSION: &str = "extraversion";
+pub const AGREEABLENESS: &str = "agreeableness";
+pub const NEUROTICISM: &str = "neuroticism";
+
+/// The personality of an entity as a set of named traits.
+///
+/// Each trait is between [`MIN_TRAIT`] & [`MAX_TRAIT`], where 0 is average.
+/// Traits that were never set are 0, so any trait set can be used.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Personality {
+    traits: BTreeMap<String, i32>,
+}
+
+impl Personality {
+    pub fn new() -> Personality {
+        Personality::default()
+    }
+
+    /// Creates a personality with the traits of the Big Five model.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::personality::*;
+    /// let personality = Personality::big_five(10, 20, 80, -30, -150);
+    ///
+    /// assert_eq!(personality.get(EXTRAVERSION), 80);
+    /// assert_eq!(personality.get(NEUROTICISM), -100);
+    /// ```
+    pub fn big_five(
+        openness: i32,
+        conscientiousness: i32,
+        extraversion: i32,
+        agreeableness: i32,
+        neuroticism: i32,
+    ) -> Personality {
+        let mut personality = Personality::new();
+        personality.set(OPENNESS, openness);
+        personality.set(CONSCIENTIOUSNESS, conscientiousness);
+        personality.set(EXTRAVERSION, extraversion);
+        personality.set(AGREEABLENESS, agreeableness);
+        personality.set(NEUROTICISM, neuroticism);
+        personality
+    }
+
+    /// Gets a trait.
+    pub fn get(&self, name: &str) -> i32 {
+        self.traits.get(name).copied().unwrap_or(0)
+    }
+
+    /// Sets a trait, clamped to its bounds.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::personality::Personality;
+    /// let mut personality = Personality::new();
+    ///
+    /// personality.set("curiosity", 120);
+    ///
+    /// assert_eq!(personality.get("curiosity"), 100);
+    /// assert_eq!(personality.get("greed"), 0);
+    /// ```
+    pub fn set(&mut self, name: &str, value: i32) {
+        self.traits
+            .insert(name.to_string(), value.clamp(MIN_TRAIT, MAX_TRAIT));
+    }
+
+    /// Gets a trait as a value between -1 & 1.
+    pub fn get_normalized(&self, name: &str) -> f32 {
+        self.get(name) as f32 / MAX_TRAIT as f32
+    }
+}
+
+/// Stores the personalities of entities.
+///
+/// Entities without a personality have an average one.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Personalities {
+    entities: BTreeMap<u32, Personality>,
+}
+
+impl Personalities {
+    pub fn new() -> Personalities {
+        Personalities::default()
+    }
+
+    /// Gets the personality of an entity.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::personality::{Personalities, Personality};
+    /// let mut personalities = Personalities::new();
+    /// personalities.set(3, Personality::big_five(0, 0, 50, 0, 0));
+    ///
+    /// assert_eq!(personalities.get(3).get("extraversion"), 50);
+    /// assert_eq!(personalities.get(4), &Personality::new());
+    /// ```
+    pub fn get(&self, entity: u32) -> &Personality {
+        static AVERAGE: Personality = Personality {
+            traits: BTreeMap::new(),
+        };
+        self.entities.get(&entity).unwrap_or(&AVERAGE)
+    }
+
+    /// Sets or replaces the personality of an entity.
+    pub fn set(&mut self, entity: u32, personality: Personality) {
+        self.entities.insert(entity, personality);
+    }
+
+    /// Removes the personality of an entity.
+    pub fn remove(&mut self, entity: u32) -> Option<Personality> {
+        self.entities.remove(&entity)
+    }
+}
+
+/// A context that contains the personalities of entities.
+pub trait PersonalityContext {
+    fn get_personalities(&self) -> &Personalities;
+}
+
+impl PersonalityContext for Personalities {
+    fn get_personalities(&self) -> &Personalities {
+        self
+    }
+}
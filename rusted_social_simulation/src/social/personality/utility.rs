@@ -0,0 +1,204 @@
+use crate::social::explanation::Explanation;
+use crate::social::personality::PersonalityContext;
+use crate::social::practice::RoleContext;
+use crate::social::utility::{Utility, UtilityRule};
+
+/// Gets a trait of the actor as a value between -1 & 1. Without an actor the trait is 0.
+fn get_actor_trait<T: PersonalityContext + RoleContext>(context: &T, name: &str) -> f32 {
+    context
+        .get_actor()
+        .map(|actor| context.get_personalities().get(actor).get_normalized(name))
+        .unwrap_or(0.0)
+}
+
+/// An utility rule that scales another rule by a trait of the actor.
+///
+/// The factor is `1 + weight * trait`, with the trait between -1 & 1, but never negative.
+/// E.g. a weight of 0.5 scales the utility by 1.5 for the highest trait & by 0.5 for the lowest one,
+/// so the highest trait has three times the utility of the lowest one.
+pub struct TraitScaledUtility<T> {
+    rule: Box<dyn UtilityRule<T>>,
+    name: String,
+    weight: f32,
+}
+
+impl<T> TraitScaledUtility<T> {
+    pub fn new(rule: Box<dyn UtilityRule<T>>, name: &str, weight: f32) -> TraitScaledUtility<T> {
+        TraitScaledUtility {
+            rule,
+            name: name.to_string(),
+            weight,
+        }
+    }
+
+    fn scale(&self, utility: Utility, context: &T) -> Utility
+    where
+        T: PersonalityContext + RoleContext,
+    {
+        let factor = (1.0 + self.weight * get_actor_trait(context, &self.name)).max(0.0);
+        Utility::from_f32(utility.to_f32() * factor)
+    }
+}
+
+impl<T: PersonalityContext + RoleContext> UtilityRule<T> for TraitScaledUtility<T> {
+    fn calculate_utility(&self, context: &T) -> Utility {
+        self.scale(self.rule.calculate_utility(context), context)
+    }
+
+    fn explain(&self, context: &T) -> Explanation {
+        let child = self.rule.explain(context);
+        let utility = self.calculate_utility(context);
+        Explanation::utility(&format!("scaled by {}", self.name), utility, vec![child])
+    }
+}
+
+/// An utility rule that adds a bias to another rule depending on a trait of the actor.
+///
+/// The highest trait adds the full bias, while the lowest one subtracts it.
+pub struct TraitBiasedUtility<T> {
+    rule: Box<dyn UtilityRule<T>>,
+    name: String,
+    bias: Utility,
+}
+
+impl<T> TraitBiasedUtility<T> {
+    pub fn new(rule: Box<dyn UtilityRule<T>>, name: &str, bias: Utility) -> TraitBiasedUtility<T> {
+        TraitBiasedUtility {
+            rule,
+            name: name.to_string(),
+            bias,
+        }
+    }
+
+    fn get_bias(&self, context: &T) -> Utility
+    where
+        T: PersonalityContext + RoleContext,
+    {
+        Utility::from_f32(self.bias.to_f32() * get_actor_trait(context, &self.name))
+    }
+}
+
+impl<T: PersonalityContext + RoleContext> UtilityRule<T> for TraitBiasedUtility<T> {
+    fn calculate_utility(&self, context: &T) -> Utility {
+        self.rule
+            .calculate_utility(context)
+            .saturating_add(self.get_bias(context))
+    }
+
+    fn explain(&self, context: &T) -> Explanation {
+        let child = self.rule.explain(context);
+        let utility = self.calculate_utility(context);
+        Explanation::utility(&format!("biased by {}", self.name), utility, vec![child])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::social::action::ActionBuilder;
+    use crate::social::execution::ExecutionContext;
+    use crate::social::personality::*;
+    use crate::social::selection::{ArgMaxSelector, Selector};
+    use crate::social::utility::FixedUtility;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    const EXTRAVERT: u32 = 10;
+    const NEUROTIC: u32 = 11;
+    const AVERAGE: u32 = 12;
+
+    #[test]
+    fn test_scaled() {
+        let rule = TraitScaledUtility::new(
+            Box::new(FixedUtility::new(Utility::from_integer(40))),
+            EXTRAVERSION,
+            0.5,
+        );
+
+        assert_eq!(calculate(&rule, EXTRAVERT), Utility::from_integer(60));
+        assert_eq!(calculate(&rule, NEUROTIC), Utility::from_integer(30));
+        assert_eq!(calculate(&rule, AVERAGE), Utility::from_integer(40));
+    }
+
+    #[test]
+    fn test_scaled_is_never_negative() {
+        let rule = TraitScaledUtility::new(
+            Box::new(FixedUtility::new(Utility::from_integer(40))),
+            EXTRAVERSION,
+            2.0,
+        );
+
+        assert_eq!(calculate(&rule, NEUROTIC), Utility::default());
+    }
+
+    #[test]
+    fn test_biased() {
+        let rule = TraitBiasedUtility::new(
+            Box::new(FixedUtility::new(Utility::from_integer(10))),
+            NEUROTICISM,
+            Utility::from_integer(20),
+        );
+
+        assert_eq!(calculate(&rule, EXTRAVERT), Utility::from_integer(-2));
+        assert_eq!(calculate(&rule, NEUROTIC), Utility::from_integer(26));
+        assert_eq!(calculate(&rule, AVERAGE), Utility::from_integer(10));
+    }
+
+    #[test]
+    fn test_explain() {
+        let rule = TraitBiasedUtility::new(
+            Box::new(FixedUtility::new(Utility::from_integer(10))),
+            NEUROTICISM,
+            Utility::from_integer(20),
+        );
+        let mut context = create_context();
+        context.bind_actor(NEUROTIC);
+
+        assert_eq!(
+            rule.explain(&context).to_text(),
+            "biased by neuroticism = 26\n  fixed = 10\n"
+        );
+    }
+
+    #[test]
+    fn test_personality_changes_action() {
+        let small_talk = ActionBuilder::new("small talk")
+            .with_utility_rule(Box::new(TraitScaledUtility::new(
+                Box::new(FixedUtility::new(Utility::from_integer(10))),
+                EXTRAVERSION,
+                1.0,
+            )))
+            .build();
+        let leave = ActionBuilder::new("leave")
+            .with_utility_rule(Box::new(TraitBiasedUtility::new(
+                Box::new(FixedUtility::new(Utility::from_integer(8))),
+                NEUROTICISM,
+                Utility::from_integer(10),
+            )))
+            .build();
+        let actions = vec![small_talk.as_ref(), leave.as_ref()];
+        let mut context = create_context();
+        let mut rng = StdRng::seed_from_u64(0);
+
+        context.bind_actor(EXTRAVERT);
+        let selected = ArgMaxSelector.select(&actions, &context, &mut rng);
+        assert_eq!(selected.unwrap().get_name(), "small talk");
+
+        context.bind_actor(NEUROTIC);
+        let selected = ArgMaxSelector.select(&actions, &context, &mut rng);
+        assert_eq!(selected.unwrap().get_name(), "leave");
+    }
+
+    fn calculate<R: UtilityRule<ExecutionContext<Personalities>>>(rule: &R, actor: u32) -> Utility {
+        let mut context = create_context();
+        context.bind_actor(actor);
+        rule.calculate_utility(&context)
+    }
+
+    fn create_context() -> ExecutionContext<Personalities> {
+        let mut personalities = Personalities::new();
+        personalities.set(EXTRAVERT, Personality::big_five(0, 0, 100, 0, -60));
+        personalities.set(NEUROTIC, Personality::big_five(0, 0, -50, 0, 80));
+        ExecutionContext::new(personalities)
+    }
+}
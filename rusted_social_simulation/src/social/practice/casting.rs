@@ -0,0 +1,505 @@
+use crate::social::condition::Condition;
+use crate::social::error::SocialError;
+use crate::social::execution::ExecutionContext;
+use crate::social::practice::participants::Participants;
+use crate::social::practice::role::Role;
+use crate::social::practice::PracticeTemplate;
+use crate::social::utility::{FixedUtility, Utility, UtilityRule};
+use std::cmp::Reverse;
+
+/// Defines which entities can fill a role of a practice template & how suitable they are.
+///
+/// Both are evaluated with the candidate as actor.
+pub struct RoleCasting<T> {
+    eligibility: Vec<Box<dyn Condition<T>>>,
+    suitability: Box<dyn UtilityRule<T>>,
+}
+
+impl<T> RoleCasting<T> {
+    pub fn new(
+        eligibility: Vec<Box<dyn Condition<T>>>,
+        suitability: Box<dyn UtilityRule<T>>,
+    ) -> RoleCasting<T> {
+        RoleCasting {
+            eligibility,
+            suitability,
+        }
+    }
+
+    /// Is the candidate eligible for the role, because all conditions are true?
+    pub fn is_eligible(&self, context: &T) -> bool {
+        self.eligibility
+            .iter()
+            .all(|condition| condition.evaluate(context))
+    }
+
+    /// Gets the suitability of the candidate for the role.
+    pub fn get_suitability(&self, context: &T) -> Utility {
+        self.suitability.calculate_utility(context)
+    }
+}
+
+impl<T> Default for RoleCasting<T> {
+    /// Every entity is eligible & equally suitable.
+    fn default() -> Self {
+        RoleCasting::new(Vec::new(), Box::new(FixedUtility::new(Utility::default())))
+    }
+}
+
+/// Assigns the candidates to the roles of a template, so that the total suitability is maximal
+/// and no entity has more than one role.
+///
+/// Each role gets the minimum number of entities of its [`Cardinality`](crate::social::practice::role::Cardinality).
+/// Optional roles get a single entity, if one is available, and stay empty otherwise.
+/// Roles without a [`RoleCasting`] accept every candidate.
+/// Fails, if a required role can't be filled.
+///
+/// ```
+///# use rusted_social_simulation::social::error::SocialError;
+///# use rusted_social_simulation::social::execution::ExecutionContext;
+///# use rusted_social_simulation::social::practice::casting::cast;
+///# use rusted_social_simulation::social::practice::role::{Cardinality, Role};
+///# use rusted_social_simulation::social::practice::simple::SimplePracticeTemplate;
+/// let speaker = Role::Character { id: 0 };
+/// let listener = Role::Character { id: 1 };
+/// let witness = Role::Observer { id: 0 };
+/// let role_names = vec![
+///     (speaker, "Speaker".to_string()),
+///     (listener, "Listener".to_string()),
+///     (witness, "Witness".to_string()),
+/// ];
+/// let mut template: SimplePracticeTemplate<ExecutionContext<u32>> =
+///     SimplePracticeTemplate::new(0, "talk".to_string(), role_names.into_iter().collect(), Default::default());
+/// template.set_cardinality(witness, Cardinality::optional());
+/// let mut context = ExecutionContext::new(42);
+///
+/// assert_eq!(cast(&template, &[10, 11, 12], &mut context).unwrap().get_all(), vec![10, 11, 12]);
+/// assert_eq!(cast(&template, &[10, 11], &mut context).unwrap().get_all(), vec![10, 11]);
+/// assert_eq!(
+///     cast(&template, &[10], &mut context).err(),
+///     Some(SocialError::UncastableRole { template: "talk".to_string(), role: listener })
+/// );
+/// ```
+pub fn cast<T>(
+    template: &dyn PracticeTemplate<ExecutionContext<T>>,
+    candidates: &[u32],
+    context: &mut ExecutionContext<T>,
+) -> Result<Participants, SocialError> {
+    let mut roles = template.get_roles();
+    roles.sort_unstable();
+
+    let mut groups = Vec::new();
+
+    for role in &roles {
+        let cardinality = template.get_cardinality(*role);
+        let casting = template.get_casting(*role);
+        let mut options = Vec::new();
+
+        for candidate in candidates {
+            context.bind_actor(*candidate);
+
+            match casting {
+                Some(casting) if casting.is_eligible(context) => {
+                    options.push((*candidate, casting.get_suitability(context)))
+                }
+                Some(_) => {}
+                None => options.push((*candidate, Utility::default())),
+            }
+        }
+
+        let optional = cardinality.is_optional();
+        // The most suitable candidates are tried first, so good assignments are found early.
+        options.sort_by_key(|(_, utility)| Reverse(*utility));
+
+        groups.push(Group {
+            role: *role,
+            options,
+            count: if optional { 1 } else { cardinality.get_min() },
+            optional: optional && cardinality.contains(1),
+        });
+    }
+
+    context.unbind();
+
+    let mut search = Search::new(&groups);
+    search.run(&groups, 0, &mut Vec::new(), Utility::default());
+
+    match search.best {
+        Some((_, assigned)) => Ok(assigned.into_iter().collect()),
+        None => Err(SocialError::UncastableRole {
+            template: template.get_name().to_string(),
+            role: roles[search.depth],
+        }),
+    }
+}
+
+/// The candidates assigned to roles so far.
+type Assignment = Vec<(Role, u32)>;
+
+/// A depth-first search over the assignments of candidates to roles.
+///
+/// Branches, that can't exceed the best assignment so far, are pruned.
+struct Search {
+    best: Option<(Utility, Assignment)>,
+    /// The number of roles filled by the most complete assignment.
+    depth: usize,
+    /// The upper bound of the suitability of the roles starting at each index.
+    bounds: Vec<Utility>,
+}
+
+impl Search {
+    fn new(groups: &[Group]) -> Search {
+        let mut bounds = vec![Utility::default(); groups.len() + 1];
+
+        for (index, group) in groups.iter().enumerate().rev() {
+            let mut bound = group.get_bound(0, group.count);
+
+            if group.optional && bound < Utility::default() {
+                bound = Utility::default();
+            }
+
+            bounds[index] = bound.saturating_add(bounds[index + 1]);
+        }
+
+        Search {
+            best: None,
+            depth: 0,
+            bounds,
+        }
+    }
+
+    /// Can an assignment with this suitability & the bound of the remaining roles beat the best one?
+    fn can_improve(&self, suitability: Utility, bound: Utility) -> bool {
+        self.best
+            .as_ref()
+            .is_none_or(|(best, _)| suitability.saturating_add(bound) > *best)
+    }
+
+    /// Fills the role with the index & then the following ones.
+    fn run(
+        &mut self,
+        groups: &[Group],
+        index: usize,
+        assigned: &mut Assignment,
+        suitability: Utility,
+    ) {
+        self.depth = self.depth.max(index);
+
+        if !self.can_improve(suitability, self.bounds[index]) {
+            return;
+        }
+
+        if index == groups.len() {
+            self.best = Some((suitability, assigned.clone()));
+            return;
+        }
+
+        let group = &groups[index];
+
+        self.fill(groups, index, 0, group.count, assigned, suitability);
+
+        if group.optional {
+            self.run(groups, index + 1, assigned, suitability);
+        }
+    }
+
+    /// Tries the combinations of unassigned candidates for the role with the index,
+    /// starting at an option.
+    fn fill(
+        &mut self,
+        groups: &[Group],
+        index: usize,
+        start: usize,
+        count: usize,
+        assigned: &mut Assignment,
+        suitability: Utility,
+    ) {
+        if count == 0 {
+            return self.run(groups, index + 1, assigned, suitability);
+        }
+
+        let group = &groups[index];
+
+        for (i, (candidate, utility)) in group.options.iter().enumerate().skip(start) {
+            // The options are sorted, so the following ones can't do better.
+            if group.options.len() - i < count
+                || !self.can_improve(
+                    suitability,
+                    group
+                        .get_bound(i, count)
+                        .saturating_add(self.bounds[index + 1]),
+                )
+            {
+                return;
+            }
+
+            if assigned.iter().any(|(_, entity)| entity == candidate) {
+                continue;
+            }
+
+            assigned.push((group.role, *candidate));
+            self.fill(
+                groups,
+                index,
+                i + 1,
+                count - 1,
+                assigned,
+                suitability.saturating_add(*utility),
+            );
+            assigned.pop();
+        }
+    }
+}
+
+/// The candidates for a single role, sorted by their suitability, & how many of them are needed.
+struct Group {
+    role: Role,
+    options: Vec<(u32, Utility)>,
+    count: usize,
+    /// Can the role also stay empty? Filling it is preferred for equal suitability.
+    optional: bool,
+}
+
+impl Group {
+    /// Gets the upper bound of the suitability of the role, if it is filled starting at an option.
+    fn get_bound(&self, start: usize, count: usize) -> Utility {
+        self.options
+            .iter()
+            .skip(start)
+            .take(count)
+            .fold(Utility::default(), |sum, (_, utility)| {
+                sum.saturating_add(*utility)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::social::practice::role::Cardinality;
+    use crate::social::practice::simple::SimplePracticeTemplate;
+    use crate::social::practice::RoleContext;
+    use std::collections::HashMap;
+
+    const LEADER: Role = Role::Character { id: 0 };
+    const FOLLOWER: Role = Role::Character { id: 1 };
+
+    /// Is the actor one of the entities?
+    struct ActorIn(Vec<u32>);
+
+    impl Condition<ExecutionContext<u32>> for ActorIn {
+        fn evaluate(&self, context: &ExecutionContext<u32>) -> bool {
+            context
+                .get_actor()
+                .is_some_and(|actor| self.0.contains(&actor))
+        }
+    }
+
+    /// The utility of each actor.
+    struct ActorUtility(HashMap<u32, Utility>);
+
+    impl UtilityRule<ExecutionContext<u32>> for ActorUtility {
+        fn calculate_utility(&self, context: &ExecutionContext<u32>) -> Utility {
+            context
+                .get_actor()
+                .and_then(|actor| self.0.get(&actor).copied())
+                .unwrap_or_default()
+        }
+    }
+
+    #[test]
+    fn test_maximize_total_suitability() {
+        let mut template = create_template();
+        template.set_casting(
+            LEADER,
+            create_casting(vec![1, 2], hashmap! { 1 => 10, 2 => 9 }),
+        );
+        template.set_casting(
+            FOLLOWER,
+            create_casting(vec![1, 2], hashmap! { 1 => 8, 2 => 1 }),
+        );
+
+        assert_eq!(
+            cast(&template, &[1, 2], &mut ExecutionContext::new(0)),
+            Ok(hashmap! { LEADER => 2, FOLLOWER => 1 }.into())
+        );
+    }
+
+    #[test]
+    fn test_ineligible_candidates() {
+        let mut template = create_template();
+        template.set_casting(LEADER, create_casting(vec![3], hashmap! { 1 => 10 }));
+
+        assert_eq!(
+            cast(&template, &[1, 2, 3], &mut ExecutionContext::new(0)),
+            Ok(hashmap! { LEADER => 3, FOLLOWER => 1 }.into())
+        );
+    }
+
+    #[test]
+    fn test_no_eligible_candidate() {
+        let mut template = create_template();
+        template.set_casting(FOLLOWER, create_casting(vec![4], HashMap::new()));
+
+        assert_eq!(
+            cast(&template, &[1, 2, 3], &mut ExecutionContext::new(0)),
+            Err(SocialError::UncastableRole {
+                template: "lead".to_string(),
+                role: FOLLOWER
+            })
+        );
+    }
+
+    #[test]
+    fn test_entity_has_only_one_role() {
+        let mut template = create_template();
+        template.set_casting(LEADER, create_casting(vec![1], HashMap::new()));
+        template.set_casting(FOLLOWER, create_casting(vec![1], HashMap::new()));
+
+        assert_eq!(
+            cast(&template, &[1, 2], &mut ExecutionContext::new(0)),
+            Err(SocialError::UncastableRole {
+                template: "lead".to_string(),
+                role: FOLLOWER
+            })
+        );
+    }
+
+    #[test]
+    fn test_cast_group() {
+        let mut template = create_template();
+        template.set_cardinality(FOLLOWER, Cardinality::new(2, None));
+        template.set_casting(
+            FOLLOWER,
+            create_casting(vec![1, 2, 3], hashmap! { 1 => 5, 2 => 1, 3 => 4 }),
+        );
+
+        assert_eq!(
+            cast(&template, &[1, 2, 3, 4], &mut ExecutionContext::new(0)),
+            Ok(hashmap! { LEADER => vec![2], FOLLOWER => vec![1, 3] }.into())
+        );
+    }
+
+    #[test]
+    fn test_group_too_small() {
+        let mut template = create_template();
+        template.set_cardinality(FOLLOWER, Cardinality::exactly(3));
+
+        assert_eq!(
+            cast(&template, &[1, 2, 3], &mut ExecutionContext::new(0)),
+            Err(SocialError::UncastableRole {
+                template: "lead".to_string(),
+                role: FOLLOWER
+            })
+        );
+    }
+
+    #[test]
+    fn test_optional_role() {
+        let mut template = create_template();
+        template.set_cardinality(FOLLOWER, Cardinality::optional());
+        template.set_casting(FOLLOWER, create_casting(vec![1, 4], HashMap::new()));
+        template.set_casting(LEADER, create_casting(vec![1, 2], hashmap! { 1 => 3 }));
+
+        assert_eq!(
+            cast(&template, &[1, 2, 3], &mut ExecutionContext::new(0)),
+            Ok(hashmap! { LEADER => 1 }.into())
+        );
+        assert_eq!(
+            cast(&template, &[1, 2, 3, 4], &mut ExecutionContext::new(0)),
+            Ok(hashmap! { LEADER => 1, FOLLOWER => 4 }.into())
+        );
+    }
+
+    #[test]
+    fn test_roles_without_cardinality_are_required() {
+        let role_names = hashmap! {
+            LEADER => "Leader".to_string(),
+            FOLLOWER => "Follower".to_string(),
+        };
+        let template: SimplePracticeTemplate<ExecutionContext<u32>> =
+            SimplePracticeTemplate::new(0, "lead".to_string(), role_names, HashMap::new());
+
+        assert_eq!(
+            cast(&template, &[1, 2, 3], &mut ExecutionContext::new(0)),
+            Ok(hashmap! { LEADER => 1, FOLLOWER => 2 }.into())
+        );
+        assert_eq!(
+            cast(&template, &[1], &mut ExecutionContext::new(0)),
+            Err(SocialError::UncastableRole {
+                template: "lead".to_string(),
+                role: FOLLOWER
+            })
+        );
+    }
+
+    #[test]
+    fn test_many_candidates() {
+        let witness = Role::Observer { id: 0 };
+        let role_names = hashmap! {
+            LEADER => "Leader".to_string(),
+            FOLLOWER => "Follower".to_string(),
+            witness => "Witness".to_string(),
+        };
+        let mut template =
+            SimplePracticeTemplate::new(0, "lead".to_string(), role_names, HashMap::new());
+        template.set_cardinality(FOLLOWER, Cardinality::new(5, None));
+        template.set_cardinality(witness, Cardinality::optional());
+        let candidates: Vec<u32> = (1..=40).collect();
+        let all = candidates.clone();
+        let even = candidates.iter().copied().filter(|c| c % 2 == 0).collect();
+        template.set_casting(
+            LEADER,
+            create_casting(all.clone(), all.iter().map(|c| (*c, *c as i32)).collect()),
+        );
+        template.set_casting(
+            FOLLOWER,
+            create_casting(
+                all.clone(),
+                all.iter().map(|c| (*c, 50 - *c as i32)).collect(),
+            ),
+        );
+        template.set_casting(
+            witness,
+            create_casting(even, all.iter().map(|c| (*c, 1)).collect()),
+        );
+
+        assert_eq!(
+            cast(&template, &candidates, &mut ExecutionContext::new(0)),
+            Ok(hashmap! {
+                LEADER => vec![40],
+                FOLLOWER => vec![1, 2, 3, 4, 5],
+                witness => vec![6],
+            }
+            .into())
+        );
+    }
+
+    fn create_template() -> SimplePracticeTemplate<ExecutionContext<u32>> {
+        let role_names = hashmap! {
+            LEADER => "Leader".to_string(),
+            FOLLOWER => "Follower".to_string(),
+        };
+
+        let mut template =
+            SimplePracticeTemplate::new(0, "lead".to_string(), role_names, HashMap::new());
+        template.set_cardinality(LEADER, Cardinality::required());
+        template.set_cardinality(FOLLOWER, Cardinality::required());
+        template
+    }
+
+    fn create_casting(
+        eligible: Vec<u32>,
+        utilities: HashMap<u32, i32>,
+    ) -> RoleCasting<ExecutionContext<u32>> {
+        let utilities = utilities
+            .into_iter()
+            .map(|(actor, utility)| (actor, Utility::from_integer(utility)))
+            .collect();
+
+        RoleCasting::new(
+            vec![Box::new(ActorIn(eligible))],
+            Box::new(ActorUtility(utilities)),
+        )
+    }
+}
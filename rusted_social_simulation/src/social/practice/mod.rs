@@ -1,14 +1,107 @@
 use crate::social::action::Action;
-use crate::social::practice::role::Role;
+use crate::social::error::SocialError;
+use crate::social::practice::casting::RoleCasting;
+use crate::social::practice::participants::Participants;
+use crate::social::practice::role::{Cardinality, Role, Target};
+use crate::social::snapshot::TurnPolicySnapshot;
+use crate::social::utility::Utility;
+use std::collections::{HashMap, HashSet};
+use std::ops::Deref;
+use std::sync::Arc;
 
+pub mod casting;
+pub mod participants;
+pub mod registry;
 pub mod role;
 pub mod simple;
+pub mod staged;
+pub mod turn;
+
+/// The actions of each role.
+pub type RoleActions<T> = HashMap<Role, Vec<Box<dyn Action<T>>>>;
+
+/// Gets the actions of a role. A role without actions has none.
+pub(crate) fn get_role_actions<T>(actions: &RoleActions<T>, role: Role) -> Vec<&dyn Action<T>> {
+    match actions.get(&role) {
+        Some(actions) => actions.iter().map(|action| action.as_ref()).collect(),
+        None => Vec::new(),
+    }
+}
+
+/// The template of a practice, that is either borrowed or shared.
+///
+/// A practice with a shared template has no lifetime, so it can be stored e.g. in a registry.
+pub enum TemplateRef<'a, P: ?Sized> {
+    Borrowed(&'a P),
+    Shared(Arc<P>),
+}
+
+impl<'a, P: ?Sized> Deref for TemplateRef<'a, P> {
+    type Target = P;
+
+    fn deref(&self) -> &P {
+        match self {
+            TemplateRef::Borrowed(template) => template,
+            TemplateRef::Shared(template) => template.as_ref(),
+        }
+    }
+}
+
+/// The roles of a practice template with their names, cardinalities & castings.
+pub struct TemplateRoles<T> {
+    names: HashMap<Role, String>,
+    cardinalities: HashMap<Role, Cardinality>,
+    castings: HashMap<Role, RoleCasting<T>>,
+}
+
+impl<T> TemplateRoles<T> {
+    pub fn new(names: HashMap<Role, String>) -> TemplateRoles<T> {
+        TemplateRoles {
+            names,
+            cardinalities: HashMap::new(),
+            castings: HashMap::new(),
+        }
+    }
+
+    pub fn get_roles(&self) -> Vec<Role> {
+        self.names.keys().copied().collect()
+    }
+
+    /// Gets the name of a role in the template with the name.
+    pub fn try_get_name(&self, template: &str, role: Role) -> Result<&str, SocialError> {
+        self.names
+            .get(&role)
+            .map(|name| name.as_str())
+            .ok_or_else(|| SocialError::UnknownRole {
+                template: template.to_string(),
+                role,
+            })
+    }
+
+    pub fn get_cardinality(&self, role: Role) -> Cardinality {
+        self.cardinalities.get(&role).copied().unwrap_or_default()
+    }
+
+    /// Sets how many entities a role needs at least & can have at most.
+    pub fn set_cardinality(&mut self, role: Role, cardinality: Cardinality) {
+        self.cardinalities.insert(role, cardinality);
+    }
+
+    pub fn get_casting(&self, role: Role) -> Option<&RoleCasting<T>> {
+        self.castings.get(&role)
+    }
+
+    /// Sets which entities can fill a role & how suitable they are.
+    pub fn set_casting(&mut self, role: Role, casting: RoleCasting<T>) {
+        self.castings.insert(role, casting);
+    }
+}
 
 /// A template for a social practice.
 ///
 /// It defines which roles participate in a social practice
 /// and which actions are available for each role.
-pub trait PracticeTemplate<T> {
+pub trait PracticeTemplate<T>: Send + Sync {
     /// Gets all actions of a role in this practice template.
     fn get_actions(&self, role: Role) -> Vec<&dyn Action<T>>;
 
@@ -22,7 +115,47 @@ pub trait PracticeTemplate<T> {
     fn get_roles(&self) -> Vec<Role>;
 
     /// Gets the name of a role in this practice template.
-    fn get_role_name(&self, role: Role) -> &str;
+    fn try_get_role_name(&self, role: Role) -> Result<&str, SocialError>;
+
+    /// Gets the name of a role in this practice template.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::practice::role::Role;
+    ///# use rusted_social_simulation::social::practice::simple::create_test_template;
+    ///# use rusted_social_simulation::social::practice::PracticeTemplate;
+    /// let speaker = Role::Character { id: 0 };
+    /// let template = create_test_template();
+    ///
+    /// assert_eq!(template.get_role_name(speaker), "Speaker");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// ```should_panic
+    ///# use rusted_social_simulation::social::practice::role::Role;
+    ///# use rusted_social_simulation::social::practice::simple::create_test_template;
+    ///# use rusted_social_simulation::social::practice::PracticeTemplate;
+    /// let unknown_role = Role::Character { id: 99 };
+    /// let template = create_test_template();
+    ///
+    /// template.get_role_name(unknown_role);
+    /// ```
+    fn get_role_name(&self, role: Role) -> &str {
+        self.try_get_role_name(role)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Gets how many entities a role needs at least & can have at most.
+    fn get_cardinality(&self, _role: Role) -> Cardinality {
+        Cardinality::default()
+    }
+
+    /// Gets which entities can fill a role & how suitable they are. None accepts every entity.
+    fn get_casting(&self, _role: Role) -> Option<&RoleCasting<T>> {
+        None
+    }
 }
 
 /// A social practice, which is an instance of a template.
@@ -32,12 +165,238 @@ pub trait Practice<T> {
     /// Gets all actions of an entity in this practice.
     fn get_actions(&self, entity: u32) -> Vec<&dyn Action<T>>;
 
+    /// Gets all actions of an entity in this practice, even if it isn't its turn.
+    fn get_potential_actions(&self, entity: u32) -> Vec<&dyn Action<T>> {
+        self.get_actions(entity)
+    }
+
+    /// Gets all entities that participate in this practice.
+    fn get_entities(&self) -> Vec<u32>;
+
+    /// Gets the entity that has a role in this practice.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::practice::role::Role;
+    ///# use rusted_social_simulation::social::practice::simple::{create_test_template, create_test_practice};
+    ///# use rusted_social_simulation::social::practice::{PracticeTemplate, Practice};
+    /// let template = create_test_template();
+    /// let practice = create_test_practice(&template);
+    ///
+    /// assert_eq!(practice.get_entity(Role::Character { id: 1 }), Some(11));
+    /// assert_eq!(practice.get_entity(Role::Character { id: 99 }), None);
+    /// ```
+    fn get_entity(&self, role: Role) -> Option<u32> {
+        self.get_entities_of(role).first().copied()
+    }
+
+    /// Gets all entities that have a role in this practice, sorted by their id.
+    fn get_entities_of(&self, role: Role) -> Vec<u32> {
+        self.get_entities()
+            .into_iter()
+            .filter(|entity| self.try_get_role(*entity) == Ok(role))
+            .collect()
+    }
+
+    /// Gets which entity has which role in this practice.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::practice::role::Role;
+    ///# use rusted_social_simulation::social::practice::simple::{create_test_template, create_test_practice};
+    ///# use rusted_social_simulation::social::practice::{PracticeTemplate, Practice};
+    /// let template = create_test_template();
+    /// let practice = create_test_practice(&template);
+    ///
+    /// let participants = practice.get_participants();
+    ///
+    /// assert_eq!(participants.get_all(), vec![10, 11]);
+    /// assert_eq!(participants.get_entity(Role::Character { id: 0 }), Some(10));
+    /// ```
+    fn get_participants(&self) -> Participants {
+        self.get_entities()
+            .into_iter()
+            .filter_map(|entity| self.try_get_role(entity).ok().map(|role| (role, entity)))
+            .collect()
+    }
+
     /// Gets the id of this social practice.
     fn get_id(&self) -> u32;
 
     /// Gets the role of an entity that participate in this practice.
-    fn get_role(&self, entity: u32) -> Role;
+    fn try_get_role(&self, entity: u32) -> Result<Role, SocialError>;
+
+    /// Gets the role of an entity that participate in this practice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::practice::role::Role;
+    ///# use rusted_social_simulation::social::practice::simple::{create_test_template, create_test_practice};
+    ///# use rusted_social_simulation::social::practice::{PracticeTemplate, Practice};
+    /// let template = create_test_template();
+    /// let practice = create_test_practice(&template);
+    ///
+    /// assert_eq!(practice.get_role(10), Role::Character { id: 0 });
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// ```should_panic
+    ///# use rusted_social_simulation::social::practice::role::Role;
+    ///# use rusted_social_simulation::social::practice::simple::{create_test_template, create_test_practice};
+    ///# use rusted_social_simulation::social::practice::{PracticeTemplate, Practice};
+    /// let template = create_test_template();
+    /// let practice = create_test_practice(&template);
+    ///
+    /// practice.get_role(99);
+    /// ```
+    fn get_role(&self, entity: u32) -> Role {
+        self.try_get_role(entity)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
 
     /// Gets the template of this practice.
     fn get_template(&self) -> &dyn PracticeTemplate<T>;
+
+    /// Gets the name of the current stage, if the practice has stages.
+    fn get_stage(&self) -> Option<&str> {
+        None
+    }
+
+    /// Has the practice reached its end? A finished practice has no actions.
+    fn is_finished(&self) -> bool {
+        false
+    }
+
+    /// Advances the lifecycle of the practice, e.g. by transitioning to another stage.
+    fn update(&mut self, _context: &T) {}
+
+    /// Gets the entities, whose turn it is. By default all entities with a role that can act, can act at any time.
+    fn get_turn(&self) -> Vec<u32> {
+        self.get_participants().get_actors()
+    }
+
+    /// Checks, if an entity participates & it is its turn.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::error::SocialError;
+    ///# use rusted_social_simulation::social::practice::simple::{create_test_template, create_test_practice};
+    ///# use rusted_social_simulation::social::practice::Practice;
+    /// let template = create_test_template();
+    /// let practice = create_test_practice(&template);
+    ///
+    /// assert_eq!(practice.check_turn(11), Ok(()));
+    /// assert_eq!(practice.check_turn(99), Err(SocialError::UnknownEntity { practice: 5, entity: 99 }));
+    /// ```
+    fn check_turn(&self, entity: u32) -> Result<(), SocialError> {
+        self.try_get_role(entity)?;
+
+        if self.get_turn().contains(&entity) {
+            Ok(())
+        } else {
+            Err(SocialError::OutOfTurn {
+                practice: self.get_id(),
+                entity,
+            })
+        }
+    }
+
+    /// Does the practice need the initiative of its entities to decide whose turn it is?
+    fn uses_initiative(&self) -> bool {
+        false
+    }
+
+    /// Sets the initiative, which is the highest utility of the available actions of each entity.
+    fn set_initiative(&mut self, _initiative: Vec<(u32, Utility)>) {}
+
+    /// Passes the turn on, after an entity acted & addressed other entities.
+    fn end_turn(&mut self, _actor: u32, _addressed: &[u32]) {}
+
+    /// Passes the turn on, after an entity had no available action.
+    fn pass_turn(&mut self, _entity: u32) {}
+
+    /// Saves the turn policy & its state, if the practice has a serializable one.
+    fn save_turn_policy(&self) -> Option<TurnPolicySnapshot> {
+        None
+    }
+}
+
+/// Checks, if each role is part of the template & has an allowed number of entities
+/// and each entity has only one role.
+pub(crate) fn validate_participants<T>(
+    practice: u32,
+    participants: &Participants,
+    template: &dyn PracticeTemplate<T>,
+) -> Result<(), SocialError> {
+    let mut entities = HashSet::new();
+
+    for (role, entity) in participants.get_pairs() {
+        template.try_get_role_name(role)?;
+
+        if !entities.insert(entity) {
+            return Err(SocialError::DuplicateEntity { practice, entity });
+        }
+    }
+
+    let mut roles = template.get_roles();
+    roles.sort_unstable();
+
+    for role in roles {
+        let count = participants.get_entities(role).len();
+
+        if !template.get_cardinality(role).contains(count) {
+            return Err(SocialError::InvalidCardinality {
+                template: template.get_name().to_string(),
+                role,
+                count,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Gets the role of an entity that participates in a practice.
+pub(crate) fn try_get_role(
+    practice: u32,
+    participants: &Participants,
+    entity: u32,
+) -> Result<Role, SocialError> {
+    participants
+        .get_role(entity)
+        .ok_or(SocialError::UnknownEntity { practice, entity })
+}
+
+/// A context that knows which entities participate in the current practice,
+/// so rules can refer to them by their role.
+pub trait RoleContext {
+    /// Gets the entity that executes the current action.
+    fn get_actor(&self) -> Option<u32>;
+
+    /// Gets the entity that has a role in the current practice.
+    /// For roles with multiple entities, it is the one with the lowest id.
+    fn get_entity(&self, role: Role) -> Option<u32>;
+
+    /// Gets all entities that have a role in the current practice, sorted by their id.
+    fn get_entities_of(&self, role: Role) -> Vec<u32> {
+        self.get_entity(role).into_iter().collect()
+    }
+
+    /// Gets all entities that participate in the current practice, sorted by their id.
+    fn get_participants(&self) -> Vec<u32>;
+
+    /// Gets the entities a target refers to.
+    fn resolve(&self, target: Target) -> Vec<u32> {
+        match target {
+            Target::Actor => self.get_actor().into_iter().collect(),
+            Target::Role(role) => self.get_entities_of(role),
+            Target::Entity(entity) => vec![entity],
+            Target::Others => {
+                let actor = self.get_actor();
+                self.get_participants()
+                    .into_iter()
+                    .filter(|entity| Some(*entity) != actor)
+                    .collect()
+            }
+        }
+    }
 }
@@ -0,0 +1,166 @@
+use crate::social::practice::role::Role;
+use std::collections::HashMap;
+use std::iter::FromIterator;
+
+/// Which entities participate in a practice & which role they have.
+///
+/// Each entity has one role, but a role can have multiple entities, e.g. the students of a lecture.
+///
+/// ```
+///# use rusted_social_simulation::social::practice::participants::Participants;
+///# use rusted_social_simulation::social::practice::role::Role;
+/// let teacher = Role::Character { id: 0 };
+/// let student = Role::Character { id: 1 };
+/// let participants: Participants = vec![(teacher, 10), (student, 12), (student, 11)].into_iter().collect();
+///
+/// assert_eq!(participants.get_entities(student), &[11, 12]);
+/// assert_eq!(participants.get_entity(student), Some(11));
+/// assert_eq!(participants.get_role(12), Some(student));
+/// assert_eq!(participants.get_all(), vec![10, 11, 12]);
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Participants {
+    entities: HashMap<Role, Vec<u32>>,
+}
+
+impl Participants {
+    pub fn new() -> Participants {
+        Participants::default()
+    }
+
+    /// Adds an entity with a role. The entities of each role stay sorted by their id.
+    pub fn add(&mut self, role: Role, entity: u32) {
+        let entities = self.entities.entry(role).or_default();
+
+        if let Err(index) = entities.binary_search(&entity) {
+            entities.insert(index, entity);
+        }
+    }
+
+    /// Gets all entities with the role, sorted by their id.
+    pub fn get_entities(&self, role: Role) -> &[u32] {
+        self.entities
+            .get(&role)
+            .map(|entities| entities.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Gets the entity with the role. For roles with multiple entities, it is the one with the lowest id.
+    pub fn get_entity(&self, role: Role) -> Option<u32> {
+        self.get_entities(role).first().copied()
+    }
+
+    /// Gets the first role of the entity.
+    pub fn get_role(&self, entity: u32) -> Option<Role> {
+        self.get_roles()
+            .into_iter()
+            .find(|role| self.get_entities(*role).contains(&entity))
+    }
+
+    /// Gets all roles with at least one entity, sorted.
+    pub fn get_roles(&self) -> Vec<Role> {
+        let mut roles: Vec<Role> = self
+            .entities
+            .iter()
+            .filter(|(_, entities)| !entities.is_empty())
+            .map(|(role, _)| *role)
+            .collect();
+        roles.sort_unstable();
+        roles
+    }
+
+    /// Gets all entities, sorted by their id. An entity with multiple roles is included multiple times.
+    pub fn get_all(&self) -> Vec<u32> {
+        let mut entities: Vec<u32> = self.entities.values().flatten().copied().collect();
+        entities.sort_unstable();
+        entities
+    }
+
+    /// Gets all entities with a role that can act, sorted by their id.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::practice::participants::Participants;
+    ///# use rusted_social_simulation::social::practice::role::Role;
+    /// let speaker = Role::Character { id: 0 };
+    /// let witness = Role::Observer { id: 0 };
+    /// let participants: Participants = vec![(speaker, 10), (witness, 11)].into_iter().collect();
+    ///
+    /// assert_eq!(participants.get_actors(), vec![10]);
+    /// ```
+    pub fn get_actors(&self) -> Vec<u32> {
+        let mut entities: Vec<u32> = self
+            .entities
+            .iter()
+            .filter(|(role, _)| role.can_act())
+            .flat_map(|(_, entities)| entities.iter().copied())
+            .collect();
+        entities.sort_unstable();
+        entities
+    }
+
+    /// Gets the role of the entity, if it is one that can act.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::practice::participants::Participants;
+    ///# use rusted_social_simulation::social::practice::role::Role;
+    /// let speaker = Role::Character { id: 0 };
+    /// let witness = Role::Observer { id: 0 };
+    /// let participants: Participants = vec![(speaker, 10), (witness, 11)].into_iter().collect();
+    ///
+    /// assert_eq!(participants.get_acting_role(10), Some(speaker));
+    /// assert_eq!(participants.get_acting_role(11), None);
+    /// assert_eq!(participants.get_acting_role(99), None);
+    /// ```
+    pub fn get_acting_role(&self, entity: u32) -> Option<Role> {
+        self.get_role(entity).filter(|role| role.can_act())
+    }
+
+    /// Can the entity act, because it has a role that can act?
+    pub fn can_act(&self, entity: u32) -> bool {
+        self.get_acting_role(entity).is_some()
+    }
+
+    /// Gets all pairs of role & entity, sorted by role & entity.
+    pub fn get_pairs(&self) -> Vec<(Role, u32)> {
+        self.get_roles()
+            .into_iter()
+            .flat_map(|role| {
+                self.get_entities(role)
+                    .iter()
+                    .map(move |entity| (role, *entity))
+            })
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entities.values().all(|entities| entities.is_empty())
+    }
+}
+
+impl FromIterator<(Role, u32)> for Participants {
+    fn from_iter<I: IntoIterator<Item = (Role, u32)>>(iter: I) -> Self {
+        let mut participants = Participants::new();
+
+        for (role, entity) in iter {
+            participants.add(role, entity);
+        }
+
+        participants
+    }
+}
+
+impl From<HashMap<Role, u32>> for Participants {
+    /// Each role has a single entity.
+    fn from(role_to_id_map: HashMap<Role, u32>) -> Self {
+        role_to_id_map.into_iter().collect()
+    }
+}
+
+impl From<HashMap<Role, Vec<u32>>> for Participants {
+    fn from(role_to_ids_map: HashMap<Role, Vec<u32>>) -> Self {
+        role_to_ids_map
+            .into_iter()
+            .flat_map(|(role, entities)| entities.into_iter().map(move |entity| (role, entity)))
+            .collect()
+    }
+}
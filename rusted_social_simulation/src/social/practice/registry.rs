@@ -0,0 +1,367 @@
+use crate::social::error::SocialError;
+use crate::social::practice::participants::Participants;
+use crate::social::practice::simple::SimplePractice;
+use crate::social::practice::{Practice, PracticeTemplate};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+/// Owns the practice templates & the active practices created from them.
+///
+/// The templates are shared via [`Arc`], so they can also be used by other threads.
+///
+/// The active practices are indexed by their participating entities.
+pub struct PracticeRegistry<T> {
+    templates: HashMap<u32, Arc<dyn PracticeTemplate<T>>>,
+    practices: BTreeMap<u32, Box<dyn Practice<T>>>,
+    entity_index: HashMap<u32, Vec<u32>>,
+    next_id: u32,
+}
+
+impl<T> PracticeRegistry<T> {
+    pub fn new() -> PracticeRegistry<T> {
+        PracticeRegistry {
+            templates: HashMap::new(),
+            practices: BTreeMap::new(),
+            entity_index: HashMap::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Adds a template, if no other template has the same id.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::error::SocialError;
+    ///# use rusted_social_simulation::social::practice::registry::PracticeRegistry;
+    ///# use rusted_social_simulation::social::practice::simple::create_test_template;
+    ///# use std::sync::Arc;
+    /// let mut registry = PracticeRegistry::new();
+    ///
+    /// assert_eq!(registry.add_template(Arc::new(create_test_template())), Ok(()));
+    /// assert_eq!(
+    ///     registry.add_template(Arc::new(create_test_template())),
+    ///     Err(SocialError::DuplicateTemplate { id: 42 })
+    /// );
+    /// ```
+    pub fn add_template(
+        &mut self,
+        template: Arc<dyn PracticeTemplate<T>>,
+    ) -> Result<(), SocialError> {
+        let id = template.get_id();
+
+        if self.templates.contains_key(&id) {
+            return Err(SocialError::DuplicateTemplate { id });
+        }
+
+        self.templates.insert(id, template);
+        Ok(())
+    }
+
+    /// Gets the template with the id.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::error::SocialError;
+    ///# use rusted_social_simulation::social::practice::registry::PracticeRegistry;
+    ///# use rusted_social_simulation::social::practice::simple::create_test_template;
+    ///# use std::sync::Arc;
+    /// let mut registry = PracticeRegistry::new();
+    /// registry.add_template(Arc::new(create_test_template())).unwrap();
+    ///
+    /// assert_eq!(registry.try_get_template(42).unwrap().get_name(), "template0");
+    /// assert_eq!(registry.try_get_template(3).err(), Some(SocialError::MissingTemplate { id: 3 }));
+    /// ```
+    pub fn try_get_template(&self, id: u32) -> Result<&Arc<dyn PracticeTemplate<T>>, SocialError> {
+        self.templates
+            .get(&id)
+            .ok_or(SocialError::MissingTemplate { id })
+    }
+
+    /// Creates a practice from the template with the id & returns the generated id of the practice.
+    ///
+    /// ```
+    ///# use maplit::hashmap;
+    ///# use rusted_social_simulation::social::practice::registry::PracticeRegistry;
+    ///# use rusted_social_simulation::social::practice::role::Role;
+    ///# use rusted_social_simulation::social::practice::simple::create_test_template;
+    ///# use rusted_social_simulation::social::practice::Practice;
+    ///# use std::sync::Arc;
+    /// let speaker = Role::Character { id: 0 };
+    /// let listener = Role::Character { id: 1 };
+    /// let mut registry = PracticeRegistry::new();
+    /// registry.add_template(Arc::new(create_test_template())).unwrap();
+    ///
+    /// assert_eq!(registry.create_practice(42, hashmap! { speaker => 10, listener => 11 }), Ok(0));
+    /// assert_eq!(registry.create_practice(42, hashmap! { speaker => 11, listener => 12 }), Ok(1));
+    /// assert_eq!(registry.get_practice(1).unwrap().get_entities(), vec![11, 12]);
+    /// ```
+    pub fn create_practice(
+        &mut self,
+        template: u32,
+        participants: impl Into<Participants>,
+    ) -> Result<u32, SocialError>
+    where
+        T: 'static,
+    {
+        let template = self.try_get_template(template)?.clone();
+
+        self.add_practice(|id| {
+            let practice = SimplePractice::try_new_shared(id, participants, template)?;
+            Ok(Box::new(practice))
+        })
+    }
+
+    /// Adds a practice of any kind, e.g. a staged or a turn-taking one,
+    /// & returns the generated id of the practice, that is passed to the factory.
+    /// The created practice must use that id.
+    ///
+    /// ```
+    ///# use maplit::hashmap;
+    ///# use rusted_social_simulation::social::practice::registry::PracticeRegistry;
+    ///# use rusted_social_simulation::social::practice::role::Role;
+    ///# use rusted_social_simulation::social::practice::staged::{create_test_staged_template, StagedPractice};
+    ///# use rusted_social_simulation::social::practice::Practice;
+    ///# use std::sync::Arc;
+    /// let speaker = Role::Character { id: 0 };
+    /// let listener = Role::Character { id: 1 };
+    /// let template = Arc::new(create_test_staged_template());
+    /// let mut registry = PracticeRegistry::new();
+    ///
+    /// let id = registry.add_practice(|id| {
+    ///     let participants = hashmap! { speaker => 10, listener => 11 };
+    ///     Ok(Box::new(StagedPractice::try_new_shared(id, participants, template)?))
+    /// });
+    ///
+    /// assert_eq!(id, Ok(0));
+    /// assert_eq!(registry.get_practice(0).unwrap().get_stage(), Some("greeting"));
+    /// ```
+    pub fn add_practice<F>(&mut self, factory: F) -> Result<u32, SocialError>
+    where
+        F: FnOnce(u32) -> Result<Box<dyn Practice<T>>, SocialError>,
+    {
+        let id = self.next_id;
+        let practice = factory(id)?;
+
+        if practice.get_id() != id {
+            return Err(SocialError::PracticeIdMismatch {
+                expected: id,
+                actual: practice.get_id(),
+            });
+        }
+
+        for entity in practice.get_entities() {
+            self.entity_index.entry(entity).or_default().push(id);
+        }
+
+        self.practices.insert(id, practice);
+        self.next_id += 1;
+        Ok(id)
+    }
+
+    /// Removes a practice, e.g. after it finished.
+    pub fn remove_practice(&mut self, id: u32) -> Option<Box<dyn Practice<T>>> {
+        let practice = self.practices.remove(&id)?;
+
+        for entity in practice.get_entities() {
+            if let Some(practices) = self.entity_index.get_mut(&entity) {
+                practices.retain(|practice| *practice != id);
+
+                if practices.is_empty() {
+                    self.entity_index.remove(&entity);
+                }
+            }
+        }
+
+        Some(practice)
+    }
+
+    /// Gets the active practice with the id.
+    pub fn get_practice(&self, id: u32) -> Option<&dyn Practice<T>> {
+        self.practices.get(&id).map(|practice| practice.as_ref())
+    }
+
+    /// Gets the active practice with the id to modify it, e.g. to update its lifecycle.
+    pub fn get_practice_mut(&mut self, id: u32) -> Option<&mut (dyn Practice<T> + 'static)> {
+        self.practices
+            .get_mut(&id)
+            .map(|practice| practice.as_mut())
+    }
+
+    /// Gets all active practices sorted by their id.
+    pub fn get_practices(&self) -> Vec<&dyn Practice<T>> {
+        self.practices
+            .values()
+            .map(|practice| practice.as_ref())
+            .collect()
+    }
+
+    /// Gets the active practices an entity participates in, sorted by their id.
+    pub fn get_practices_of(&self, entity: u32) -> Vec<&dyn Practice<T>> {
+        self.entity_index
+            .get(&entity)
+            .map(|ids| ids.iter().filter_map(|id| self.get_practice(*id)).collect())
+            .unwrap_or_default()
+    }
+}
+
+impl<T> Default for PracticeRegistry<T> {
+    fn default() -> Self {
+        PracticeRegistry::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::social::practice::role::Role;
+    use crate::social::practice::simple::create_test_template;
+    use crate::social::practice::staged::{create_test_staged_template, StagedPractice};
+    use crate::social::practice::turn::{RoundRobin, TurnTakingPractice};
+
+    const SPEAKER: Role = Role::Character { id: 0 };
+    const LISTENER: Role = Role::Character { id: 1 };
+
+    #[test]
+    fn test_get_practices_of() {
+        let registry = create_registry();
+
+        assert_eq!(get_ids(registry.get_practices_of(10)), vec![0, 1]);
+        assert_eq!(get_ids(registry.get_practices_of(11)), vec![0]);
+        assert_eq!(get_ids(registry.get_practices_of(12)), vec![1]);
+        assert!(registry.get_practices_of(99).is_empty());
+    }
+
+    #[test]
+    fn test_remove_practice() {
+        let mut registry = create_registry();
+
+        assert_eq!(registry.remove_practice(0).map(|p| p.get_id()), Some(0));
+        assert!(registry.remove_practice(0).is_none());
+
+        assert_eq!(get_ids(registry.get_practices()), vec![1]);
+        assert_eq!(get_ids(registry.get_practices_of(10)), vec![1]);
+        assert!(registry.get_practices_of(11).is_empty());
+    }
+
+    #[test]
+    fn test_create_practice_with_invalid_roles() {
+        let mut registry = create_registry();
+        let unknown_role = Role::Character { id: 99 };
+
+        assert_eq!(
+            registry.create_practice(42, hashmap! { SPEAKER => 13, LISTENER => 13 }),
+            Err(SocialError::DuplicateEntity {
+                practice: 2,
+                entity: 13
+            })
+        );
+        assert!(registry
+            .create_practice(42, hashmap! { unknown_role => 13 })
+            .is_err());
+        assert!(registry.get_practices_of(13).is_empty());
+        assert_eq!(
+            registry.create_practice(42, hashmap! { SPEAKER => 13, LISTENER => 14 }),
+            Ok(2)
+        );
+    }
+
+    #[test]
+    fn test_create_practice_with_unknown_template() {
+        let mut registry = create_registry();
+
+        assert_eq!(
+            registry.create_practice(3, hashmap! { SPEAKER => 13 }),
+            Err(SocialError::MissingTemplate { id: 3 })
+        );
+    }
+
+    #[test]
+    fn test_add_turn_taking_staged_practice() {
+        let mut registry = create_registry();
+        let template = Arc::new(create_test_staged_template());
+
+        let id = registry.add_practice(|id| {
+            let participants = hashmap! { SPEAKER => 12, LISTENER => 13 };
+            let practice = StagedPractice::try_new_shared(id, participants, template)?;
+            let policy = Box::new(RoundRobin::new(vec![SPEAKER, LISTENER]));
+            Ok(Box::new(TurnTakingPractice::new(
+                Box::new(practice),
+                policy,
+            )))
+        });
+
+        assert_eq!(id, Ok(2));
+        assert_eq!(get_ids(registry.get_practices_of(12)), vec![1, 2]);
+        assert_eq!(get_ids(registry.get_practices_of(13)), vec![2]);
+
+        let practice = registry.get_practice_mut(2).unwrap();
+        practice.update(&0);
+
+        assert_eq!(practice.get_stage(), Some("small talk"));
+        assert_eq!(practice.get_turn(), vec![12]);
+    }
+
+    #[test]
+    fn test_add_invalid_practice() {
+        let mut registry = create_registry();
+        let template = Arc::new(create_test_staged_template());
+
+        assert_eq!(
+            registry.add_practice(|id| {
+                let participants = hashmap! { SPEAKER => 13, LISTENER => 13 };
+                Ok(Box::new(StagedPractice::try_new_shared(
+                    id,
+                    participants,
+                    template,
+                )?))
+            }),
+            Err(SocialError::DuplicateEntity {
+                practice: 2,
+                entity: 13
+            })
+        );
+        assert!(registry.get_practices_of(13).is_empty());
+    }
+
+    #[test]
+    fn test_add_practice_with_other_id() {
+        let mut registry = create_registry();
+        let template = Arc::new(create_test_staged_template());
+
+        assert_eq!(
+            registry.add_practice(|_| {
+                let participants = hashmap! { SPEAKER => 13, LISTENER => 14 };
+                Ok(Box::new(StagedPractice::try_new_shared(
+                    7,
+                    participants,
+                    template,
+                )?))
+            }),
+            Err(SocialError::PracticeIdMismatch {
+                expected: 2,
+                actual: 7
+            })
+        );
+        assert!(registry.get_practices_of(13).is_empty());
+        assert_eq!(
+            registry.create_practice(42, hashmap! { SPEAKER => 13, LISTENER => 14 }),
+            Ok(2)
+        );
+    }
+
+    fn create_registry() -> PracticeRegistry<u32> {
+        let mut registry = PracticeRegistry::new();
+        registry
+            .add_template(Arc::new(create_test_template()))
+            .unwrap();
+        registry
+            .create_practice(42, hashmap! { SPEAKER => 10, LISTENER => 11 })
+            .unwrap();
+        registry
+            .create_practice(42, hashmap! { SPEAKER => 12, LISTENER => 10 })
+            .unwrap();
+        registry
+    }
+
+    fn get_ids(practices: Vec<&dyn Practice<u32>>) -> Vec<u32> {
+        practices.iter().map(|practice| practice.get_id()).collect()
+    }
+}
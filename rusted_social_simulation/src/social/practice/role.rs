@@ -1,4 +1,8 @@
+use crate::social::error::SocialError;
 use core::fmt;
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::str::FromStr;
 
 /// A role in a social practice.
 ///
@@ -9,15 +13,200 @@ use core::fmt;
 /// let role0 = Role::Character{ id: 0 };
 /// let role1 = Role::Character{ id: 1 };
 /// ```
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+///
+/// Only characters act. Observers witness the practice, while objects & locations are used by it.
+///
+/// Each role has a stable key, which is also used to serialize it:
+///
+/// ```
+///# use rusted_social_simulation::social::practice::role::Role;
+/// let role = Role::Observer { id: 2 };
+///
+/// assert_eq!(role.get_key(), "observer:2");
+/// assert_eq!("observer:2".parse(), Ok(role));
+/// assert_eq!(role.to_string(), "Observer(2)");
+/// assert_eq!(ron::from_str("\"observer:2\""), Ok(role));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
 pub enum Role {
+    /// A character that acts in the practice.
     Character { id: u32 },
+    /// A character that witnesses the practice, but can't act.
+    Observer { id: u32 },
+    /// An object used by the practice, e.g. a gift.
+    Object { id: u32 },
+    /// A location of the practice, e.g. a tavern.
+    Location { id: u32 },
+}
+
+impl Role {
+    /// Gets the id, that is unique for each type of role in a practice.
+    pub fn get_id(&self) -> u32 {
+        match self {
+            Role::Character { id }
+            | Role::Observer { id }
+            | Role::Object { id }
+            | Role::Location { id } => *id,
+        }
+    }
+
+    /// Gets the type of the role as used by the key.
+    pub fn get_type(&self) -> &'static str {
+        match self {
+            Role::Character { .. } => "character",
+            Role::Observer { .. } => "observer",
+            Role::Object { .. } => "object",
+            Role::Location { .. } => "location",
+        }
+    }
+
+    /// Gets the stable key of the role, e.g. to reference it in data files.
+    pub fn get_key(&self) -> String {
+        format!("{}:{}", self.get_type(), self.get_id())
+    }
+
+    /// Can an entity with the role execute actions?
+    pub fn can_act(&self) -> bool {
+        matches!(self, Role::Character { .. })
+    }
+
+    /// Is an entity with the role a character, that can witness the practice?
+    pub fn is_character(&self) -> bool {
+        matches!(self, Role::Character { .. } | Role::Observer { .. })
+    }
 }
 
 impl fmt::Display for Role {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Role::Character { id } => write!(f, "Character({})", id),
+            Role::Observer { id } => write!(f, "Observer({})", id),
+            Role::Object { id } => write!(f, "Object({})", id),
+            Role::Location { id } => write!(f, "Location({})", id),
+        }
+    }
+}
+
+impl FromStr for Role {
+    type Err = SocialError;
+
+    /// Parses the key of a role.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::error::SocialError;
+    ///# use rusted_social_simulation::social::practice::role::Role;
+    /// assert_eq!("location:3".parse(), Ok(Role::Location { id: 3 }));
+    /// assert_eq!(
+    ///     "speaker".parse::<Role>(),
+    ///     Err(SocialError::InvalidRoleKey { key: "speaker".to_string() })
+    /// );
+    /// ```
+    fn from_str(key: &str) -> Result<Self, Self::Err> {
+        let error = || SocialError::InvalidRoleKey {
+            key: key.to_string(),
+        };
+        let (role_type, id) = key.split_once(':').ok_or_else(error)?;
+        let id = id.parse().map_err(|_| error())?;
+
+        match role_type {
+            "character" => Ok(Role::Character { id }),
+            "observer" => Ok(Role::Observer { id }),
+            "object" => Ok(Role::Object { id }),
+            "location" => Ok(Role::Location { id }),
+            _ => Err(error()),
         }
     }
 }
+
+impl From<Role> for String {
+    fn from(role: Role) -> Self {
+        role.get_key()
+    }
+}
+
+impl TryFrom<String> for Role {
+    type Error = SocialError;
+
+    fn try_from(key: String) -> Result<Self, Self::Error> {
+        key.parse()
+    }
+}
+
+/// A symbolic reference to entities relative to the current practice & action.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum Target {
+    /// The entity executing the action.
+    Actor,
+    /// The entity with the role.
+    Role(Role),
+    /// A specific entity.
+    Entity(u32),
+    /// All participating entities except the actor.
+    Others,
+}
+
+/// How many entities a role of a practice needs at least & can have at most.
+///
+/// ```
+///# use rusted_social_simulation::social::practice::role::Cardinality;
+/// let students = Cardinality::new(1, None);
+///
+/// assert!(!students.contains(0));
+/// assert!(students.contains(30));
+/// assert!(!Cardinality::default().contains(0));
+/// assert!(Cardinality::default().contains(1));
+/// assert!(!Cardinality::default().contains(2));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Cardinality {
+    min: usize,
+    max: Option<usize>,
+}
+
+impl Cardinality {
+    /// Creates a cardinality. Without a maximum the role can have any number of entities.
+    pub fn new(min: usize, max: Option<usize>) -> Cardinality {
+        Cardinality { min, max }
+    }
+
+    /// The role needs exactly a number of entities.
+    pub fn exactly(count: usize) -> Cardinality {
+        Cardinality::new(count, Some(count))
+    }
+
+    /// The role can stay empty or have a single entity.
+    pub fn optional() -> Cardinality {
+        Cardinality::new(0, Some(1))
+    }
+
+    /// The role needs a single entity.
+    pub fn required() -> Cardinality {
+        Cardinality::exactly(1)
+    }
+
+    pub fn get_min(&self) -> usize {
+        self.min
+    }
+
+    pub fn get_max(&self) -> Option<usize> {
+        self.max
+    }
+
+    /// Can the role stay empty?
+    pub fn is_optional(&self) -> bool {
+        self.min == 0
+    }
+
+    /// Is the number of entities allowed?
+    pub fn contains(&self, count: usize) -> bool {
+        count >= self.min && self.max.is_none_or(|max| count <= max)
+    }
+}
+
+impl Default for Cardinality {
+    /// The role needs a single entity.
+    fn default() -> Self {
+        Cardinality::required()
+    }
+}
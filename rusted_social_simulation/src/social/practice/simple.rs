@@ -1,14 +1,22 @@
 use crate::social::action::{Action, MockAction};
-use crate::social::practice::role::Role;
-use crate::social::practice::{Practice, PracticeTemplate};
+use crate::social::error::SocialError;
+use crate::social::practice::casting::RoleCasting;
+use crate::social::practice::participants::Participants;
+use crate::social::practice::role::{Cardinality, Role};
+use crate::social::practice::{
+    get_role_actions, try_get_role, validate_participants, Practice, PracticeTemplate, RoleActions,
+    TemplateRef, TemplateRoles,
+};
+use crate::social::snapshot::PracticeSnapshot;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 /// A simple implementation of PracticeTemplate.
 pub struct SimplePracticeTemplate<T> {
     id: u32,
     name: String,
-    role_names: HashMap<Role, String>,
-    actions: HashMap<Role, Vec<Box<dyn Action<T>>>>,
+    roles: TemplateRoles<T>,
+    actions: RoleActions<T>,
 }
 
 impl<T> SimplePracticeTemplate<T> {
@@ -16,15 +24,25 @@ impl<T> SimplePracticeTemplate<T> {
         id: u32,
         name: String,
         role_names: HashMap<Role, String>,
-        actions: HashMap<Role, Vec<Box<dyn Action<T>>>>,
+        actions: RoleActions<T>,
     ) -> SimplePracticeTemplate<T> {
         SimplePracticeTemplate {
             id,
             name,
-            role_names,
+            roles: TemplateRoles::new(role_names),
             actions,
         }
     }
+
+    /// Sets how many entities a role needs at least & can have at most.
+    pub fn set_cardinality(&mut self, role: Role, cardinality: Cardinality) {
+        self.roles.set_cardinality(role, cardinality);
+    }
+
+    /// Sets which entities can fill a role & how suitable they are.
+    pub fn set_casting(&mut self, role: Role, casting: RoleCasting<T>) {
+        self.roles.set_casting(role, casting);
+    }
 }
 
 impl<T> PracticeTemplate<T> for SimplePracticeTemplate<T> {
@@ -44,11 +62,7 @@ impl<T> PracticeTemplate<T> for SimplePracticeTemplate<T> {
     /// assert_eq!(actions.get(1).unwrap().get_name(), "action1");
     /// ```
     fn get_actions(&self, role: Role) -> Vec<&dyn Action<T>> {
-        if let Some(actions) = self.actions.get(&role) {
-            return actions.iter().map(|action| action.as_ref()).collect();
-        }
-
-        Vec::new()
+        get_role_actions(&self.actions, role)
     }
 
     /// Gets the id of this practice template.
@@ -94,67 +108,157 @@ impl<T> PracticeTemplate<T> for SimplePracticeTemplate<T> {
     /// assert!(roles.contains(&listener));
     /// ```
     fn get_roles(&self) -> Vec<Role> {
-        self.role_names.iter().map(|(role, _name)| *role).collect()
+        self.roles.get_roles()
     }
 
     /// Gets the name of a role in this practice template.
     ///
-    /// # Examples
-    ///
     /// ```
+    ///# use rusted_social_simulation::social::error::SocialError;
     ///# use rusted_social_simulation::social::practice::role::Role;
     ///# use rusted_social_simulation::social::practice::simple::create_test_template;
     ///# use rusted_social_simulation::social::practice::PracticeTemplate;
     /// let speaker = Role::Character { id: 0 };
-    /// let template = create_test_template();
-    ///
-    /// assert_eq!(template.get_role_name(speaker), "Speaker");
-    /// ```
-    ///
-    /// # Panics
-    ///
-    /// ```should_panic
-    ///# use rusted_social_simulation::social::practice::role::Role;
-    ///# use rusted_social_simulation::social::practice::simple::create_test_template;
-    ///# use rusted_social_simulation::social::practice::PracticeTemplate;
     /// let unknown_role = Role::Character { id: 99 };
     /// let template = create_test_template();
     ///
-    /// template.get_role_name(unknown_role);
+    /// assert_eq!(template.try_get_role_name(speaker), Ok("Speaker"));
+    /// assert_eq!(
+    ///     template.try_get_role_name(unknown_role),
+    ///     Err(SocialError::UnknownRole { template: "template0".to_string(), role: unknown_role })
+    /// );
     /// ```
-    fn get_role_name(&self, role: Role) -> &str {
-        self.role_names.get(&role).unwrap_or_else(|| {
-            panic!(
-                "PracticeTemplate '{}' doesn't have the role {}!",
-                self.name, role
-            )
-        })
+    fn try_get_role_name(&self, role: Role) -> Result<&str, SocialError> {
+        self.roles.try_get_name(&self.name, role)
+    }
+
+    fn get_cardinality(&self, role: Role) -> Cardinality {
+        self.roles.get_cardinality(role)
+    }
+
+    fn get_casting(&self, role: Role) -> Option<&RoleCasting<T>> {
+        self.roles.get_casting(role)
     }
 }
 
 /// A simple implementation of PracticeTemplate.
 pub struct SimplePractice<'a, T> {
     id: u32,
-    role_to_id_map: HashMap<Role, u32>,
-    template: &'a dyn PracticeTemplate<T>,
+    participants: Participants,
+    template: TemplateRef<'a, dyn PracticeTemplate<T> + 'a>,
 }
 
 impl<'a, T> SimplePractice<'a, T> {
-    pub fn new(
+    /// Creates a practice, if each role is part of the template and each entity has only one role.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::error::SocialError;
+    ///# use rusted_social_simulation::social::practice::participants::Participants;
+    ///# use rusted_social_simulation::social::practice::role::Role;
+    ///# use rusted_social_simulation::social::practice::simple::{create_test_template, SimplePractice};
+    /// let speaker = Role::Character { id: 0 };
+    /// let listener = Role::Character { id: 1 };
+    /// let template = create_test_template();
+    /// let participants: Participants = vec![(speaker, 10), (listener, 10)].into_iter().collect();
+    ///
+    /// assert_eq!(
+    ///     SimplePractice::try_new(5, participants, &template).err(),
+    ///     Some(SocialError::DuplicateEntity { practice: 5, entity: 10 })
+    /// );
+    /// ```
+    pub fn try_new(
+        id: u32,
+        participants: impl Into<Participants>,
+        template: &'a dyn PracticeTemplate<T>,
+    ) -> Result<SimplePractice<'a, T>, SocialError> {
+        Self::try_create(id, participants.into(), TemplateRef::Borrowed(template))
+    }
+
+    /// Creates a practice like [`SimplePractice::try_new`], but shares the ownership of the template.
+    ///
+    /// ```
+    ///# use maplit::hashmap;
+    ///# use rusted_social_simulation::social::practice::role::Role;
+    ///# use rusted_social_simulation::social::practice::simple::{create_test_template, SimplePractice};
+    ///# use rusted_social_simulation::social::practice::Practice;
+    ///# use std::sync::Arc;
+    /// let speaker = Role::Character { id: 0 };
+    /// let listener = Role::Character { id: 1 };
+    /// let template = Arc::new(create_test_template());
+    ///
+    /// let practice = SimplePractice::try_new_shared(5, hashmap! { speaker => 10, listener => 11 }, template).unwrap();
+    ///
+    /// assert_eq!(practice.get_actions(10).len(), 2);
+    /// ```
+    pub fn try_new_shared(
         id: u32,
-        role_to_id_map: HashMap<Role, u32>,
-        template: &dyn PracticeTemplate<T>,
-    ) -> SimplePractice<T> {
-        SimplePractice {
+        participants: impl Into<Participants>,
+        template: Arc<dyn PracticeTemplate<T> + 'a>,
+    ) -> Result<SimplePractice<'a, T>, SocialError> {
+        Self::try_create(id, participants.into(), TemplateRef::Shared(template))
+    }
+
+    fn try_create(
+        id: u32,
+        participants: Participants,
+        template: TemplateRef<'a, dyn PracticeTemplate<T> + 'a>,
+    ) -> Result<SimplePractice<'a, T>, SocialError> {
+        validate_participants(id, &participants, &*template)?;
+
+        Ok(SimplePractice {
             id,
-            role_to_id_map,
+            participants,
             template,
-        }
+        })
+    }
+
+    /// Creates a practice like [`SimplePractice::try_new`], but panics for invalid roles & entities.
+    ///
+    /// ```should_panic
+    ///# use rusted_social_simulation::social::practice::participants::Participants;
+    ///# use rusted_social_simulation::social::practice::role::Role;
+    ///# use rusted_social_simulation::social::practice::simple::{create_test_template, SimplePractice};
+    /// let unknown_role = Role::Character { id: 99 };
+    /// let template = create_test_template();
+    /// let participants: Participants = vec![(unknown_role, 10)].into_iter().collect();
+    ///
+    /// SimplePractice::new(5, participants, &template);
+    /// ```
+    pub fn new(
+        id: u32,
+        participants: impl Into<Participants>,
+        template: &'a dyn PracticeTemplate<T>,
+    ) -> SimplePractice<'a, T> {
+        Self::try_new(id, participants, template).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Restores a practice from a snapshot, if the template is the referenced one.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::error::SocialError;
+    ///# use rusted_social_simulation::social::practice::simple::{create_test_template, create_test_practice, SimplePractice};
+    ///# use rusted_social_simulation::social::practice::Practice;
+    ///# use rusted_social_simulation::social::snapshot::PracticeSnapshot;
+    /// let template = create_test_template();
+    /// let snapshot = PracticeSnapshot::save(&create_test_practice(&template));
+    ///
+    /// let practice = SimplePractice::try_restore(&snapshot, &template).unwrap();
+    ///
+    /// assert_eq!(practice.get_id(), 5);
+    /// assert_eq!(practice.get_entities(), vec![10, 11]);
+    /// ```
+    pub fn try_restore(
+        snapshot: &PracticeSnapshot,
+        template: &'a dyn PracticeTemplate<T>,
+    ) -> Result<SimplePractice<'a, T>, SocialError> {
+        snapshot.check_template(template)?;
+        Self::try_new(snapshot.get_id(), snapshot.get_participants(), template)
     }
 }
 
 impl<'a, T> Practice<T> for SimplePractice<'a, T> {
     /// Gets all actions of an entity in this practice.
+    /// An entity that doesn't participate has no actions.
     ///
     /// ```
     ///# use rusted_social_simulation::social::practice::role::Role;
@@ -168,13 +272,16 @@ impl<'a, T> Practice<T> for SimplePractice<'a, T> {
     /// assert_eq!(actions.len(), 2);
     /// assert_eq!(actions.get(0).unwrap().get_name(), "action0");
     /// assert_eq!(actions.get(1).unwrap().get_name(), "action1");
+    /// assert!(practice.get_actions(99).is_empty());
     /// ```
     fn get_actions(&self, entity: u32) -> Vec<&dyn Action<T>> {
-        let role = self.get_role(entity);
-        self.template.get_actions(role)
+        self.participants
+            .get_acting_role(entity)
+            .map(|role| self.template.get_actions(role))
+            .unwrap_or_default()
     }
 
-    /// Gets the id of this social practice.
+    /// Gets all entities that participate in this practice, sorted by their id.
     ///
     /// ```
     ///# use rusted_social_simulation::social::practice::simple::{create_test_template, create_test_practice};
@@ -182,50 +289,49 @@ impl<'a, T> Practice<T> for SimplePractice<'a, T> {
     /// let template = create_test_template();
     /// let practice = create_test_practice(&template);
     ///
-    /// assert_eq!(practice.get_id(), 5);
+    /// assert_eq!(practice.get_entities(), vec![10, 11]);
     /// ```
-    fn get_id(&self) -> u32 {
-        self.id
+    fn get_entities(&self) -> Vec<u32> {
+        self.participants.get_all()
     }
 
-    /// Gets the role of an entity that participate in this practice.
-    ///
-    /// # Examples
+    fn get_entities_of(&self, role: Role) -> Vec<u32> {
+        self.participants.get_entities(role).to_vec()
+    }
+
+    fn get_participants(&self) -> Participants {
+        self.participants.clone()
+    }
+
+    /// Gets the id of this social practice.
     ///
     /// ```
-    ///# use rusted_social_simulation::social::practice::role::Role;
     ///# use rusted_social_simulation::social::practice::simple::{create_test_template, create_test_practice};
     ///# use rusted_social_simulation::social::practice::{PracticeTemplate, Practice};
     /// let template = create_test_template();
     /// let practice = create_test_practice(&template);
     ///
-    /// assert_eq!(practice.get_role(10), Role::Character { id: 0 });
+    /// assert_eq!(practice.get_id(), 5);
     /// ```
+    fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    /// Gets the role of an entity that participate in this practice.
     ///
-    /// # Panics
-    ///
-    /// ```should_panic
+    /// ```
+    ///# use rusted_social_simulation::social::error::SocialError;
     ///# use rusted_social_simulation::social::practice::role::Role;
     ///# use rusted_social_simulation::social::practice::simple::{create_test_template, create_test_practice};
     ///# use rusted_social_simulation::social::practice::{PracticeTemplate, Practice};
     /// let template = create_test_template();
     /// let practice = create_test_practice(&template);
     ///
-    /// practice.get_role(99);
+    /// assert_eq!(practice.try_get_role(10), Ok(Role::Character { id: 0 }));
+    /// assert_eq!(practice.try_get_role(99), Err(SocialError::UnknownEntity { practice: 5, entity: 99 }));
     /// ```
-    fn get_role(&self, entity: u32) -> Role {
-        for (role, id) in &self.role_to_id_map {
-            if *id == entity {
-                return *role;
-            }
-        }
-
-        panic!(
-            "Practice {} of template '{}' doesn't have the role for entity {}!",
-            self.id,
-            self.template.get_name(),
-            entity
-        );
+    fn try_get_role(&self, entity: u32) -> Result<Role, SocialError> {
+        try_get_role(self.id, &self.participants, entity)
     }
 
     /// Gets the template of this practice.
@@ -239,7 +345,7 @@ impl<'a, T> Practice<T> for SimplePractice<'a, T> {
     /// assert_eq!(practice.get_template().get_name(), "template0");
     /// ```
     fn get_template(&self) -> &dyn PracticeTemplate<T> {
-        self.template
+        &*self.template
     }
 }
 
@@ -264,7 +370,7 @@ pub fn create_test_template() -> SimplePracticeTemplate<u32> {
 }
 
 /// Create a SimplePractice for testing.
-pub fn create_test_practice(template: &dyn PracticeTemplate<u32>) -> SimplePractice<u32> {
+pub fn create_test_practice(template: &dyn PracticeTemplate<u32>) -> SimplePractice<'_, u32> {
     let speaker = Role::Character { id: 0 };
     let listener = Role::Character { id: 1 };
 
@@ -295,4 +401,63 @@ mod tests {
 
         assert!(template.get_actions(unknown_role).is_empty());
     }
+
+    #[test]
+    fn test_group_role() {
+        let speaker = Role::Character { id: 0 };
+        let listener = Role::Character { id: 1 };
+        let mut template = create_test_template();
+        template.set_cardinality(listener, Cardinality::new(1, None));
+        let participants = hashmap! { speaker => vec![10], listener => vec![12, 11] };
+
+        let practice = SimplePractice::new(5, participants, &template);
+
+        assert_eq!(practice.get_entities(), vec![10, 11, 12]);
+        assert_eq!(practice.get_entities_of(listener), vec![11, 12]);
+        assert_eq!(practice.get_entity(listener), Some(11));
+        assert_eq!(practice.get_role(12), listener);
+        assert!(practice.get_actions(12).is_empty());
+        assert_eq!(practice.get_actions(10).len(), 2);
+    }
+
+    #[test]
+    fn test_observer_can_not_act() {
+        let witness = Role::Observer { id: 0 };
+        let action: Box<dyn Action<u32>> = Box::new(MockAction::new("gasp".to_string()));
+        let template = SimplePracticeTemplate::new(
+            0,
+            "witness".to_string(),
+            hashmap! { witness => "Witness".to_string() },
+            hashmap! { witness => vec![action] },
+        );
+
+        let practice = SimplePractice::new(5, hashmap! { witness => 10 }, &template);
+
+        assert_eq!(template.get_actions(witness).len(), 1);
+        assert_eq!(practice.get_entities(), vec![10]);
+        assert!(practice.get_actions(10).is_empty());
+        assert!(practice.get_turn().is_empty());
+    }
+
+    #[test]
+    fn test_invalid_cardinality() {
+        let speaker = Role::Character { id: 0 };
+        let listener = Role::Character { id: 1 };
+        let mut template = create_test_template();
+        template.set_cardinality(listener, Cardinality::new(1, Some(2)));
+
+        for count in [0, 3] {
+            let listeners: Vec<u32> = (0..count).map(|i| 11 + i).collect();
+            let participants = hashmap! { speaker => vec![10], listener => listeners };
+
+            assert_eq!(
+                SimplePractice::try_new(5, participants, &template).err(),
+                Some(SocialError::InvalidCardinality {
+                    template: "template0".to_string(),
+                    role: listener,
+                    count: count as usize,
+                })
+            );
+        }
+    }
 }
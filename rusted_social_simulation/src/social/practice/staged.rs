@@ -0,0 +1,520 @@
+use crate::social::action::{Action, MockAction};
+use crate::social::condition::{Condition, MockCondition};
+use crate::social::error::SocialError;
+use crate::social::practice::casting::RoleCasting;
+use crate::social::practice::participants::Participants;
+use crate::social::practice::role::{Cardinality, Role};
+use crate::social::practice::{
+    get_role_actions, try_get_role, validate_participants, Practice, PracticeTemplate, RoleActions,
+    TemplateRef, TemplateRoles,
+};
+use crate::social::snapshot::PracticeSnapshot;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+/// A transition from one stage of a practice to another.
+pub struct Transition<T> {
+    target: String,
+    condition: Box<dyn Condition<T>>,
+}
+
+impl<T> Transition<T> {
+    pub fn new(target: &str, condition: Box<dyn Condition<T>>) -> Transition<T> {
+        Transition {
+            target: target.to_string(),
+            condition,
+        }
+    }
+
+    /// Gets the name of the stage the transition leads to.
+    pub fn get_target(&self) -> &str {
+        &self.target
+    }
+}
+
+/// A stage of a practice, e.g. the greeting of a conversation.
+///
+/// It defines the actions available during the stage & when to move on to another stage.
+pub struct Stage<T> {
+    name: String,
+    actions: RoleActions<T>,
+    transitions: Vec<Transition<T>>,
+    is_terminal: bool,
+}
+
+impl<T> Stage<T> {
+    pub fn new(name: &str, actions: RoleActions<T>, transitions: Vec<Transition<T>>) -> Stage<T> {
+        Stage {
+            name: name.to_string(),
+            actions,
+            transitions,
+            is_terminal: false,
+        }
+    }
+
+    /// Creates a stage that ends the practice.
+    pub fn terminal(name: &str) -> Stage<T> {
+        Stage {
+            name: name.to_string(),
+            actions: HashMap::new(),
+            transitions: Vec::new(),
+            is_terminal: true,
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Gets all actions of a role during this stage.
+    pub fn get_actions(&self, role: Role) -> Vec<&dyn Action<T>> {
+        get_role_actions(&self.actions, role)
+    }
+
+    pub fn get_transitions(&self) -> &[Transition<T>] {
+        &self.transitions
+    }
+
+    /// Does the practice end, if it reaches this stage?
+    pub fn is_terminal(&self) -> bool {
+        self.is_terminal
+    }
+}
+
+/// A practice template that is divided into stages.
+///
+/// A practice starts in the first stage and finishes, when it reaches a terminal stage.
+pub struct StagedPracticeTemplate<T> {
+    id: u32,
+    name: String,
+    roles: TemplateRoles<T>,
+    stages: Vec<Stage<T>>,
+}
+
+impl<T> StagedPracticeTemplate<T> {
+    /// Creates a template, if it has stages with unique names and all transitions lead to one of them.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::condition::MockCondition;
+    ///# use rusted_social_simulation::social::error::SocialError;
+    ///# use rusted_social_simulation::social::practice::staged::{Stage, StagedPracticeTemplate, Transition};
+    /// let transition = Transition::new("unknown", Box::new(MockCondition::new(true)));
+    /// let stages: Vec<Stage<u32>> = vec![Stage::new("greeting", Default::default(), vec![transition])];
+    ///
+    /// assert_eq!(
+    ///     StagedPracticeTemplate::try_new(0, "talk".to_string(), Default::default(), stages).err(),
+    ///     Some(SocialError::UnknownStage { template: "talk".to_string(), stage: "unknown".to_string() })
+    /// );
+    /// ```
+    pub fn try_new(
+        id: u32,
+        name: String,
+        role_names: HashMap<Role, String>,
+        stages: Vec<Stage<T>>,
+    ) -> Result<StagedPracticeTemplate<T>, SocialError> {
+        if stages.is_empty() {
+            return Err(SocialError::MissingStages { template: name });
+        }
+
+        let mut stage_names = HashSet::new();
+
+        for stage in &stages {
+            if !stage_names.insert(stage.get_name()) {
+                return Err(SocialError::DuplicateStage {
+                    template: name,
+                    stage: stage.get_name().to_string(),
+                });
+            }
+        }
+
+        for stage in &stages {
+            for transition in stage.get_transitions() {
+                if !stage_names.contains(transition.get_target()) {
+                    return Err(SocialError::UnknownStage {
+                        template: name,
+                        stage: transition.get_target().to_string(),
+                    });
+                }
+            }
+        }
+
+        Ok(StagedPracticeTemplate {
+            id,
+            name,
+            roles: TemplateRoles::new(role_names),
+            stages,
+        })
+    }
+
+    /// Gets all stages. The first one is the initial stage.
+    pub fn get_stages(&self) -> &[Stage<T>] {
+        &self.stages
+    }
+
+    /// Gets the stage with the index.
+    pub fn get_stage(&self, index: usize) -> Option<&Stage<T>> {
+        self.stages.get(index)
+    }
+
+    /// Gets the index of the stage with the name.
+    pub fn get_stage_index(&self, name: &str) -> Option<usize> {
+        self.stages
+            .iter()
+            .position(|stage| stage.get_name() == name)
+    }
+
+    /// Sets how many entities a role needs at least & can have at most.
+    pub fn set_cardinality(&mut self, role: Role, cardinality: Cardinality) {
+        self.roles.set_cardinality(role, cardinality);
+    }
+
+    /// Sets which entities can fill a role & how suitable they are.
+    pub fn set_casting(&mut self, role: Role, casting: RoleCasting<T>) {
+        self.roles.set_casting(role, casting);
+    }
+}
+
+impl<T> PracticeTemplate<T> for StagedPracticeTemplate<T> {
+    /// Gets the actions of a role in all stages.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::practice::role::Role;
+    ///# use rusted_social_simulation::social::practice::staged::create_test_staged_template;
+    ///# use rusted_social_simulation::social::practice::PracticeTemplate;
+    /// let speaker = Role::Character { id: 0 };
+    /// let template = create_test_staged_template();
+    ///
+    /// let actions = template.get_actions(speaker);
+    ///
+    /// assert_eq!(actions.len(), 3);
+    /// assert_eq!(actions.get(0).unwrap().get_name(), "hello");
+    /// ```
+    fn get_actions(&self, role: Role) -> Vec<&dyn Action<T>> {
+        self.stages
+            .iter()
+            .flat_map(|stage| stage.get_actions(role))
+            .collect()
+    }
+
+    fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    fn get_roles(&self) -> Vec<Role> {
+        self.roles.get_roles()
+    }
+
+    fn try_get_role_name(&self, role: Role) -> Result<&str, SocialError> {
+        self.roles.try_get_name(&self.name, role)
+    }
+
+    fn get_cardinality(&self, role: Role) -> Cardinality {
+        self.roles.get_cardinality(role)
+    }
+
+    fn get_casting(&self, role: Role) -> Option<&RoleCasting<T>> {
+        self.roles.get_casting(role)
+    }
+}
+
+/// An instance of a [`StagedPracticeTemplate`], that tracks its current stage.
+pub struct StagedPractice<'a, T> {
+    id: u32,
+    participants: Participants,
+    template: TemplateRef<'a, StagedPracticeTemplate<T>>,
+    stage: usize,
+}
+
+impl<'a, T> StagedPractice<'a, T> {
+    /// Creates a practice in the initial stage of its template,
+    /// if each role is part of the template and each entity has only one role.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::practice::participants::Participants;
+    ///# use rusted_social_simulation::social::practice::role::Role;
+    ///# use rusted_social_simulation::social::practice::staged::{create_test_staged_template, StagedPractice};
+    ///# use rusted_social_simulation::social::practice::Practice;
+    /// let template = create_test_staged_template();
+    /// let participants: Participants =
+    ///     vec![(Role::Character { id: 0 }, 10), (Role::Character { id: 1 }, 11)].into_iter().collect();
+    ///
+    /// let practice = StagedPractice::try_new(5, participants, &template).unwrap();
+    ///
+    /// assert_eq!(practice.get_stage(), Some("greeting"));
+    /// assert!(!practice.is_finished());
+    /// ```
+    pub fn try_new(
+        id: u32,
+        participants: impl Into<Participants>,
+        template: &'a StagedPracticeTemplate<T>,
+    ) -> Result<StagedPractice<'a, T>, SocialError> {
+        Self::try_create(id, participants.into(), TemplateRef::Borrowed(template))
+    }
+
+    /// Creates a practice like [`StagedPractice::try_new`], but shares the ownership of the template.
+    pub fn try_new_shared(
+        id: u32,
+        participants: impl Into<Participants>,
+        template: Arc<StagedPracticeTemplate<T>>,
+    ) -> Result<StagedPractice<'a, T>, SocialError> {
+        Self::try_create(id, participants.into(), TemplateRef::Shared(template))
+    }
+
+    fn try_create(
+        id: u32,
+        participants: Participants,
+        template: TemplateRef<'a, StagedPracticeTemplate<T>>,
+    ) -> Result<StagedPractice<'a, T>, SocialError> {
+        validate_participants(id, &participants, &*template)?;
+
+        Ok(StagedPractice {
+            id,
+            participants,
+            template,
+            stage: 0,
+        })
+    }
+
+    /// Restores a practice & its current stage from a snapshot, if the template is the referenced one.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::error::SocialError;
+    ///# use rusted_social_simulation::social::practice::staged::{create_test_staged_template, create_test_staged_practice, StagedPractice};
+    ///# use rusted_social_simulation::social::practice::Practice;
+    ///# use rusted_social_simulation::social::snapshot::PracticeSnapshot;
+    /// let template = create_test_staged_template();
+    /// let snapshot = PracticeSnapshot::new(5, 7, create_test_staged_practice(&template).get_participants(), Some("farewell".to_string()));
+    ///
+    /// let practice = StagedPractice::try_restore(&snapshot, &template).unwrap();
+    ///
+    /// assert_eq!(practice.get_stage(), Some("farewell"));
+    /// assert!(practice.is_finished());
+    /// ```
+    pub fn try_restore(
+        snapshot: &PracticeSnapshot,
+        template: &'a StagedPracticeTemplate<T>,
+    ) -> Result<StagedPractice<'a, T>, SocialError> {
+        snapshot.check_template(template)?;
+        let mut practice = Self::try_new(snapshot.get_id(), snapshot.get_participants(), template)?;
+
+        if let Some(stage) = snapshot.get_stage() {
+            practice.stage =
+                template
+                    .get_stage_index(stage)
+                    .ok_or_else(|| SocialError::UnknownStage {
+                        template: template.name.clone(),
+                        stage: stage.to_string(),
+                    })?;
+        }
+
+        Ok(practice)
+    }
+
+    fn get_current_stage(&self) -> &Stage<T> {
+        &self.template.stages[self.stage]
+    }
+}
+
+impl<'a, T> Practice<T> for StagedPractice<'a, T> {
+    /// Gets the actions of an entity in the current stage.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::practice::staged::{create_test_staged_template, create_test_staged_practice};
+    ///# use rusted_social_simulation::social::practice::Practice;
+    /// let template = create_test_staged_template();
+    /// let practice = create_test_staged_practice(&template);
+    ///
+    /// let actions = practice.get_actions(10);
+    ///
+    /// assert_eq!(actions.len(), 1);
+    /// assert_eq!(actions.get(0).unwrap().get_name(), "hello");
+    /// assert!(practice.get_actions(11).is_empty());
+    /// ```
+    fn get_actions(&self, entity: u32) -> Vec<&dyn Action<T>> {
+        self.participants
+            .get_acting_role(entity)
+            .map(|role| self.get_current_stage().get_actions(role))
+            .unwrap_or_default()
+    }
+
+    fn get_entities(&self) -> Vec<u32> {
+        self.participants.get_all()
+    }
+
+    fn get_entities_of(&self, role: Role) -> Vec<u32> {
+        self.participants.get_entities(role).to_vec()
+    }
+
+    fn get_participants(&self) -> Participants {
+        self.participants.clone()
+    }
+
+    fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    fn try_get_role(&self, entity: u32) -> Result<Role, SocialError> {
+        try_get_role(self.id, &self.participants, entity)
+    }
+
+    fn get_template(&self) -> &dyn PracticeTemplate<T> {
+        &*self.template
+    }
+
+    fn get_stage(&self) -> Option<&str> {
+        Some(self.get_current_stage().get_name())
+    }
+
+    fn is_finished(&self) -> bool {
+        self.get_current_stage().is_terminal()
+    }
+
+    /// Transitions to the target of the first transition, whose condition is true.
+    /// Only one transition is taken per update.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::practice::staged::{create_test_staged_template, create_test_staged_practice};
+    ///# use rusted_social_simulation::social::practice::Practice;
+    /// let template = create_test_staged_template();
+    /// let mut practice = create_test_staged_practice(&template);
+    ///
+    /// practice.update(&42);
+    /// assert_eq!(practice.get_stage(), Some("small talk"));
+    ///
+    /// practice.update(&42);
+    /// assert_eq!(practice.get_stage(), Some("farewell"));
+    /// assert!(practice.is_finished());
+    /// assert!(practice.get_actions(10).is_empty());
+    /// ```
+    fn update(&mut self, context: &T) {
+        let target = self
+            .get_current_stage()
+            .get_transitions()
+            .iter()
+            .find(|transition| transition.condition.evaluate(context))
+            .and_then(|transition| self.template.get_stage_index(transition.get_target()));
+
+        if let Some(index) = target {
+            self.stage = index;
+        }
+    }
+}
+
+/// Create a StagedPracticeTemplate for testing.
+///
+/// The speaker greets, talks and then the practice ends.
+pub fn create_test_staged_template() -> StagedPracticeTemplate<u32> {
+    let speaker = Role::Character { id: 0 };
+    let listener = Role::Character { id: 1 };
+
+    let role_names = hashmap! {
+        speaker => "Speaker".to_string(),
+        listener => "Listener".to_string(),
+    };
+
+    let hello: Box<dyn Action<u32>> = Box::new(MockAction::new("hello".to_string()));
+    let joke: Box<dyn Action<u32>> = Box::new(MockAction::new("joke".to_string()));
+    let story: Box<dyn Action<u32>> = Box::new(MockAction::new("story".to_string()));
+
+    let stages = vec![
+        Stage::new(
+            "greeting",
+            hashmap! { speaker => vec![hello] },
+            vec![Transition::new(
+                "small talk",
+                Box::new(MockCondition::new(true)),
+            )],
+        ),
+        Stage::new(
+            "small talk",
+            hashmap! { speaker => vec![joke, story] },
+            vec![
+                Transition::new("greeting", Box::new(MockCondition::new(false))),
+                Transition::new("farewell", Box::new(MockCondition::new(true))),
+            ],
+        ),
+        Stage::terminal("farewell"),
+    ];
+
+    StagedPracticeTemplate::try_new(7, "staged0".to_string(), role_names, stages).unwrap()
+}
+
+/// Create a StagedPractice for testing.
+pub fn create_test_staged_practice(
+    template: &StagedPracticeTemplate<u32>,
+) -> StagedPractice<'_, u32> {
+    let speaker = Role::Character { id: 0 };
+    let listener = Role::Character { id: 1 };
+
+    let role_to_id_map = hashmap! {
+        speaker => 10,
+        listener => 11,
+    };
+
+    StagedPractice::try_new(5, role_to_id_map, template).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_stages() {
+        let result: Result<StagedPracticeTemplate<u32>, SocialError> =
+            StagedPracticeTemplate::try_new(0, "talk".to_string(), HashMap::new(), Vec::new());
+
+        assert_eq!(
+            result.err(),
+            Some(SocialError::MissingStages {
+                template: "talk".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_duplicate_stage() {
+        let stages: Vec<Stage<u32>> = vec![Stage::terminal("end"), Stage::terminal("end")];
+        let result = StagedPracticeTemplate::try_new(0, "talk".to_string(), HashMap::new(), stages);
+
+        assert_eq!(
+            result.err(),
+            Some(SocialError::DuplicateStage {
+                template: "talk".to_string(),
+                stage: "end".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_no_transition() {
+        let stages: Vec<Stage<u32>> = vec![
+            Stage::new(
+                "start",
+                HashMap::new(),
+                vec![Transition::new("end", Box::new(MockCondition::new(false)))],
+            ),
+            Stage::terminal("end"),
+        ];
+        let template =
+            StagedPracticeTemplate::try_new(0, "talk".to_string(), HashMap::new(), stages).unwrap();
+        let mut practice = StagedPractice::try_new(1, Participants::new(), &template).unwrap();
+
+        practice.update(&42);
+
+        assert_eq!(practice.get_stage(), Some("start"));
+        assert!(!practice.is_finished());
+    }
+
+    #[test]
+    fn test_template_stage_index() {
+        let template = create_test_staged_template();
+
+        assert_eq!(template.get_stage_index("farewell"), Some(2));
+        assert_eq!(template.get_stage_index("unknown"), None);
+        assert!(template.get_stage(2).unwrap().is_terminal());
+    }
+}
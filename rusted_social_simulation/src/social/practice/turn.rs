@@ -0,0 +1,567 @@
+use crate::social::action::Action;
+use crate::social::error::SocialError;
+use crate::social::practice::participants::Participants;
+use crate::social::practice::role::Role;
+use crate::social::practice::{Practice, PracticeTemplate};
+use crate::social::snapshot::{PracticeSnapshot, TurnPolicySnapshot};
+use crate::social::utility::Utility;
+use serde::{Deserialize, Serialize};
+
+/// A policy that decides which participants of a practice can act.
+pub trait TurnPolicy {
+    /// Gets the entities, whose turn it is.
+    ///
+    /// The initiative is the highest utility of the available actions of each entity,
+    /// but only provided, if the policy uses it.
+    fn get_current(&self, participants: &Participants, initiative: &[(u32, Utility)]) -> Vec<u32>;
+
+    /// Passes the turn on, after an entity acted & addressed other entities.
+    fn end_turn(&mut self, actor: u32, addressed: &[u32], participants: &Participants);
+
+    /// Passes the turn on, after an entity had no available action.
+    fn pass_turn(&mut self, _entity: u32, _participants: &Participants) {}
+
+    /// Does the policy need the initiative of the participants?
+    fn uses_initiative(&self) -> bool {
+        false
+    }
+
+    /// Would the entity interrupt someone, if it acts now?
+    fn is_interruption(&self, _entity: u32) -> bool {
+        false
+    }
+
+    /// Saves the policy & its state. Custom policies can't be saved by default.
+    fn save(&self) -> Option<TurnPolicySnapshot> {
+        None
+    }
+}
+
+/// The roles act one after another in a fixed order.
+///
+/// Roles without an entity & roles that can't act are skipped.
+/// All entities of a group role can act, but the first one to act ends the turn of the role.
+/// If none of them can act, the next role gets the turn.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RoundRobin {
+    order: Vec<Role>,
+    index: usize,
+    passed: Vec<u32>,
+}
+
+impl RoundRobin {
+    pub fn new(order: Vec<Role>) -> RoundRobin {
+        RoundRobin {
+            order,
+            index: 0,
+            passed: Vec::new(),
+        }
+    }
+}
+
+impl TurnPolicy for RoundRobin {
+    /// Returns the entities of the current role.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::practice::role::Role;
+    ///# use rusted_social_simulation::social::practice::turn::{RoundRobin, TurnPolicy};
+    /// let speaker = Role::Character { id: 0 };
+    /// let listener = Role::Character { id: 1 };
+    /// let participants = vec![(speaker, 10), (listener, 11)].into_iter().collect();
+    /// let mut policy = RoundRobin::new(vec![listener, speaker]);
+    ///
+    /// assert_eq!(policy.get_current(&participants, &[]), vec![11]);
+    /// policy.end_turn(11, &[], &participants);
+    /// assert_eq!(policy.get_current(&participants, &[]), vec![10]);
+    /// policy.end_turn(10, &[], &participants);
+    /// assert_eq!(policy.get_current(&participants, &[]), vec![11]);
+    /// ```
+    fn get_current(&self, participants: &Participants, _: &[(u32, Utility)]) -> Vec<u32> {
+        let length = self.order.len();
+
+        (0..length)
+            .map(|offset| self.order[(self.index + offset) % length])
+            .filter(|role| role.can_act())
+            .map(|role| participants.get_entities(role))
+            .find(|entities| !entities.is_empty())
+            .map(|entities| entities.to_vec())
+            .unwrap_or_default()
+    }
+
+    fn end_turn(&mut self, actor: u32, _: &[u32], participants: &Participants) {
+        let position = self
+            .order
+            .iter()
+            .position(|role| participants.get_entities(*role).contains(&actor));
+
+        if let Some(position) = position {
+            self.index = (position + 1) % self.order.len();
+        }
+
+        self.passed.clear();
+    }
+
+    /// Passes the turn to the next role, after all entities of the current role passed.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::practice::role::Role;
+    ///# use rusted_social_simulation::social::practice::turn::{RoundRobin, TurnPolicy};
+    /// let speaker = Role::Character { id: 0 };
+    /// let listener = Role::Character { id: 1 };
+    /// let participants = vec![(speaker, 10), (listener, 11), (listener, 12)].into_iter().collect();
+    /// let mut policy = RoundRobin::new(vec![listener, speaker]);
+    ///
+    /// policy.pass_turn(11, &participants);
+    /// assert_eq!(policy.get_current(&participants, &[]), vec![11, 12]);
+    /// policy.pass_turn(12, &participants);
+    /// assert_eq!(policy.get_current(&participants, &[]), vec![10]);
+    /// ```
+    fn pass_turn(&mut self, entity: u32, participants: &Participants) {
+        let current = self.get_current(participants, &[]);
+
+        if !current.contains(&entity) {
+            return;
+        }
+
+        self.passed.push(entity);
+
+        if current.iter().all(|entity| self.passed.contains(entity)) {
+            self.end_turn(entity, &[], participants);
+        }
+    }
+
+    fn save(&self) -> Option<TurnPolicySnapshot> {
+        Some(TurnPolicySnapshot::RoundRobin(self.clone()))
+    }
+}
+
+/// Whoever was addressed replies.
+///
+/// If an actor addresses nobody, all other entities can reply.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AddressedReplies {
+    first: Role,
+    addressed: Option<Vec<u32>>,
+}
+
+impl AddressedReplies {
+    /// The entity of the first role starts.
+    pub fn new(first: Role) -> AddressedReplies {
+        AddressedReplies {
+            first,
+            addressed: None,
+        }
+    }
+}
+
+impl TurnPolicy for AddressedReplies {
+    /// Returns the entities, that were addressed by the last actor.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::practice::role::Role;
+    ///# use rusted_social_simulation::social::practice::turn::{AddressedReplies, TurnPolicy};
+    /// let host = Role::Character { id: 0 };
+    /// let guest0 = Role::Character { id: 1 };
+    /// let guest1 = Role::Character { id: 2 };
+    /// let participants = vec![(host, 10), (guest0, 11), (guest1, 12)].into_iter().collect();
+    /// let mut policy = AddressedReplies::new(host);
+    ///
+    /// assert_eq!(policy.get_current(&participants, &[]), vec![10]);
+    /// policy.end_turn(10, &[12], &participants);
+    /// assert_eq!(policy.get_current(&participants, &[]), vec![12]);
+    /// policy.end_turn(12, &[], &participants);
+    /// assert_eq!(policy.get_current(&participants, &[]), vec![10, 11]);
+    /// ```
+    fn get_current(&self, participants: &Participants, _: &[(u32, Utility)]) -> Vec<u32> {
+        match &self.addressed {
+            Some(addressed) => addressed
+                .iter()
+                .copied()
+                .filter(|entity| participants.can_act(*entity))
+                .collect(),
+            None if self.first.can_act() => participants.get_entities(self.first).to_vec(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Only participants, that can act, are addressed.
+    /// If nobody is left, all other entities can reply instead.
+    fn end_turn(&mut self, actor: u32, addressed: &[u32], participants: &Participants) {
+        let addressed: Vec<u32> = addressed
+            .iter()
+            .copied()
+            .filter(|entity| participants.can_act(*entity))
+            .collect();
+
+        if addressed.is_empty() {
+            self.addressed = Some(get_others(actor, participants));
+        } else {
+            self.addressed = Some(addressed);
+        }
+    }
+
+    /// Removes the entity from the current ones.
+    /// If nobody is left, all other entities can reply instead.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::practice::role::Role;
+    ///# use rusted_social_simulation::social::practice::turn::{AddressedReplies, TurnPolicy};
+    /// let host = Role::Character { id: 0 };
+    /// let guest0 = Role::Character { id: 1 };
+    /// let guest1 = Role::Character { id: 2 };
+    /// let participants = vec![(host, 10), (guest0, 11), (guest1, 12)].into_iter().collect();
+    /// let mut policy = AddressedReplies::new(host);
+    ///
+    /// policy.pass_turn(10, &participants);
+    /// assert_eq!(policy.get_current(&participants, &[]), vec![11, 12]);
+    /// policy.pass_turn(11, &participants);
+    /// assert_eq!(policy.get_current(&participants, &[]), vec![12]);
+    /// ```
+    fn pass_turn(&mut self, entity: u32, participants: &Participants) {
+        let mut current = self.get_current(participants, &[]);
+        current.retain(|current| *current != entity);
+
+        if current.is_empty() {
+            self.addressed = Some(get_others(entity, participants));
+        } else {
+            self.addressed = Some(current);
+        }
+    }
+
+    fn save(&self) -> Option<TurnPolicySnapshot> {
+        Some(TurnPolicySnapshot::AddressedReplies(self.clone()))
+    }
+}
+
+/// Everybody can act at any time.
+///
+/// The last actor & the entities it addressed have the floor.
+/// Everybody else interrupts them by acting.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct FreeForAll {
+    speaker: Option<u32>,
+    floor: Vec<u32>,
+    interruptions: u32,
+}
+
+impl FreeForAll {
+    pub fn new() -> FreeForAll {
+        FreeForAll::default()
+    }
+
+    /// Gets the last actor.
+    pub fn get_speaker(&self) -> Option<u32> {
+        self.speaker
+    }
+
+    /// Gets how often an actor interrupted others.
+    pub fn get_interruptions(&self) -> u32 {
+        self.interruptions
+    }
+}
+
+impl TurnPolicy for FreeForAll {
+    /// Returns all entities, that can act.
+    fn get_current(&self, participants: &Participants, _: &[(u32, Utility)]) -> Vec<u32> {
+        participants.get_actors()
+    }
+
+    /// Counts interruptions & gives the floor to the actor & the entities it addressed.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::practice::role::Role;
+    ///# use rusted_social_simulation::social::practice::turn::{FreeForAll, TurnPolicy};
+    /// let participants = (0..3).map(|id| (Role::Character { id }, 10 + id)).collect();
+    /// let mut policy = FreeForAll::new();
+    ///
+    /// policy.end_turn(10, &[11], &participants);
+    /// assert!(!policy.is_interruption(11));
+    /// assert!(policy.is_interruption(12));
+    ///
+    /// policy.end_turn(12, &[], &participants);
+    /// assert_eq!(policy.get_speaker(), Some(12));
+    /// assert_eq!(policy.get_interruptions(), 1);
+    /// ```
+    fn end_turn(&mut self, actor: u32, addressed: &[u32], _: &Participants) {
+        if self.is_interruption(actor) {
+            self.interruptions += 1;
+        }
+
+        self.speaker = Some(actor);
+        self.floor = addressed.to_vec();
+    }
+
+    /// Acting interrupts the last actor, unless it addressed nobody or the entity.
+    fn is_interruption(&self, entity: u32) -> bool {
+        !self.floor.is_empty() && !self.floor.contains(&entity) && self.speaker != Some(entity)
+    }
+
+    fn save(&self) -> Option<TurnPolicySnapshot> {
+        Some(TurnPolicySnapshot::FreeForAll(self.clone()))
+    }
+}
+
+/// The entity with the most useful action acts.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct InitiativeByUtility;
+
+impl TurnPolicy for InitiativeByUtility {
+    /// Returns the entity with the highest initiative. The first one wins ties.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::practice::turn::{InitiativeByUtility, TurnPolicy};
+    ///# use rusted_social_simulation::social::utility::Utility;
+    /// let initiative = vec![(10, 5), (11, 9), (12, 9)].into_iter()
+    ///     .map(|(entity, utility)| (entity, Utility::from_integer(utility)))
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(InitiativeByUtility.get_current(&Default::default(), &initiative), vec![11]);
+    /// assert!(InitiativeByUtility.get_current(&Default::default(), &[]).is_empty());
+    /// ```
+    fn get_current(&self, _: &Participants, initiative: &[(u32, Utility)]) -> Vec<u32> {
+        let mut best: Option<(u32, Utility)> = None;
+
+        for (entity, utility) in initiative {
+            if best.is_none_or(|(_, max)| *utility > max) {
+                best = Some((*entity, *utility));
+            }
+        }
+
+        best.map(|(entity, _)| entity).into_iter().collect()
+    }
+
+    fn end_turn(&mut self, _: u32, _: &[u32], _: &Participants) {}
+
+    fn uses_initiative(&self) -> bool {
+        true
+    }
+
+    fn save(&self) -> Option<TurnPolicySnapshot> {
+        Some(TurnPolicySnapshot::InitiativeByUtility)
+    }
+}
+
+/// A practice that only lets entities act in their turn.
+///
+/// It wraps another practice & uses a [`TurnPolicy`] to decide whose turn it is.
+pub struct TurnTakingPractice<'a, T> {
+    practice: Box<dyn Practice<T> + 'a>,
+    policy: Box<dyn TurnPolicy>,
+    initiative: Vec<(u32, Utility)>,
+}
+
+impl<'a, T> TurnTakingPractice<'a, T> {
+    pub fn new(
+        practice: Box<dyn Practice<T> + 'a>,
+        policy: Box<dyn TurnPolicy>,
+    ) -> TurnTakingPractice<'a, T> {
+        TurnTakingPractice {
+            practice,
+            policy,
+            initiative: Vec::new(),
+        }
+    }
+
+    /// Restores the turn policy & its state from a snapshot around an already restored practice.
+    pub fn try_restore(
+        snapshot: &PracticeSnapshot,
+        practice: Box<dyn Practice<T> + 'a>,
+    ) -> Result<TurnTakingPractice<'a, T>, SocialError> {
+        let policy = snapshot
+            .get_turn_policy()
+            .ok_or(SocialError::MissingTurnPolicy {
+                practice: snapshot.get_id(),
+            })?
+            .restore();
+
+        Ok(TurnTakingPractice::new(practice, policy))
+    }
+
+    /// Would the entity interrupt someone, if it acts now?
+    pub fn is_interruption(&self, entity: u32) -> bool {
+        self.policy.is_interruption(entity)
+    }
+}
+
+impl<'a, T> Practice<T> for TurnTakingPractice<'a, T> {
+    /// Gets the actions of an entity, if it is its turn.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::practice::role::Role;
+    ///# use rusted_social_simulation::social::practice::simple::{create_test_template, create_test_practice};
+    ///# use rusted_social_simulation::social::practice::turn::{RoundRobin, TurnTakingPractice};
+    ///# use rusted_social_simulation::social::practice::Practice;
+    /// let template = create_test_template();
+    /// let speaker = Role::Character { id: 0 };
+    /// let listener = Role::Character { id: 1 };
+    /// let policy = Box::new(RoundRobin::new(vec![listener, speaker]));
+    /// let mut practice = TurnTakingPractice::new(Box::new(create_test_practice(&template)), policy);
+    ///
+    /// assert!(practice.get_actions(10).is_empty());
+    /// practice.end_turn(11, &[]);
+    /// assert_eq!(practice.get_actions(10).len(), 2);
+    /// ```
+    fn get_actions(&self, entity: u32) -> Vec<&dyn Action<T>> {
+        if self.check_turn(entity).is_ok() {
+            self.practice.get_actions(entity)
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn get_potential_actions(&self, entity: u32) -> Vec<&dyn Action<T>> {
+        self.practice.get_actions(entity)
+    }
+
+    fn get_entities(&self) -> Vec<u32> {
+        self.practice.get_entities()
+    }
+
+    fn get_id(&self) -> u32 {
+        self.practice.get_id()
+    }
+
+    fn try_get_role(&self, entity: u32) -> Result<Role, SocialError> {
+        self.practice.try_get_role(entity)
+    }
+
+    fn get_template(&self) -> &dyn PracticeTemplate<T> {
+        self.practice.get_template()
+    }
+
+    fn get_stage(&self) -> Option<&str> {
+        self.practice.get_stage()
+    }
+
+    fn is_finished(&self) -> bool {
+        self.practice.is_finished()
+    }
+
+    fn update(&mut self, context: &T) {
+        self.practice.update(context)
+    }
+
+    /// Gets the entities, whose turn it is according to the policy.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::error::SocialError;
+    ///# use rusted_social_simulation::social::practice::role::Role;
+    ///# use rusted_social_simulation::social::practice::simple::{create_test_template, create_test_practice};
+    ///# use rusted_social_simulation::social::practice::turn::{AddressedReplies, TurnTakingPractice};
+    ///# use rusted_social_simulation::social::practice::Practice;
+    /// let template = create_test_template();
+    /// let policy = Box::new(AddressedReplies::new(Role::Character { id: 0 }));
+    /// let practice = TurnTakingPractice::new(Box::new(create_test_practice(&template)), policy);
+    ///
+    /// assert_eq!(practice.get_turn(), vec![10]);
+    /// assert_eq!(practice.check_turn(11), Err(SocialError::OutOfTurn { practice: 5, entity: 11 }));
+    /// ```
+    fn get_turn(&self) -> Vec<u32> {
+        let participants = self.practice.get_participants();
+        self.policy.get_current(&participants, &self.initiative)
+    }
+
+    fn uses_initiative(&self) -> bool {
+        self.policy.uses_initiative()
+    }
+
+    fn set_initiative(&mut self, initiative: Vec<(u32, Utility)>) {
+        self.initiative = initiative;
+    }
+
+    fn end_turn(&mut self, actor: u32, addressed: &[u32]) {
+        let participants = self.practice.get_participants();
+        self.policy.end_turn(actor, addressed, &participants);
+    }
+
+    fn pass_turn(&mut self, entity: u32) {
+        let participants = self.practice.get_participants();
+        self.policy.pass_turn(entity, &participants);
+    }
+
+    fn save_turn_policy(&self) -> Option<TurnPolicySnapshot> {
+        self.policy.save()
+    }
+}
+
+fn get_others(actor: u32, participants: &Participants) -> Vec<u32> {
+    participants
+        .get_all()
+        .into_iter()
+        .filter(|entity| *entity != actor)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_participants() -> Participants {
+        (0..3).map(|id| (Role::Character { id }, 10 + id)).collect()
+    }
+
+    #[test]
+    fn test_round_robin_with_group() {
+        let teacher = Role::Character { id: 0 };
+        let student = Role::Character { id: 1 };
+        let participants = vec![(teacher, 10), (student, 11), (student, 12)]
+            .into_iter()
+            .collect();
+        let mut policy = RoundRobin::new(vec![teacher, student]);
+
+        policy.end_turn(10, &[], &participants);
+        assert_eq!(policy.get_current(&participants, &[]), vec![11, 12]);
+        policy.end_turn(12, &[], &participants);
+        assert_eq!(policy.get_current(&participants, &[]), vec![10]);
+    }
+
+    #[test]
+    fn test_round_robin_skips_missing_roles() {
+        let participants = create_participants();
+        let unknown = Role::Character { id: 99 };
+        let mut policy = RoundRobin::new(vec![unknown, Role::Character { id: 2 }]);
+
+        assert_eq!(policy.get_current(&participants, &[]), vec![12]);
+        policy.end_turn(12, &[], &participants);
+        assert_eq!(policy.get_current(&participants, &[]), vec![12]);
+    }
+
+    #[test]
+    fn test_round_robin_without_roles() {
+        let policy = RoundRobin::new(Vec::new());
+
+        assert!(policy.get_current(&create_participants(), &[]).is_empty());
+    }
+
+    #[test]
+    fn test_addressed_ignores_non_participants() {
+        let participants = create_participants();
+        let mut policy = AddressedReplies::new(Role::Character { id: 0 });
+
+        policy.end_turn(10, &[99, 11], &participants);
+
+        assert_eq!(policy.get_current(&participants, &[]), vec![11]);
+    }
+
+    #[test]
+    fn test_addressed_only_non_participants() {
+        let participants = create_participants();
+        let mut policy = AddressedReplies::new(Role::Character { id: 0 });
+
+        policy.end_turn(10, &[99], &participants);
+
+        assert_eq!(policy.get_current(&participants, &[]), vec![11, 12]);
+    }
+
+    #[test]
+    fn test_free_for_all_without_floor() {
+        let participants = create_participants();
+        let mut policy = FreeForAll::new();
+
+        policy.end_turn(10, &[], &participants);
+        policy.end_turn(11, &[], &participants);
+
+        assert_eq!(policy.get_current(&participants, &[]), vec![10, 11, 12]);
+        assert_eq!(policy.get_interruptions(), 0);
+    }
+}
@@ -0,0 +1,109 @@
+use crate::social::condition::Condition;
+use crate::social::practice::role::Target;
+use crate::social::practice::RoleContext;
+use crate::social::relationship::{get_values, RelationshipContext};
+
+/// A condition that checks, if the relationships between 2 targets have at least a certain value.
+///
+/// E.g. the speaker's affinity toward the listener is at least 20.
+pub struct RelationshipAtLeast {
+    from: Target,
+    to: Target,
+    dimension: String,
+    threshold: i32,
+}
+
+impl RelationshipAtLeast {
+    pub fn new(from: Target, to: Target, dimension: &str, threshold: i32) -> RelationshipAtLeast {
+        RelationshipAtLeast {
+            from,
+            to,
+            dimension: dimension.to_string(),
+            threshold,
+        }
+    }
+}
+
+impl<T: RelationshipContext + RoleContext> Condition<T> for RelationshipAtLeast {
+    /// Returns true, if all relationships are greater or equal to the threshold.
+    /// Returns false, if a target refers to no entity.
+    fn evaluate(&self, context: &T) -> bool {
+        let values = get_values(context, self.from, self.to, &self.dimension);
+        !values.is_empty() && values.iter().all(|value| *value >= self.threshold)
+    }
+}
+
+/// A condition that checks, if the relationships between 2 targets have at most a certain value.
+pub struct RelationshipAtMost {
+    from: Target,
+    to: Target,
+    dimension: String,
+    threshold: i32,
+}
+
+impl RelationshipAtMost {
+    pub fn new(from: Target, to: Target, dimension: &str, threshold: i32) -> RelationshipAtMost {
+        RelationshipAtMost {
+            from,
+            to,
+            dimension: dimension.to_string(),
+            threshold,
+        }
+    }
+}
+
+impl<T: RelationshipContext + RoleContext> Condition<T> for RelationshipAtMost {
+    /// Returns true, if all relationships are less or equal to the threshold.
+    /// Returns false, if a target refers to no entity.
+    fn evaluate(&self, context: &T) -> bool {
+        let values = get_values(context, self.from, self.to, &self.dimension);
+        !values.is_empty() && values.iter().all(|value| *value <= self.threshold)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::social::practice::role::Role;
+    use crate::social::relationship::tests::{TestContext, LISTENER, SPEAKER};
+
+    #[test]
+    fn test_at_least() {
+        let context = TestContext::new();
+
+        assert!(RelationshipAtLeast::new(SPEAKER, LISTENER, "affinity", 30).evaluate(&context));
+        assert!(!RelationshipAtLeast::new(SPEAKER, LISTENER, "affinity", 31).evaluate(&context));
+        assert!(!RelationshipAtLeast::new(LISTENER, SPEAKER, "affinity", 0).evaluate(&context));
+    }
+
+    #[test]
+    fn test_at_most() {
+        let context = TestContext::new();
+
+        assert!(RelationshipAtMost::new(LISTENER, SPEAKER, "affinity", -10).evaluate(&context));
+        assert!(!RelationshipAtMost::new(SPEAKER, LISTENER, "affinity", 29).evaluate(&context));
+    }
+
+    #[test]
+    fn test_actor_toward_others() {
+        let context = TestContext::new();
+
+        assert!(
+            RelationshipAtLeast::new(Target::Actor, Target::Others, "affinity", 30)
+                .evaluate(&context)
+        );
+        assert!(
+            !RelationshipAtLeast::new(Target::Actor, Target::Others, "affinity", 31)
+                .evaluate(&context)
+        );
+    }
+
+    #[test]
+    fn test_unknown_role() {
+        let context = TestContext::new();
+        let unknown = Target::Role(Role::Character { id: 99 });
+
+        assert!(!RelationshipAtLeast::new(unknown, LISTENER, "affinity", -100).evaluate(&context));
+        assert!(!RelationshipAtMost::new(SPEAKER, unknown, "affinity", 100).evaluate(&context));
+    }
+}
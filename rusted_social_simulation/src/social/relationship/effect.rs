@@ -0,0 +1,94 @@
+use crate::social::effect::Effect;
+use crate::social::practice::role::Target;
+use crate::social::practice::RoleContext;
+use crate::social::relationship::{get_pairs, RelationshipContext};
+
+/// An effect that modifies the relationships between 2 targets.
+pub struct ModifyRelationship {
+    from: Target,
+    to: Target,
+    dimension: String,
+    delta: i32,
+}
+
+impl ModifyRelationship {
+    pub fn new(from: Target, to: Target, dimension: &str, delta: i32) -> ModifyRelationship {
+        ModifyRelationship {
+            from,
+            to,
+            dimension: dimension.to_string(),
+            delta,
+        }
+    }
+}
+
+impl<T: RelationshipContext + RoleContext> Effect<T> for ModifyRelationship {
+    /// Adds a value to each relationship. Does nothing, if a target refers to no entity.
+    fn apply(&self, context: &mut T) {
+        for (from, to) in get_pairs(context, self.from, self.to) {
+            context
+                .get_relationships_mut()
+                .modify(from, to, &self.dimension, self.delta);
+        }
+    }
+}
+
+/// An effect that lets all relationships decay, e.g. once per tick.
+pub struct DecayRelationships;
+
+impl<T: RelationshipContext> Effect<T> for DecayRelationships {
+    /// Moves all relationships towards 0.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::effect::Effect;
+    ///# use rusted_social_simulation::social::relationship::{Dimension, Relationships};
+    ///# use rusted_social_simulation::social::relationship::effect::DecayRelationships;
+    /// let mut relationships = Relationships::new();
+    /// relationships.add_dimension("trust", Dimension::try_new(-100, 100, false, 1).unwrap());
+    /// relationships.set(1, 2, "trust", 30);
+    ///
+    /// DecayRelationships.apply(&mut relationships);
+    ///
+    /// assert_eq!(relationships.get(1, 2, "trust"), 29);
+    /// ```
+    fn apply(&self, context: &mut T) {
+        context.get_relationships_mut().decay();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::social::practice::role::Role;
+    use crate::social::relationship::tests::{TestContext, LISTENER, SPEAKER};
+
+    #[test]
+    fn test_modify() {
+        let mut context = TestContext::new();
+
+        ModifyRelationship::new(LISTENER, SPEAKER, "affinity", 25).apply(&mut context);
+
+        assert_eq!(context.relationships.get(11, 10, "affinity"), 15);
+        assert_eq!(context.relationships.get(10, 11, "affinity"), 30);
+    }
+
+    #[test]
+    fn test_modify_others() {
+        let mut context = TestContext::new();
+
+        ModifyRelationship::new(Target::Others, Target::Actor, "affinity", 5).apply(&mut context);
+
+        assert_eq!(context.relationships.get(11, 10, "affinity"), -5);
+        assert_eq!(context.relationships.get(12, 10, "affinity"), 5);
+    }
+
+    #[test]
+    fn test_unknown_role() {
+        let mut context = TestContext::new();
+        let unknown = Target::Role(Role::Character { id: 99 });
+
+        ModifyRelationship::new(unknown, SPEAKER, "affinity", 25).apply(&mut context);
+
+        assert_eq!(context.relationships.get(11, 10, "affinity"), -10);
+    }
+}
@@ -0,0 +1,457 @@
+use crate::social::error::SocialError;
+use crate::social::practice::role::Target;
+use crate::social::practice::RoleContext;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+
+pub mod condition;
+pub mod effect;
+pub mod utility;
+
+/// A dimension of relationships, like affinity, trust or respect.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "DimensionData")]
+pub struct Dimension {
+    min: i32,
+    max: i32,
+    is_symmetric: bool,
+    decay: i32,
+}
+
+/// The unvalidated data of a [`Dimension`] during deserialization.
+#[derive(Deserialize)]
+struct DimensionData {
+    min: i32,
+    max: i32,
+    is_symmetric: bool,
+    decay: i32,
+}
+
+impl Dimension {
+    /// Creates a dimension.
+    ///
+    /// The values are clamped to \[min, max\] and move towards 0 by decay per tick.
+    /// A symmetric dimension has the same value in both directions.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::error::SocialError;
+    ///# use rusted_social_simulation::social::relationship::Dimension;
+    /// assert!(Dimension::try_new(-100, 100, false, 1).is_ok());
+    /// assert_eq!(
+    ///     Dimension::try_new(100, -100, false, 1),
+    ///     Err(SocialError::InvalidBounds { min: 100, max: -100 })
+    /// );
+    /// assert_eq!(
+    ///     Dimension::try_new(-100, 100, false, -1),
+    ///     Err(SocialError::NegativeDecay { decay: -1 })
+    /// );
+    /// ```
+    pub fn try_new(
+        min: i32,
+        max: i32,
+        is_symmetric: bool,
+        decay: i32,
+    ) -> Result<Dimension, SocialError> {
+        if min > max {
+            return Err(SocialError::InvalidBounds { min, max });
+        } else if decay < 0 {
+            return Err(SocialError::NegativeDecay { decay });
+        }
+
+        Ok(Dimension {
+            min,
+            max,
+            is_symmetric,
+            decay,
+        })
+    }
+
+    /// Clamps a value to the bounds of this dimension.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::relationship::Dimension;
+    /// let dimension = Dimension::try_new(-100, 100, false, 0).unwrap();
+    ///
+    /// assert_eq!(dimension.clamp(-120), -100);
+    /// assert_eq!(dimension.clamp(50), 50);
+    /// assert_eq!(dimension.clamp(101), 100);
+    /// ```
+    pub fn clamp(&self, value: i32) -> i32 {
+        value.max(self.min).min(self.max)
+    }
+
+    /// Gets the value of relationships, that were never set. It is 0 clamped to the bounds.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::relationship::Dimension;
+    /// assert_eq!(Dimension::try_new(-100, 100, false, 0).unwrap().get_default(), 0);
+    /// assert_eq!(Dimension::try_new(10, 100, false, 0).unwrap().get_default(), 10);
+    /// ```
+    pub fn get_default(&self) -> i32 {
+        self.clamp(0)
+    }
+
+    /// Moves a value towards 0, but keeps it within the bounds.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::relationship::Dimension;
+    /// let dimension = Dimension::try_new(-100, 100, false, 3).unwrap();
+    ///
+    /// assert_eq!(dimension.decay(10), 7);
+    /// assert_eq!(dimension.decay(-10), -7);
+    /// assert_eq!(dimension.decay(2), 0);
+    ///
+    /// let positive = Dimension::try_new(10, 100, false, 3).unwrap();
+    ///
+    /// assert_eq!(positive.decay(20), 17);
+    /// assert_eq!(positive.decay(11), 10);
+    /// ```
+    pub fn decay(&self, value: i32) -> i32 {
+        let value = if value > 0 {
+            value.saturating_sub(self.decay).max(0)
+        } else {
+            value.saturating_add(self.decay).min(0)
+        };
+
+        self.clamp(value)
+    }
+
+    pub fn is_symmetric(&self) -> bool {
+        self.is_symmetric
+    }
+}
+
+impl Default for Dimension {
+    /// An unbounded & asymmetric dimension without decay.
+    fn default() -> Self {
+        Dimension {
+            min: i32::MIN,
+            max: i32::MAX,
+            is_symmetric: false,
+            decay: 0,
+        }
+    }
+}
+
+impl TryFrom<DimensionData> for Dimension {
+    type Error = SocialError;
+
+    fn try_from(data: DimensionData) -> Result<Self, Self::Error> {
+        Dimension::try_new(data.min, data.max, data.is_symmetric, data.decay)
+    }
+}
+
+/// Stores the directed relationships between entities.
+///
+/// Each relationship is defined by the entity it is from, the entity it is towards & its dimension.
+/// Relationships that were never set have the default value of their dimension (see [`Dimension::get_default`]).
+/// Dimensions that were not added use [`Dimension::default`].
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Relationships {
+    dimensions: BTreeMap<String, Dimension>,
+    values: BTreeMap<String, BTreeMap<u32, BTreeMap<u32, i32>>>,
+}
+
+impl Relationships {
+    pub fn new() -> Relationships {
+        Relationships::default()
+    }
+
+    /// Adds or replaces a dimension.
+    pub fn add_dimension(&mut self, name: &str, dimension: Dimension) {
+        self.dimensions.insert(name.to_string(), dimension);
+    }
+
+    /// Gets a relationship.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::relationship::{Dimension, Relationships};
+    /// let mut relationships = Relationships::new();
+    /// relationships.add_dimension("trust", Dimension::try_new(-100, 100, false, 0).unwrap());
+    /// relationships.add_dimension("respect", Dimension::try_new(-100, 100, true, 0).unwrap());
+    /// relationships.set(1, 2, "trust", 30);
+    /// relationships.set(1, 2, "respect", 40);
+    ///
+    /// assert_eq!(relationships.get(1, 2, "trust"), 30);
+    /// assert_eq!(relationships.get(2, 1, "trust"), 0);
+    /// assert_eq!(relationships.get(2, 1, "respect"), 40);
+    ///
+    /// relationships.add_dimension("loyalty", Dimension::try_new(10, 100, false, 0).unwrap());
+    ///
+    /// assert_eq!(relationships.get(1, 2, "loyalty"), 10);
+    /// ```
+    pub fn get(&self, from: u32, to: u32, dimension: &str) -> i32 {
+        let (from, to) = self.get_key(from, to, dimension);
+
+        self.values
+            .get(dimension)
+            .and_then(|values| values.get(&from))
+            .and_then(|values| values.get(&to))
+            .copied()
+            .unwrap_or_else(|| self.get_dimension(dimension).get_default())
+    }
+
+    /// Sets a relationship, clamped to the bounds of its dimension.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::relationship::{Dimension, Relationships};
+    /// let mut relationships = Relationships::new();
+    /// relationships.add_dimension("affinity", Dimension::try_new(-100, 100, false, 0).unwrap());
+    ///
+    /// relationships.set(1, 2, "affinity", 130);
+    ///
+    /// assert_eq!(relationships.get(1, 2, "affinity"), 100);
+    /// ```
+    pub fn set(&mut self, from: u32, to: u32, dimension: &str, value: i32) {
+        let value = self.get_dimension(dimension).clamp(value);
+        let (from, to) = self.get_key(from, to, dimension);
+
+        self.values
+            .entry(dimension.to_string())
+            .or_default()
+            .entry(from)
+            .or_default()
+            .insert(to, value);
+    }
+
+    /// Adds a value to a relationship, clamped to the bounds of its dimension.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::relationship::{Dimension, Relationships};
+    /// let mut relationships = Relationships::new();
+    /// relationships.add_dimension("affinity", Dimension::try_new(-100, 100, false, 0).unwrap());
+    /// relationships.set(1, 2, "affinity", 30);
+    ///
+    /// relationships.modify(1, 2, "affinity", -50);
+    ///
+    /// assert_eq!(relationships.get(1, 2, "affinity"), -20);
+    /// ```
+    pub fn modify(&mut self, from: u32, to: u32, dimension: &str, delta: i32) {
+        let value = self.get(from, to, dimension).saturating_add(delta);
+        self.set(from, to, dimension, value);
+    }
+
+    /// Moves all relationships towards 0 by the decay of their dimension, but keeps them within its bounds.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::relationship::{Dimension, Relationships};
+    /// let mut relationships = Relationships::new();
+    /// relationships.add_dimension("affinity", Dimension::try_new(-100, 100, false, 5).unwrap());
+    /// relationships.set(1, 2, "affinity", 30);
+    /// relationships.set(2, 1, "affinity", -3);
+    ///
+    /// relationships.decay();
+    ///
+    /// assert_eq!(relationships.get(1, 2, "affinity"), 25);
+    /// assert_eq!(relationships.get(2, 1, "affinity"), 0);
+    /// ```
+    pub fn decay(&mut self) {
+        let default = Dimension::default();
+
+        for (name, values) in self.values.iter_mut() {
+            let dimension = self.dimensions.get(name).unwrap_or(&default);
+            let default_value = dimension.get_default();
+
+            for targets in values.values_mut() {
+                for value in targets.values_mut() {
+                    *value = dimension.decay(*value);
+                }
+
+                targets.retain(|_to, value| *value != default_value);
+            }
+
+            values.retain(|_from, targets| !targets.is_empty());
+        }
+    }
+
+    /// Removes all relationships from & towards an entity.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::relationship::Relationships;
+    /// let mut relationships = Relationships::new();
+    /// relationships.set(1, 2, "affinity", 30);
+    /// relationships.set(2, 1, "affinity", 20);
+    /// relationships.set(2, 3, "affinity", 10);
+    ///
+    /// relationships.remove(1);
+    ///
+    /// assert_eq!(relationships.get(1, 2, "affinity"), 0);
+    /// assert_eq!(relationships.get(2, 1, "affinity"), 0);
+    /// assert_eq!(relationships.get(2, 3, "affinity"), 10);
+    /// ```
+    pub fn remove(&mut self, entity: u32) {
+        for values in self.values.values_mut() {
+            values.remove(&entity);
+
+            for targets in values.values_mut() {
+                targets.remove(&entity);
+            }
+
+            values.retain(|_from, targets| !targets.is_empty());
+        }
+    }
+
+    fn get_dimension(&self, dimension: &str) -> Dimension {
+        self.dimensions.get(dimension).cloned().unwrap_or_default()
+    }
+
+    /// Symmetric relationships are always stored from the entity with the lower id.
+    fn get_key(&self, from: u32, to: u32, dimension: &str) -> (u32, u32) {
+        let is_symmetric = self
+            .dimensions
+            .get(dimension)
+            .map(|dimension| dimension.is_symmetric)
+            .unwrap_or(false);
+
+        if is_symmetric && from > to {
+            (to, from)
+        } else {
+            (from, to)
+        }
+    }
+}
+
+/// A context that contains relationships.
+pub trait RelationshipContext {
+    fn get_relationships(&self) -> &Relationships;
+
+    fn get_relationships_mut(&mut self) -> &mut Relationships;
+}
+
+impl RelationshipContext for Relationships {
+    fn get_relationships(&self) -> &Relationships {
+        self
+    }
+
+    fn get_relationships_mut(&mut self) -> &mut Relationships {
+        self
+    }
+}
+
+/// Gets all pairs of different entities between 2 targets.
+pub(crate) fn get_pairs<T: RoleContext>(context: &T, from: Target, to: Target) -> Vec<(u32, u32)> {
+    let targets = context.resolve(to);
+
+    context
+        .resolve(from)
+        .into_iter()
+        .flat_map(|from| {
+            targets
+                .iter()
+                .filter(move |to| **to != from)
+                .map(move |to| (from, *to))
+        })
+        .collect()
+}
+
+/// Gets the relationships between 2 targets.
+fn get_values<T: RelationshipContext + RoleContext>(
+    context: &T,
+    from: Target,
+    to: Target,
+    dimension: &str,
+) -> Vec<i32> {
+    get_pairs(context, from, to)
+        .into_iter()
+        .map(|(from, to)| context.get_relationships().get(from, to, dimension))
+        .collect()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::social::practice::role::Role;
+    use std::collections::HashMap;
+
+    pub const SPEAKER: Target = Target::Role(Role::Character { id: 0 });
+    pub const LISTENER: Target = Target::Role(Role::Character { id: 1 });
+
+    /// A context for testing with a speaker (entity 10) & 2 listeners (entity 11 & 12).
+    /// The speaker is the actor.
+    pub struct TestContext {
+        pub relationships: Relationships,
+        pub roles: HashMap<Role, u32>,
+    }
+
+    impl TestContext {
+        pub fn new() -> TestContext {
+            let mut relationships = Relationships::new();
+            relationships
+                .add_dimension("affinity", Dimension::try_new(-100, 100, false, 0).unwrap());
+            relationships.set(10, 11, "affinity", 30);
+            relationships.set(11, 10, "affinity", -10);
+            relationships.set(10, 12, "affinity", 50);
+
+            TestContext {
+                relationships,
+                roles: hashmap! {
+                    Role::Character { id: 0 } => 10,
+                    Role::Character { id: 1 } => 11,
+                    Role::Character { id: 2 } => 12,
+                },
+            }
+        }
+    }
+
+    impl RelationshipContext for TestContext {
+        fn get_relationships(&self) -> &Relationships {
+            &self.relationships
+        }
+
+        fn get_relationships_mut(&mut self) -> &mut Relationships {
+            &mut self.relationships
+        }
+    }
+
+    impl RoleContext for TestContext {
+        fn get_actor(&self) -> Option<u32> {
+            Some(10)
+        }
+
+        fn get_entity(&self, role: Role) -> Option<u32> {
+            self.roles.get(&role).copied()
+        }
+
+        fn get_participants(&self) -> Vec<u32> {
+            vec![10, 11, 12]
+        }
+    }
+
+    #[test]
+    fn test_unknown_dimension_is_unbounded() {
+        let mut relationships = Relationships::new();
+
+        relationships.set(1, 2, "fear", 1000);
+
+        assert_eq!(relationships.get(1, 2, "fear"), 1000);
+        assert_eq!(relationships.get(2, 1, "fear"), 0);
+    }
+
+    #[test]
+    fn test_modify_symmetric() {
+        let mut relationships = Relationships::new();
+        relationships.add_dimension("friendship", Dimension::try_new(0, 10, true, 0).unwrap());
+
+        relationships.modify(5, 3, "friendship", 4);
+        relationships.modify(3, 5, "friendship", 4);
+
+        assert_eq!(relationships.get(3, 5, "friendship"), 8);
+        assert_eq!(relationships.get(5, 3, "friendship"), 8);
+    }
+
+    #[test]
+    fn test_deserialize_invalid_dimension() {
+        let text = "(min: 10, max: -10, is_symmetric: false, decay: 0)";
+
+        assert!(ron::from_str::<Dimension>(text).is_err());
+        assert!(
+            ron::from_str::<Dimension>("(min: -10, max: 10, is_symmetric: false, decay: -2)")
+                .is_err()
+        );
+        assert_eq!(
+            ron::from_str::<Dimension>("(min: -10, max: 10, is_symmetric: true, decay: 2)"),
+            Ok(Dimension::try_new(-10, 10, true, 2).unwrap())
+        );
+    }
+}
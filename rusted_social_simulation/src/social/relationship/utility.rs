@@ -0,0 +1,105 @@
+use crate::social::practice::role::Target;
+use crate::social::practice::RoleContext;
+use crate::social::relationship::{get_values, RelationshipContext};
+use crate::social::utility::{Utility, UtilityRule};
+
+/// An utility rule that scales the relationships between 2 targets.
+pub struct RelationshipScaledUtility {
+    from: Target,
+    to: Target,
+    dimension: String,
+    factor: Utility,
+}
+
+impl RelationshipScaledUtility {
+    pub fn new(
+        from: Target,
+        to: Target,
+        dimension: &str,
+        factor: Utility,
+    ) -> RelationshipScaledUtility {
+        RelationshipScaledUtility {
+            from,
+            to,
+            dimension: dimension.to_string(),
+            factor,
+        }
+    }
+}
+
+impl<T: RelationshipContext + RoleContext> UtilityRule<T> for RelationshipScaledUtility {
+    /// Returns the sum of the relationships multiplied with a factor.
+    /// Returns 0, if a target refers to no entity. The result saturates instead of overflowing.
+    fn calculate_utility(&self, context: &T) -> Utility {
+        get_values(context, self.from, self.to, &self.dimension)
+            .iter()
+            .map(|value| Utility::from_integer(*value).saturating_mul(self.factor))
+            .fold(Utility::default(), |sum, utility| {
+                sum.saturating_add(utility)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::social::practice::role::Role;
+    use crate::social::relationship::tests::{TestContext, LISTENER, SPEAKER};
+
+    #[test]
+    fn test_scaled() {
+        let context = TestContext::new();
+
+        assert_eq!(
+            RelationshipScaledUtility::new(SPEAKER, LISTENER, "affinity", Utility::from_integer(2))
+                .calculate_utility(&context),
+            Utility::from_integer(60)
+        );
+        assert_eq!(
+            RelationshipScaledUtility::new(LISTENER, SPEAKER, "affinity", Utility::from_integer(3))
+                .calculate_utility(&context),
+            Utility::from_integer(-30)
+        );
+    }
+
+    #[test]
+    fn test_actor_toward_others() {
+        let context = TestContext::new();
+
+        assert_eq!(
+            RelationshipScaledUtility::new(
+                Target::Actor,
+                Target::Others,
+                "affinity",
+                Utility::from_integer(1)
+            )
+            .calculate_utility(&context),
+            Utility::from_integer(80)
+        );
+    }
+
+    #[test]
+    fn test_saturate() {
+        let mut context = TestContext::new();
+        context.relationships.set(10, 11, "affinity", 100);
+        context.relationships.set(10, 12, "affinity", 100);
+
+        assert_eq!(
+            RelationshipScaledUtility::new(Target::Actor, Target::Others, "affinity", Utility::MAX)
+                .calculate_utility(&context),
+            Utility::MAX
+        );
+    }
+
+    #[test]
+    fn test_unknown_role() {
+        let context = TestContext::new();
+        let unknown = Target::Role(Role::Character { id: 99 });
+
+        assert_eq!(
+            RelationshipScaledUtility::new(SPEAKER, unknown, "affinity", Utility::from_integer(2))
+                .calculate_utility(&context),
+            Utility::default()
+        );
+    }
+}
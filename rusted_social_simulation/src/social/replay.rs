@@ -0,0 +1,191 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The selection of an action by an entity, whose turn it was.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Choice {
+    tick: u32,
+    practice: u32,
+    actor: u32,
+    available: Vec<String>,
+    chosen: Option<String>,
+}
+
+impl Choice {
+    pub fn new(
+        tick: u32,
+        practice: u32,
+        actor: u32,
+        available: Vec<String>,
+        chosen: Option<String>,
+    ) -> Choice {
+        Choice {
+            tick,
+            practice,
+            actor,
+            available,
+            chosen,
+        }
+    }
+
+    pub fn get_tick(&self) -> u32 {
+        self.tick
+    }
+
+    pub fn get_practice(&self) -> u32 {
+        self.practice
+    }
+
+    pub fn get_actor(&self) -> u32 {
+        self.actor
+    }
+
+    /// Gets the names of the actions, that were available.
+    pub fn get_available(&self) -> &[String] {
+        &self.available
+    }
+
+    /// Gets the name of the chosen action, if any.
+    pub fn get_chosen(&self) -> Option<&str> {
+        self.chosen.as_deref()
+    }
+}
+
+impl fmt::Display for Choice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "entity {} chose {:?} from {:?} in practice {} at tick {}",
+            self.actor, self.chosen, self.available, self.practice, self.tick
+        )
+    }
+}
+
+/// Everything needed to reproduce a run of a simulation:
+/// the seed, the initial context & the choices of all entities.
+///
+/// The practices aren't part of it & need to be recreated from the same templates.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Recording<T> {
+    seed: u64,
+    initial: T,
+    ticks: u32,
+    choices: Vec<Choice>,
+}
+
+impl<T> Recording<T> {
+    pub fn new(seed: u64, initial: T, ticks: u32, choices: Vec<Choice>) -> Recording<T> {
+        Recording {
+            seed,
+            initial,
+            ticks,
+            choices,
+        }
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Gets the context at the start of the run.
+    pub fn get_initial(&self) -> &T {
+        &self.initial
+    }
+
+    /// Gets the number of recorded ticks.
+    pub fn get_ticks(&self) -> u32 {
+        self.ticks
+    }
+
+    pub fn get_choices(&self) -> &[Choice] {
+        &self.choices
+    }
+}
+
+/// The first difference between a replay & its recording.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Divergence {
+    /// The replay can only start before the first tick.
+    Started { tick: u32 },
+    /// The replay uses another seed.
+    Seed { expected: u64, actual: u64 },
+    /// The replay starts with another context.
+    Initial,
+    /// A choice differs. A missing choice is *None*.
+    Choice {
+        index: usize,
+        expected: Option<Box<Choice>>,
+        actual: Option<Box<Choice>>,
+    },
+}
+
+impl fmt::Display for Divergence {
+    /// Describes the divergence.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::replay::{Choice, Divergence};
+    /// let expected = Choice::new(3, 0, 10, vec!["a".to_string(), "b".to_string()], Some("a".to_string()));
+    /// let divergence = Divergence::Choice { index: 5, expected: Some(Box::new(expected)), actual: None };
+    ///
+    /// assert_eq!(
+    ///     divergence.to_string(),
+    ///     "Choice 5 diverged: Expected entity 10 chose Some(\"a\") from [\"a\", \"b\"] in practice 0 at tick 3, but got nothing!"
+    /// );
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Divergence::Started { tick } => {
+                write!(f, "The replay started at tick {} instead of 0!", tick)
+            }
+            Divergence::Seed { expected, actual } => write!(
+                f,
+                "The replay uses seed {} instead of {}!",
+                actual, expected
+            ),
+            Divergence::Initial => {
+                write!(f, "The replay starts with another context!")
+            }
+            Divergence::Choice {
+                index,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Choice {} diverged: Expected {}, but got {}!",
+                index,
+                describe(expected),
+                describe(actual)
+            ),
+        }
+    }
+}
+
+fn describe(choice: &Option<Box<Choice>>) -> String {
+    match choice {
+        Some(choice) => choice.to_string(),
+        None => "nothing".to_string(),
+    }
+}
+
+/// Compares the new choices of a replay with the recorded ones.
+///
+/// Returns the first divergence, if any.
+pub(crate) fn compare_choices(
+    expected: &[Choice],
+    actual: &[Choice],
+    start: usize,
+) -> Result<(), Divergence> {
+    for index in start..actual.len() {
+        let expected = expected.get(index);
+
+        if expected != actual.get(index) {
+            return Err(Divergence::Choice {
+                index,
+                expected: expected.cloned().map(Box::new),
+                actual: actual.get(index).cloned().map(Box::new),
+            });
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,499 @@
+use crate::social::action::Action;
+use crate::social::utility::Utility;
+use rand::{Rng, RngCore};
+
+/// A strategy to select one of multiple actions, e.g. the actions of an entity in a practice.
+///
+/// All randomness comes from the given random number generator,
+/// so a seeded generator makes the selection reproducible.
+pub trait Selector<T> {
+    /// Selects one of the actions that are available with the current context.
+    ///
+    /// Returns None, if no action is available.
+    fn select<'a>(
+        &self,
+        actions: &[&'a dyn Action<T>],
+        context: &T,
+        rng: &mut dyn RngCore,
+    ) -> Option<&'a dyn Action<T>>;
+}
+
+/// Selects the available action with the highest utility.
+pub struct ArgMaxSelector;
+
+impl<T> Selector<T> for ArgMaxSelector {
+    /// Selects the available action with the highest utility.
+    /// If multiple actions have the highest utility, the first one is selected.
+    ///
+    /// ```
+    ///# use rand::rngs::StdRng;
+    ///# use rand::SeedableRng;
+    ///# use rusted_social_simulation::social::action::{Action, SimpleAction};
+    ///# use rusted_social_simulation::social::condition::MockCondition;
+    ///# use rusted_social_simulation::social::effect::DoNothing;
+    ///# use rusted_social_simulation::social::selection::{ArgMaxSelector, Selector};
+    ///# use rusted_social_simulation::social::utility::{FixedUtility, Utility};
+    ///# fn action(name: &str, is_available: bool, utility: i32) -> SimpleAction<u32> {
+    ///#   let condition = Box::new(MockCondition::new(is_available));
+    ///#   let utility_rule = Box::new(FixedUtility::new(Utility::from_integer(utility)));
+    ///#   SimpleAction::new(name.to_string(), condition, utility_rule, Box::new(DoNothing))
+    ///# }
+    /// let action0 = action("action0", true, 5);
+    /// let action1 = action("action1", true, 20);
+    /// let action2 = action("action2", false, 100);
+    /// let actions: Vec<&dyn Action<u32>> = vec![&action0, &action1, &action2];
+    /// let mut rng = StdRng::seed_from_u64(0);
+    ///
+    /// let selected = ArgMaxSelector.select(&actions, &42, &mut rng);
+    ///
+    /// assert_eq!(selected.unwrap().get_name(), "action1");
+    /// ```
+    fn select<'a>(
+        &self,
+        actions: &[&'a dyn Action<T>],
+        context: &T,
+        _rng: &mut dyn RngCore,
+    ) -> Option<&'a dyn Action<T>> {
+        select_max(&get_available(actions, context))
+    }
+}
+
+/// Selects an available action randomly, weighted by its utility.
+pub struct WeightedRandomSelector;
+
+impl<T> Selector<T> for WeightedRandomSelector {
+    /// Selects an available action with a probability proportional to its utility.
+    ///
+    /// Actions with an utility of 0 or less are never selected,
+    /// unless all available actions have such an utility.
+    /// Then all of them are equally likely.
+    ///
+    /// ```
+    ///# use rand::rngs::StdRng;
+    ///# use rand::SeedableRng;
+    ///# use rusted_social_simulation::social::action::{Action, SimpleAction};
+    ///# use rusted_social_simulation::social::condition::MockCondition;
+    ///# use rusted_social_simulation::social::effect::DoNothing;
+    ///# use rusted_social_simulation::social::selection::{Selector, WeightedRandomSelector};
+    ///# use rusted_social_simulation::social::utility::{FixedUtility, Utility};
+    ///# fn action(name: &str, is_available: bool, utility: i32) -> SimpleAction<u32> {
+    ///#   let condition = Box::new(MockCondition::new(is_available));
+    ///#   let utility_rule = Box::new(FixedUtility::new(Utility::from_integer(utility)));
+    ///#   SimpleAction::new(name.to_string(), condition, utility_rule, Box::new(DoNothing))
+    ///# }
+    /// let action0 = action("action0", true, 0);
+    /// let action1 = action("action1", true, 20);
+    /// let action2 = action("action2", false, 100);
+    /// let actions: Vec<&dyn Action<u32>> = vec![&action0, &action1, &action2];
+    /// let mut rng = StdRng::seed_from_u64(0);
+    ///
+    /// let selected = WeightedRandomSelector.select(&actions, &42, &mut rng);
+    ///
+    /// assert_eq!(selected.unwrap().get_name(), "action1");
+    /// ```
+    fn select<'a>(
+        &self,
+        actions: &[&'a dyn Action<T>],
+        context: &T,
+        rng: &mut dyn RngCore,
+    ) -> Option<&'a dyn Action<T>> {
+        select_weighted(&get_available(actions, context), rng)
+    }
+}
+
+/// Selects an available action randomly with the probabilities of the softmax function.
+pub struct SoftmaxSelector {
+    temperature: f64,
+}
+
+impl SoftmaxSelector {
+    /// Creates a softmax selector.
+    ///
+    /// A low temperature favors the actions with the highest utility,
+    /// while a high temperature makes all actions more equally likely.
+    ///
+    /// # Panics
+    ///
+    /// ```should_panic
+    ///# use rusted_social_simulation::social::selection::SoftmaxSelector;
+    /// SoftmaxSelector::new(0.0);
+    /// ```
+    pub fn new(temperature: f64) -> SoftmaxSelector {
+        if temperature <= 0.0 {
+            panic!("The temperature {} must be positive!", temperature);
+        }
+
+        SoftmaxSelector { temperature }
+    }
+}
+
+impl<T> Selector<T> for SoftmaxSelector {
+    /// Selects an available action with a probability of `exp(utility / temperature)`,
+    /// normalized over all available actions.
+    ///
+    /// ```
+    ///# use rand::rngs::StdRng;
+    ///# use rand::SeedableRng;
+    ///# use rusted_social_simulation::social::action::{Action, SimpleAction};
+    ///# use rusted_social_simulation::social::condition::MockCondition;
+    ///# use rusted_social_simulation::social::effect::DoNothing;
+    ///# use rusted_social_simulation::social::selection::{Selector, SoftmaxSelector};
+    ///# use rusted_social_simulation::social::utility::{FixedUtility, Utility};
+    ///# fn action(name: &str, is_available: bool, utility: i32) -> SimpleAction<u32> {
+    ///#   let condition = Box::new(MockCondition::new(is_available));
+    ///#   let utility_rule = Box::new(FixedUtility::new(Utility::from_integer(utility)));
+    ///#   SimpleAction::new(name.to_string(), condition, utility_rule, Box::new(DoNothing))
+    ///# }
+    /// let action0 = action("action0", true, 0);
+    /// let action1 = action("action1", true, 100);
+    /// let actions: Vec<&dyn Action<u32>> = vec![&action0, &action1];
+    /// let mut rng = StdRng::seed_from_u64(0);
+    ///
+    /// let selected = SoftmaxSelector::new(1.0).select(&actions, &42, &mut rng);
+    ///
+    /// assert_eq!(selected.unwrap().get_name(), "action1");
+    /// ```
+    fn select<'a>(
+        &self,
+        actions: &[&'a dyn Action<T>],
+        context: &T,
+        rng: &mut dyn RngCore,
+    ) -> Option<&'a dyn Action<T>> {
+        let available = get_available(actions, context);
+        let max = available.iter().map(|(_action, utility)| *utility).max()?;
+        let weights: Vec<f64> = available
+            .iter()
+            .map(|(_action, utility)| {
+                ((utility.to_f32() as f64 - max.to_f32() as f64) / self.temperature).exp()
+            })
+            .collect();
+        let mut value = rng.gen::<f64>() * weights.iter().sum::<f64>();
+
+        for ((action, _utility), weight) in available.iter().zip(weights.iter()) {
+            if value < *weight {
+                return Some(*action);
+            }
+            value -= weight;
+        }
+
+        available.last().map(|(action, _utility)| *action)
+    }
+}
+
+/// Selects the best available action most of the time, but sometimes a random one.
+pub struct EpsilonGreedySelector {
+    epsilon: f64,
+}
+
+impl EpsilonGreedySelector {
+    /// Creates an epsilon-greedy selector.
+    ///
+    /// Epsilon is the probability to select a random action instead of the best one.
+    ///
+    /// # Panics
+    ///
+    /// ```should_panic
+    ///# use rusted_social_simulation::social::selection::EpsilonGreedySelector;
+    /// EpsilonGreedySelector::new(1.5);
+    /// ```
+    pub fn new(epsilon: f64) -> EpsilonGreedySelector {
+        if !(0.0..=1.0).contains(&epsilon) {
+            panic!("The epsilon {} must be between 0 and 1!", epsilon);
+        }
+
+        EpsilonGreedySelector { epsilon }
+    }
+}
+
+impl<T> Selector<T> for EpsilonGreedySelector {
+    /// Selects a random available action with a probability of epsilon,
+    /// and the one with the highest utility otherwise.
+    ///
+    /// ```
+    ///# use rand::rngs::StdRng;
+    ///# use rand::SeedableRng;
+    ///# use rusted_social_simulation::social::action::{Action, SimpleAction};
+    ///# use rusted_social_simulation::social::condition::MockCondition;
+    ///# use rusted_social_simulation::social::effect::DoNothing;
+    ///# use rusted_social_simulation::social::selection::{EpsilonGreedySelector, Selector};
+    ///# use rusted_social_simulation::social::utility::{FixedUtility, Utility};
+    ///# fn action(name: &str, is_available: bool, utility: i32) -> SimpleAction<u32> {
+    ///#   let condition = Box::new(MockCondition::new(is_available));
+    ///#   let utility_rule = Box::new(FixedUtility::new(Utility::from_integer(utility)));
+    ///#   SimpleAction::new(name.to_string(), condition, utility_rule, Box::new(DoNothing))
+    ///# }
+    /// let action0 = action("action0", true, 5);
+    /// let action1 = action("action1", true, 20);
+    /// let actions: Vec<&dyn Action<u32>> = vec![&action0, &action1];
+    /// let mut rng = StdRng::seed_from_u64(0);
+    ///
+    /// let selected = EpsilonGreedySelector::new(0.0).select(&actions, &42, &mut rng);
+    ///
+    /// assert_eq!(selected.unwrap().get_name(), "action1");
+    /// ```
+    fn select<'a>(
+        &self,
+        actions: &[&'a dyn Action<T>],
+        context: &T,
+        rng: &mut dyn RngCore,
+    ) -> Option<&'a dyn Action<T>> {
+        let available = get_available(actions, context);
+
+        if rng.gen::<f64>() < self.epsilon {
+            return select_uniform(&available, rng);
+        }
+
+        select_max(&available)
+    }
+}
+
+/// Selects randomly between the k available actions with the highest utility.
+pub struct TopKSelector {
+    k: usize,
+}
+
+impl TopKSelector {
+    /// Creates a top-k selector.
+    ///
+    /// # Panics
+    ///
+    /// ```should_panic
+    ///# use rusted_social_simulation::social::selection::TopKSelector;
+    /// TopKSelector::new(0);
+    /// ```
+    pub fn new(k: usize) -> TopKSelector {
+        if k == 0 {
+            panic!("Top-k selection requires k > 0!");
+        }
+
+        TopKSelector { k }
+    }
+}
+
+impl<T> Selector<T> for TopKSelector {
+    /// Selects one of the k available actions with the highest utility,
+    /// weighted by their utility like [`WeightedRandomSelector`].
+    ///
+    /// ```
+    ///# use rand::rngs::StdRng;
+    ///# use rand::SeedableRng;
+    ///# use rusted_social_simulation::social::action::{Action, SimpleAction};
+    ///# use rusted_social_simulation::social::condition::MockCondition;
+    ///# use rusted_social_simulation::social::effect::DoNothing;
+    ///# use rusted_social_simulation::social::selection::{Selector, TopKSelector};
+    ///# use rusted_social_simulation::social::utility::{FixedUtility, Utility};
+    ///# fn action(name: &str, is_available: bool, utility: i32) -> SimpleAction<u32> {
+    ///#   let condition = Box::new(MockCondition::new(is_available));
+    ///#   let utility_rule = Box::new(FixedUtility::new(Utility::from_integer(utility)));
+    ///#   SimpleAction::new(name.to_string(), condition, utility_rule, Box::new(DoNothing))
+    ///# }
+    /// let action0 = action("action0", true, 5);
+    /// let action1 = action("action1", true, 20);
+    /// let action2 = action("action2", true, 10);
+    /// let actions: Vec<&dyn Action<u32>> = vec![&action0, &action1, &action2];
+    /// let mut rng = StdRng::seed_from_u64(0);
+    ///
+    /// let selected = TopKSelector::new(2).select(&actions, &42, &mut rng);
+    ///
+    /// assert_ne!(selected.unwrap().get_name(), "action0");
+    /// ```
+    fn select<'a>(
+        &self,
+        actions: &[&'a dyn Action<T>],
+        context: &T,
+        rng: &mut dyn RngCore,
+    ) -> Option<&'a dyn Action<T>> {
+        let mut available = get_available(actions, context);
+        available.sort_by(|(_a0, u0), (_a1, u1)| u1.cmp(u0));
+        available.truncate(self.k);
+        select_weighted(&available, rng)
+    }
+}
+
+/// Returns all available actions with their utility.
+fn get_available<'a, T>(
+    actions: &[&'a dyn Action<T>],
+    context: &T,
+) -> Vec<(&'a dyn Action<T>, Utility)> {
+    actions
+        .iter()
+        .filter(|action| action.is_available(context))
+        .map(|action| (*action, action.get_utility(context)))
+        .collect()
+}
+
+fn select_max<'a, T>(available: &[(&'a dyn Action<T>, Utility)]) -> Option<&'a dyn Action<T>> {
+    let mut best: Option<(&'a dyn Action<T>, Utility)> = None;
+
+    for (action, utility) in available {
+        match best {
+            Some((_action, best_utility)) if best_utility >= *utility => {}
+            _ => best = Some((*action, *utility)),
+        }
+    }
+
+    best.map(|(action, _utility)| action)
+}
+
+fn select_uniform<'a, T>(
+    available: &[(&'a dyn Action<T>, Utility)],
+    rng: &mut dyn RngCore,
+) -> Option<&'a dyn Action<T>> {
+    if available.is_empty() {
+        return None;
+    }
+
+    let index = rng.gen_range(0..available.len());
+    Some(available[index].0)
+}
+
+fn select_weighted<'a, T>(
+    available: &[(&'a dyn Action<T>, Utility)],
+    rng: &mut dyn RngCore,
+) -> Option<&'a dyn Action<T>> {
+    let total: i64 = available
+        .iter()
+        .map(|(_action, utility)| get_weight(*utility))
+        .sum();
+
+    if total == 0 {
+        return select_uniform(available, rng);
+    }
+
+    let mut value = rng.gen_range(0..total);
+
+    for (action, utility) in available {
+        let weight = get_weight(*utility);
+
+        if value < weight {
+            return Some(*action);
+        }
+        value -= weight;
+    }
+
+    None
+}
+
+/// Converts an utility to a weight, that is 100 for [`Utility::ONE`]. Negative utilities have no weight.
+fn get_weight(utility: Utility) -> i64 {
+    (utility.to_f32() * 100.0).round().max(0.0) as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::social::action::SimpleAction;
+    use crate::social::condition::MockCondition;
+    use crate::social::effect::DoNothing;
+    use crate::social::utility::FixedUtility;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_no_available_action() {
+        let action0 = action("action0", false, 10);
+        let actions: Vec<&dyn Action<u32>> = vec![&action0];
+
+        assert_none(&ArgMaxSelector, &actions);
+        assert_none(&WeightedRandomSelector, &actions);
+        assert_none(&SoftmaxSelector::new(1.0), &actions);
+        assert_none(&EpsilonGreedySelector::new(1.0), &actions);
+        assert_none(&TopKSelector::new(3), &actions);
+    }
+
+    #[test]
+    fn test_arg_max_with_tie() {
+        let action0 = action("action0", true, 10);
+        let action1 = action("action1", true, 10);
+        let actions: Vec<&dyn Action<u32>> = vec![&action0, &action1];
+
+        assert_eq!(
+            select_names(&ArgMaxSelector, &actions, 5),
+            vec!["action0"; 5]
+        );
+    }
+
+    #[test]
+    fn test_weighted_random_with_only_zero_utilities() {
+        let action0 = action("action0", true, 0);
+        let action1 = action("action1", true, -5);
+        let actions: Vec<&dyn Action<u32>> = vec![&action0, &action1];
+
+        let names = select_names(&WeightedRandomSelector, &actions, 100);
+
+        assert!(names.contains(&"action0".to_string()));
+        assert!(names.contains(&"action1".to_string()));
+    }
+
+    #[test]
+    fn test_epsilon_greedy_with_epsilon_of_1() {
+        let action0 = action("action0", true, 1);
+        let action1 = action("action1", true, 100);
+        let actions: Vec<&dyn Action<u32>> = vec![&action0, &action1];
+
+        let names = select_names(&EpsilonGreedySelector::new(1.0), &actions, 100);
+
+        assert!(names.contains(&"action0".to_string()));
+    }
+
+    #[test]
+    fn test_top_k_ignores_worse_actions() {
+        let action0 = action("action0", true, 1);
+        let action1 = action("action1", true, 100);
+        let action2 = action("action2", true, 50);
+        let actions: Vec<&dyn Action<u32>> = vec![&action0, &action1, &action2];
+
+        let names = select_names(&TopKSelector::new(2), &actions, 100);
+
+        assert!(!names.contains(&"action0".to_string()));
+    }
+
+    #[test]
+    fn test_same_seed_is_reproducible() {
+        let action0 = action("action0", true, 10);
+        let action1 = action("action1", true, 11);
+        let action2 = action("action2", true, 12);
+        let actions: Vec<&dyn Action<u32>> = vec![&action0, &action1, &action2];
+        let selector = SoftmaxSelector::new(5.0);
+
+        assert_eq!(
+            select_names(&selector, &actions, 20),
+            select_names(&selector, &actions, 20)
+        );
+    }
+
+    fn action(name: &str, is_available: bool, utility: i32) -> SimpleAction<u32> {
+        let condition = Box::new(MockCondition::new(is_available));
+        let utility_rule = Box::new(FixedUtility::new(Utility::from_integer(utility)));
+        SimpleAction::new(
+            name.to_string(),
+            condition,
+            utility_rule,
+            Box::new(DoNothing),
+        )
+    }
+
+    fn assert_none(selector: &dyn Selector<u32>, actions: &[&dyn Action<u32>]) {
+        let mut rng = StdRng::seed_from_u64(0);
+
+        assert!(selector.select(actions, &42, &mut rng).is_none());
+    }
+
+    fn select_names(
+        selector: &dyn Selector<u32>,
+        actions: &[&dyn Action<u32>],
+        n: usize,
+    ) -> Vec<String> {
+        let mut rng = StdRng::seed_from_u64(7);
+
+        (0..n)
+            .map(|_| {
+                selector
+                    .select(actions, &42, &mut rng)
+                    .unwrap()
+                    .get_name()
+                    .to_string()
+            })
+            .collect()
+    }
+}
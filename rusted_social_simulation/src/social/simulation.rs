@@ -0,0 +1,941 @@
+use crate::social::action::Action;
+use crate::social::effect::Effect;
+use crate::social::error::SocialError;
+use crate::social::event::{EventContext, EventLog};
+use crate::social::execution::ExecutionContext;
+use crate::social::explanation::Explanation;
+use crate::social::practice::Practice;
+use crate::social::replay::{compare_choices, Choice, Divergence, Recording};
+use crate::social::selection::Selector;
+use crate::social::snapshot::{PracticeSnapshot, SimulationSnapshot};
+use crate::social::utility::Utility;
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+
+/// A turn-based social simulation.
+///
+/// It owns the context & the active practices and advances them one tick at a time.
+/// The actions of the practices are evaluated & executed with an [`ExecutionContext`],
+/// that is bound to the acting entity and its practice.
+pub struct Simulation<'a, T> {
+    context: ExecutionContext<T>,
+    practices: Vec<Box<dyn Practice<ExecutionContext<T>> + 'a>>,
+    tick_effects: Vec<Box<dyn Effect<T>>>,
+    selector: Box<dyn Selector<ExecutionContext<T>>>,
+    controlled: Vec<u32>,
+    rng: ChaCha12Rng,
+    seed: u64,
+    tick: u32,
+    initial: Option<T>,
+    choices: Option<Vec<Choice>>,
+}
+
+impl<'a, T> Simulation<'a, T> {
+    /// Creates a simulation without practices.
+    ///
+    /// The seed initializes the random number generator used by the selector,
+    /// so two simulations with the same seed & setup behave the same.
+    pub fn new(
+        context: T,
+        selector: Box<dyn Selector<ExecutionContext<T>>>,
+        seed: u64,
+    ) -> Simulation<'a, T> {
+        Simulation::with_context(ExecutionContext::new(context), selector, seed)
+    }
+
+    fn with_context(
+        context: ExecutionContext<T>,
+        selector: Box<dyn Selector<ExecutionContext<T>>>,
+        seed: u64,
+    ) -> Simulation<'a, T> {
+        Simulation {
+            context,
+            practices: Vec::new(),
+            tick_effects: Vec::new(),
+            selector,
+            controlled: Vec::new(),
+            rng: ChaCha12Rng::seed_from_u64(seed),
+            seed,
+            tick: 0,
+            initial: None,
+            choices: None,
+        }
+    }
+
+    /// Adds a practice, whose entities will act in the following ticks.
+    ///
+    /// ```
+    ///# use maplit::hashmap;
+    ///# use rusted_social_simulation::social::practice::role::Role;
+    ///# use rusted_social_simulation::social::practice::simple::{SimplePractice, SimplePracticeTemplate};
+    ///# use rusted_social_simulation::social::selection::ArgMaxSelector;
+    ///# use rusted_social_simulation::social::simulation::Simulation;
+    /// let speaker = Role::Character { id: 0 };
+    /// let role_names = hashmap! { speaker => "Speaker".to_string() };
+    /// let template = SimplePracticeTemplate::new(0, "talk".to_string(), role_names, hashmap! {});
+    /// let mut simulation = Simulation::new(42, Box::new(ArgMaxSelector), 0);
+    ///
+    /// simulation.add_practice(Box::new(SimplePractice::new(0, hashmap! { speaker => 10 }, &template)));
+    ///
+    /// assert_eq!(simulation.get_practices().len(), 1);
+    /// ```
+    pub fn add_practice(&mut self, practice: Box<dyn Practice<ExecutionContext<T>> + 'a>) {
+        self.practices.push(practice);
+    }
+
+    /// Adds an effect that is applied at the end of each tick, e.g. to let relationships decay.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::effect::MockEffect;
+    ///# use rusted_social_simulation::social::selection::ArgMaxSelector;
+    ///# use rusted_social_simulation::social::simulation::Simulation;
+    /// let mut simulation = Simulation::new(42, Box::new(ArgMaxSelector), 0);
+    ///
+    /// simulation.add_tick_effect(Box::new(MockEffect::new(2)));
+    /// simulation.run(3);
+    ///
+    /// assert_eq!(*simulation.get_context(), 48);
+    /// ```
+    pub fn add_tick_effect(&mut self, effect: Box<dyn Effect<T>>) {
+        self.tick_effects.push(effect);
+    }
+
+    /// Lets an entity be controlled from outside the simulation, e.g. by a human player.
+    ///
+    /// [`Simulation::step`] skips its turns, so it only acts with [`Simulation::act`].
+    pub fn control(&mut self, entity: u32) {
+        if !self.controlled.contains(&entity) {
+            self.controlled.push(entity);
+        }
+    }
+
+    /// Gets the current context.
+    pub fn get_context(&self) -> &T {
+        self.context.get_context()
+    }
+
+    /// Gets the current context to modify it from outside the simulation.
+    pub fn get_context_mut(&mut self) -> &mut T {
+        self.context.get_context_mut()
+    }
+
+    /// Gets the log of all executed actions.
+    pub fn get_event_log(&self) -> &EventLog {
+        self.context.get_event_log()
+    }
+
+    /// Gets all active practices.
+    pub fn get_practices(&self) -> &[Box<dyn Practice<ExecutionContext<T>> + 'a>] {
+        &self.practices
+    }
+
+    /// Are all practices finished?
+    ///
+    /// ```
+    ///# use maplit::hashmap;
+    ///# use rusted_social_simulation::social::condition::MockCondition;
+    ///# use rusted_social_simulation::social::execution::ExecutionContext;
+    ///# use rusted_social_simulation::social::practice::participants::Participants;
+    ///# use rusted_social_simulation::social::practice::staged::{Stage, StagedPractice, StagedPracticeTemplate, Transition};
+    ///# use rusted_social_simulation::social::selection::ArgMaxSelector;
+    ///# use rusted_social_simulation::social::simulation::Simulation;
+    /// let transition = Transition::new("end", Box::new(MockCondition::new(true)));
+    /// let stages: Vec<Stage<ExecutionContext<u32>>> =
+    ///     vec![Stage::new("talk", hashmap! {}, vec![transition]), Stage::terminal("end")];
+    /// let template = StagedPracticeTemplate::try_new(0, "talk".to_string(), hashmap! {}, stages).unwrap();
+    /// let mut simulation = Simulation::new(42, Box::new(ArgMaxSelector), 0);
+    /// simulation.add_practice(Box::new(StagedPractice::try_new(0, Participants::new(), &template).unwrap()));
+    ///
+    /// assert!(!simulation.is_finished());
+    /// simulation.step();
+    /// assert!(simulation.is_finished());
+    /// ```
+    pub fn is_finished(&self) -> bool {
+        self.practices.iter().all(|practice| practice.is_finished())
+    }
+
+    /// Gets the seed of the random number generator.
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Gets the number of ticks that were simulated so far.
+    pub fn get_tick(&self) -> u32 {
+        self.tick
+    }
+
+    /// Simulates a single tick.
+    ///
+    /// Each entity of each practice selects one of its actions based on its role
+    /// and executes it. Entities act one after another in the order of
+    /// [`Practice::get_entities`], so later entities see the changes of earlier ones.
+    /// Only entities, whose turn it is, act (see [`Practice::get_turn`]).
+    /// Entities without an available action pass their turn on.
+    /// Each executed action is recorded in the event log of the [`ExecutionContext`].
+    /// After all its entities acted, each practice is updated (e.g. to change its stage).
+    /// Finished practices are skipped. Afterwards the tick effects are applied.
+    ///
+    /// ```
+    ///# use maplit::hashmap;
+    ///# use rusted_social_simulation::social::action::{Action, SimpleAction};
+    ///# use rusted_social_simulation::social::effect::MockEffect;
+    ///# use rusted_social_simulation::social::execution::{ActorHasRole, ExecutionContext, Unbound};
+    ///# use rusted_social_simulation::social::practice::role::Role;
+    ///# use rusted_social_simulation::social::practice::simple::{SimplePractice, SimplePracticeTemplate};
+    ///# use rusted_social_simulation::social::selection::ArgMaxSelector;
+    ///# use rusted_social_simulation::social::simulation::Simulation;
+    ///# use rusted_social_simulation::social::utility::{FixedUtility, Utility};
+    /// let speaker = Role::Character { id: 0 };
+    /// let listener = Role::Character { id: 1 };
+    /// let condition = Box::new(ActorHasRole::new(speaker));
+    /// let utility = Box::new(FixedUtility::new(Utility::from_integer(1)));
+    /// let effect = Box::new(Unbound::new(MockEffect::new(3)));
+    /// let action: Box<dyn Action<ExecutionContext<u32>>> =
+    ///     Box::new(SimpleAction::new("add".to_string(), condition, utility, effect));
+    /// let role_names = hashmap! { speaker => "Speaker".to_string(), listener => "Listener".to_string() };
+    /// let template = SimplePracticeTemplate::new(0, "talk".to_string(), role_names, hashmap! { speaker => vec![action] });
+    /// let practice = SimplePractice::new(0, hashmap! { speaker => 10, listener => 11 }, &template);
+    /// let mut simulation = Simulation::new(42, Box::new(ArgMaxSelector), 0);
+    /// simulation.add_practice(Box::new(practice));
+    ///
+    /// simulation.step();
+    ///
+    /// assert_eq!(*simulation.get_context(), 45);
+    /// assert_eq!(simulation.get_tick(), 1);
+    /// ```
+    pub fn step(&mut self) {
+        self.context.set_tick(self.tick);
+
+        for practice in &mut self.practices {
+            if practice.is_finished() {
+                continue;
+            }
+
+            let participants = practice.get_participants();
+
+            if practice.uses_initiative() {
+                let initiative = get_initiative(practice.as_ref(), &mut self.context);
+                practice.set_initiative(initiative);
+            }
+
+            for entity in practice.get_turn() {
+                if self.controlled.contains(&entity) {
+                    continue;
+                } else if practice.check_turn(entity).is_err() {
+                    // the turn already passed on to others
+                    continue;
+                }
+
+                self.context
+                    .bind(entity, practice.get_id(), participants.clone());
+                let actions = practice.get_actions(entity);
+                let selected = self.selector.select(&actions, &self.context, &mut self.rng);
+
+                if let Some(choices) = &mut self.choices {
+                    let context = &self.context;
+                    let available = actions
+                        .iter()
+                        .filter(|action| action.is_available(context))
+                        .map(|action| action.get_name().to_string())
+                        .collect();
+                    let chosen = selected.map(|action| action.get_name().to_string());
+                    choices.push(Choice::new(
+                        self.tick,
+                        practice.get_id(),
+                        entity,
+                        available,
+                        chosen,
+                    ));
+                }
+
+                if let Some(action) = selected {
+                    let addressed = execute(action, &mut self.context);
+                    practice.end_turn(entity, &addressed);
+                } else {
+                    practice.pass_turn(entity);
+                }
+            }
+
+            self.context.bind_practice(practice.get_id(), participants);
+            practice.update(&self.context);
+        }
+
+        self.context.unbind();
+
+        for effect in &self.tick_effects {
+            effect.apply(self.context.get_context_mut());
+        }
+
+        self.tick += 1;
+    }
+
+    /// Gets the name & utility of each action, that is currently available to an entity.
+    pub fn get_options(&mut self, entity: u32) -> Vec<(String, Utility)> {
+        let mut options = Vec::new();
+
+        for practice in &self.practices {
+            if practice.is_finished() {
+                continue;
+            }
+
+            self.context
+                .bind(entity, practice.get_id(), practice.get_participants());
+            let context = &self.context;
+
+            options.extend(
+                practice
+                    .get_actions(entity)
+                    .into_iter()
+                    .filter(|action| action.is_available(context))
+                    .map(|action| (action.get_name().to_string(), action.get_utility(context))),
+            );
+        }
+
+        self.context.unbind();
+        options
+    }
+
+    /// Lets an entity execute an available action during the current tick, e.g. for a controlled entity.
+    ///
+    /// The first practice with such an action is used. Like during [`Simulation::step`],
+    /// the action is recorded, the turn is passed on & the practice is updated.
+    ///
+    /// ```
+    ///# use maplit::hashmap;
+    ///# use rusted_social_simulation::social::action::{Action, SimpleAction};
+    ///# use rusted_social_simulation::social::condition::MockCondition;
+    ///# use rusted_social_simulation::social::effect::MockEffect;
+    ///# use rusted_social_simulation::social::error::SocialError;
+    ///# use rusted_social_simulation::social::execution::{ExecutionContext, Unbound};
+    ///# use rusted_social_simulation::social::practice::role::Role;
+    ///# use rusted_social_simulation::social::practice::simple::{SimplePractice, SimplePracticeTemplate};
+    ///# use rusted_social_simulation::social::selection::ArgMaxSelector;
+    ///# use rusted_social_simulation::social::simulation::Simulation;
+    ///# use rusted_social_simulation::social::utility::{FixedUtility, Utility};
+    /// let player = Role::Character { id: 0 };
+    /// let condition = Box::new(MockCondition::new(true));
+    /// let utility = Box::new(FixedUtility::new(Utility::from_integer(1)));
+    /// let effect = Box::new(Unbound::new(MockEffect::new(3)));
+    /// let action: Box<dyn Action<ExecutionContext<u32>>> =
+    ///     Box::new(SimpleAction::new("add".to_string(), condition, utility, effect));
+    /// let role_names = hashmap! { player => "Player".to_string() };
+    /// let template = SimplePracticeTemplate::new(0, "play".to_string(), role_names, hashmap! { player => vec![action] });
+    /// let mut simulation = Simulation::new(42, Box::new(ArgMaxSelector), 0);
+    /// simulation.add_practice(Box::new(SimplePractice::new(0, hashmap! { player => 10 }, &template)));
+    /// simulation.control(10);
+    ///
+    /// simulation.step();
+    /// assert_eq!(*simulation.get_context(), 42);
+    /// assert_eq!(simulation.get_options(10), vec![("add".to_string(), Utility::from_integer(1))]);
+    ///
+    /// assert_eq!(simulation.act(10, "add"), Ok(()));
+    /// assert_eq!(*simulation.get_context(), 45);
+    /// assert_eq!(
+    ///     simulation.act(10, "remove"),
+    ///     Err(SocialError::UnavailableAction { entity: 10, action: "remove".to_string() })
+    /// );
+    /// ```
+    pub fn act(&mut self, entity: u32, action: &str) -> Result<(), SocialError> {
+        self.context.set_tick(self.tick);
+
+        for practice in &mut self.practices {
+            if practice.is_finished() {
+                continue;
+            }
+
+            let participants = practice.get_participants();
+            self.context
+                .bind(entity, practice.get_id(), participants.clone());
+            let context = &self.context;
+            let selected = practice
+                .get_actions(entity)
+                .into_iter()
+                .find(|a| a.get_name() == action && a.is_available(context));
+
+            if let Some(selected) = selected {
+                let addressed = execute(selected, &mut self.context);
+                practice.end_turn(entity, &addressed);
+                self.context.bind_practice(practice.get_id(), participants);
+                practice.update(&self.context);
+                self.context.unbind();
+                return Ok(());
+            }
+        }
+
+        self.context.unbind();
+        Err(SocialError::UnavailableAction {
+            entity,
+            action: action.to_string(),
+        })
+    }
+
+    /// Explains the availability & utility of each action of an entity, even if it isn't its turn.
+    pub fn explain(&mut self, entity: u32) -> Vec<Explanation> {
+        let mut explanations = Vec::new();
+
+        for practice in &self.practices {
+            if practice.is_finished() {
+                continue;
+            }
+
+            self.context
+                .bind(entity, practice.get_id(), practice.get_participants());
+            let context = &self.context;
+
+            explanations.extend(
+                practice
+                    .get_potential_actions(entity)
+                    .into_iter()
+                    .map(|action| action.explain(context)),
+            );
+        }
+
+        self.context.unbind();
+        explanations
+    }
+
+    /// Simulates multiple ticks.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::selection::ArgMaxSelector;
+    ///# use rusted_social_simulation::social::simulation::Simulation;
+    /// let mut simulation = Simulation::new(42, Box::new(ArgMaxSelector), 0);
+    ///
+    /// simulation.run(5);
+    ///
+    /// assert_eq!(simulation.get_tick(), 5);
+    /// ```
+    pub fn run(&mut self, ticks: u32) {
+        for _ in 0..ticks {
+            self.step();
+        }
+    }
+
+    fn get_choices(&self) -> &[Choice] {
+        self.choices.as_deref().unwrap_or_default()
+    }
+}
+
+impl<'a, T: PartialEq> Simulation<'a, T> {
+    /// Replays a recording & verifies, that the same actions are available & chosen.
+    ///
+    /// The simulation needs the same seed & initial context (see [`Simulation::from_recording`])
+    /// and the same practices as the recorded one.
+    /// Returns the first divergence.
+    pub fn replay(&mut self, recording: &Recording<T>) -> Result<(), Divergence> {
+        if self.tick != 0 {
+            return Err(Divergence::Started { tick: self.tick });
+        } else if self.seed != recording.get_seed() {
+            return Err(Divergence::Seed {
+                expected: recording.get_seed(),
+                actual: self.seed,
+            });
+        } else if self.context.get_context() != recording.get_initial() {
+            return Err(Divergence::Initial);
+        }
+
+        let expected = recording.get_choices();
+        self.choices = Some(Vec::new());
+
+        for _ in 0..recording.get_ticks() {
+            let start = self.get_choices().len();
+            self.step();
+            compare_choices(expected, self.get_choices(), start)?;
+        }
+
+        let length = self.get_choices().len();
+
+        if length < expected.len() {
+            return Err(Divergence::Choice {
+                index: length,
+                expected: expected.get(length).cloned().map(Box::new),
+                actual: None,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a, T: Clone> Simulation<'a, T> {
+    /// Creates a simulation without practices to replay a recording.
+    pub fn from_recording(
+        recording: &Recording<T>,
+        selector: Box<dyn Selector<ExecutionContext<T>>>,
+    ) -> Simulation<'a, T> {
+        Simulation::new(
+            recording.get_initial().clone(),
+            selector,
+            recording.get_seed(),
+        )
+    }
+
+    /// Starts to record the seed, the initial context & all choices, so the run can be replayed.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::error::SocialError;
+    ///# use rusted_social_simulation::social::selection::ArgMaxSelector;
+    ///# use rusted_social_simulation::social::simulation::Simulation;
+    /// let mut simulation = Simulation::new(42, Box::new(ArgMaxSelector), 7);
+    ///
+    /// assert_eq!(simulation.start_recording(), Ok(()));
+    /// simulation.run(3);
+    ///
+    /// let recording = simulation.get_recording().unwrap();
+    /// assert_eq!(recording.get_seed(), 7);
+    /// assert_eq!(*recording.get_initial(), 42);
+    /// assert_eq!(recording.get_ticks(), 3);
+    /// assert_eq!(simulation.start_recording(), Err(SocialError::AlreadyStarted { tick: 3 }));
+    /// ```
+    pub fn start_recording(&mut self) -> Result<(), SocialError> {
+        if self.tick != 0 {
+            return Err(SocialError::AlreadyStarted { tick: self.tick });
+        }
+
+        self.initial = Some(self.context.get_context().clone());
+        self.choices = Some(Vec::new());
+        Ok(())
+    }
+
+    /// Saves the context, the event log & the state of all practices.
+    pub fn save(&self) -> SimulationSnapshot<T> {
+        let practices = self
+            .practices
+            .iter()
+            .map(|practice| PracticeSnapshot::save(practice.as_ref()))
+            .collect();
+
+        SimulationSnapshot::new(
+            self.seed,
+            self.rng.get_word_pos(),
+            self.tick,
+            self.context.get_context().clone(),
+            self.get_event_log().clone(),
+            practices,
+        )
+    }
+
+    /// Creates a simulation without practices from a snapshot.
+    ///
+    /// The practices need to be restored from [`SimulationSnapshot::get_practices`] & their templates.
+    /// The random number generator continues where it was saved,
+    /// so the random choices are the same as in an uninterrupted run.
+    pub fn load(
+        snapshot: &SimulationSnapshot<T>,
+        selector: Box<dyn Selector<ExecutionContext<T>>>,
+    ) -> Simulation<'a, T> {
+        let context = ExecutionContext::with_events(
+            snapshot.get_context().clone(),
+            snapshot.get_event_log().clone(),
+        );
+        let mut simulation = Simulation::with_context(context, selector, snapshot.get_seed());
+        simulation.rng.set_word_pos(snapshot.get_position());
+        simulation.tick = snapshot.get_tick();
+        simulation
+    }
+
+    /// Gets the recording of all ticks so far, if the recording was started.
+    pub fn get_recording(&self) -> Option<Recording<T>> {
+        let initial = self.initial.as_ref()?;
+        Some(Recording::new(
+            self.seed,
+            initial.clone(),
+            self.tick,
+            self.get_choices().to_vec(),
+        ))
+    }
+}
+
+/// Executes the action of the bound entity & records it.
+/// Returns the entities, that were addressed by it.
+fn execute<T>(
+    action: &dyn Action<ExecutionContext<T>>,
+    context: &mut ExecutionContext<T>,
+) -> Vec<u32> {
+    action.execute(context);
+    context.record(action.get_name());
+    context.take_addressed()
+}
+
+/// Gets the highest utility of the available actions of each entity of a practice.
+fn get_initiative<T>(
+    practice: &dyn Practice<ExecutionContext<T>>,
+    context: &mut ExecutionContext<T>,
+) -> Vec<(u32, Utility)> {
+    let participants = practice.get_participants();
+    let mut initiative = Vec::new();
+
+    for entity in practice.get_entities() {
+        context.bind(entity, practice.get_id(), participants.clone());
+        let utility = practice
+            .get_potential_actions(entity)
+            .into_iter()
+            .filter(|action| action.is_available(context))
+            .map(|action| action.get_utility(context))
+            .max();
+
+        if let Some(utility) = utility {
+            initiative.push((entity, utility));
+        }
+    }
+
+    initiative
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::social::action::{Action, SimpleAction};
+    use crate::social::condition::MockCondition;
+    use crate::social::effect::MockEffect;
+    use crate::social::execution::Unbound;
+    use crate::social::practice::role::Role;
+    use crate::social::practice::simple::{SimplePractice, SimplePracticeTemplate};
+    use crate::social::practice::turn::{
+        AddressedReplies, InitiativeByUtility, RoundRobin, TurnPolicy, TurnTakingPractice,
+    };
+    use crate::social::selection::{ArgMaxSelector, WeightedRandomSelector};
+    use crate::social::utility::FixedUtility;
+
+    const SPEAKER: Role = Role::Character { id: 0 };
+    const LISTENER: Role = Role::Character { id: 1 };
+
+    #[test]
+    fn test_round_robin() {
+        let template = create_template();
+        let mut simulation = create_simulation(
+            &template,
+            Box::new(RoundRobin::new(vec![SPEAKER, LISTENER])),
+        );
+
+        simulation.step();
+        assert_eq!(*simulation.get_context(), 1);
+
+        simulation.step();
+        assert_eq!(*simulation.get_context(), 11);
+
+        let events = simulation.get_event_log().get_events();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1].get_tick(), 1);
+        assert_eq!(events[1].get_actor(), 11);
+        assert_eq!(events[1].get_role(), LISTENER);
+        assert_eq!(events[1].get_action(), "add10");
+    }
+
+    #[test]
+    fn test_round_robin_skips_speaker_without_action() {
+        let template = create_template_with_speaker_condition(false);
+        let mut simulation = create_simulation(
+            &template,
+            Box::new(RoundRobin::new(vec![SPEAKER, LISTENER])),
+        );
+
+        simulation.run(2);
+
+        assert_eq!(*simulation.get_context(), 10);
+        let events = simulation.get_event_log().get_events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].get_tick(), 1);
+        assert_eq!(events[0].get_actor(), 11);
+    }
+
+    #[test]
+    fn test_addressed_replies_skips_speaker_without_action() {
+        let template = create_template_with_speaker_condition(false);
+        let mut simulation = create_simulation(&template, Box::new(AddressedReplies::new(SPEAKER)));
+
+        simulation.run(2);
+
+        assert_eq!(*simulation.get_context(), 10);
+        assert_eq!(simulation.get_event_log().get_events()[0].get_actor(), 11);
+    }
+
+    #[test]
+    fn test_initiative_by_utility() {
+        let template = create_template();
+        let mut simulation = create_simulation(&template, Box::new(InitiativeByUtility));
+
+        simulation.run(2);
+
+        assert_eq!(*simulation.get_context(), 20);
+    }
+
+    #[test]
+    fn test_replay() {
+        let template = create_random_template();
+        let mut recorded = create_random_simulation(&template, 3);
+        recorded.start_recording().unwrap();
+        recorded.run(10);
+        let recording = recorded.get_recording().unwrap();
+
+        let mut replayed = Simulation::from_recording(&recording, Box::new(WeightedRandomSelector));
+        add_practice(&mut replayed, &template);
+
+        assert_eq!(recording.get_choices().len(), 20);
+        assert_eq!(replayed.replay(&recording), Ok(()));
+        assert_eq!(replayed.get_context(), recorded.get_context());
+    }
+
+    #[test]
+    fn test_replay_with_other_seed() {
+        let template = create_random_template();
+        let mut recorded = create_random_simulation(&template, 3);
+        recorded.start_recording().unwrap();
+        recorded.run(10);
+        let recording = recorded.get_recording().unwrap();
+
+        let mut replayed = create_random_simulation(&template, 4);
+
+        assert_eq!(
+            replayed.replay(&recording),
+            Err(Divergence::Seed {
+                expected: 3,
+                actual: 4
+            })
+        );
+    }
+
+    #[test]
+    fn test_replay_with_other_initial_context() {
+        let template = create_random_template();
+        let mut recorded = create_random_simulation(&template, 3);
+        recorded.start_recording().unwrap();
+        recorded.run(10);
+        let recording = recorded.get_recording().unwrap();
+
+        let mut replayed = Simulation::new(1, Box::new(WeightedRandomSelector), 3);
+        add_practice(&mut replayed, &template);
+
+        assert_eq!(replayed.replay(&recording), Err(Divergence::Initial));
+    }
+
+    #[test]
+    fn test_replay_with_other_template() {
+        let template = create_random_template();
+        let mut recorded = create_random_simulation(&template, 3);
+        recorded.start_recording().unwrap();
+        recorded.run(2);
+        let recording = recorded.get_recording().unwrap();
+
+        let other_template = create_other_template();
+        let mut replayed = create_random_simulation(&other_template, 3);
+
+        match replayed.replay(&recording) {
+            Err(Divergence::Choice {
+                index,
+                expected: Some(expected),
+                actual: Some(actual),
+            }) => {
+                assert_eq!(index, 0);
+                assert_eq!(expected.get_available(), ["add1", "add2"]);
+                assert_eq!(actual.get_available(), ["add3"]);
+            }
+            result => panic!("Unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_replay_after_start() {
+        let template = create_random_template();
+        let mut recorded = create_random_simulation(&template, 3);
+        recorded.start_recording().unwrap();
+        recorded.run(2);
+        let recording = recorded.get_recording().unwrap();
+
+        assert_eq!(
+            recorded.replay(&recording),
+            Err(Divergence::Started { tick: 2 })
+        );
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let template = create_template();
+        let mut original = create_simulation(
+            &template,
+            Box::new(RoundRobin::new(vec![SPEAKER, LISTENER])),
+        );
+        original.run(3);
+
+        let text = ron::to_string(&original.save()).unwrap();
+        let snapshot: SimulationSnapshot<u32> = ron::from_str(&text).unwrap();
+        let mut loaded = Simulation::load(&snapshot, Box::new(ArgMaxSelector));
+
+        for snapshot in snapshot.get_practices() {
+            let practice = SimplePractice::try_restore(snapshot, &template).unwrap();
+            let practice = TurnTakingPractice::try_restore(snapshot, Box::new(practice)).unwrap();
+            loaded.add_practice(Box::new(practice));
+        }
+
+        assert_eq!(loaded.get_tick(), 3);
+        assert_eq!(loaded.get_context(), original.get_context());
+        assert_eq!(loaded.get_event_log(), original.get_event_log());
+        assert_eq!(loaded.save(), original.save());
+
+        original.step();
+        loaded.step();
+
+        assert_eq!(*loaded.get_context(), 22);
+        assert_eq!(loaded.get_context(), original.get_context());
+        assert_eq!(loaded.get_event_log(), original.get_event_log());
+    }
+
+    #[test]
+    fn test_save_and_load_random_choices() {
+        let template = create_random_template();
+        let mut uninterrupted = create_random_simulation(&template, 5);
+        uninterrupted.run(10);
+
+        let mut original = create_random_simulation(&template, 5);
+        original.run(4);
+        let text = ron::to_string(&original.save()).unwrap();
+        let snapshot: SimulationSnapshot<u32> = ron::from_str(&text).unwrap();
+        let mut loaded = Simulation::load(&snapshot, Box::new(WeightedRandomSelector));
+
+        for snapshot in snapshot.get_practices() {
+            let practice = SimplePractice::try_restore(snapshot, &template).unwrap();
+            loaded.add_practice(Box::new(practice));
+        }
+
+        loaded.run(6);
+
+        assert_eq!(loaded.get_context(), uninterrupted.get_context());
+        assert_eq!(loaded.get_event_log(), uninterrupted.get_event_log());
+    }
+
+    #[test]
+    fn test_load_without_turn_policy() {
+        let template = create_template();
+        let mut original = Simulation::new(0, Box::new(ArgMaxSelector), 0);
+        add_practice(&mut original, &template);
+        let snapshot = original.save();
+        let snapshot = &snapshot.get_practices()[0];
+        let practice = SimplePractice::try_restore(snapshot, &template).unwrap();
+
+        assert_eq!(
+            TurnTakingPractice::try_restore(snapshot, Box::new(practice)).err(),
+            Some(SocialError::MissingTurnPolicy { practice: 0 })
+        );
+    }
+
+    #[test]
+    fn test_load_with_other_template() {
+        let template = create_template();
+        let original = create_simulation(&template, Box::new(InitiativeByUtility));
+        let snapshot = original.save();
+        let other_template = create_template_with_id(3);
+
+        assert_eq!(
+            SimplePractice::try_restore(&snapshot.get_practices()[0], &other_template).err(),
+            Some(SocialError::TemplateMismatch {
+                expected: 0,
+                actual: 3
+            })
+        );
+    }
+
+    fn create_random_simulation<'a>(
+        template: &'a SimplePracticeTemplate<ExecutionContext<u32>>,
+        seed: u64,
+    ) -> Simulation<'a, u32> {
+        let mut simulation = Simulation::new(0, Box::new(WeightedRandomSelector), seed);
+        add_practice(&mut simulation, template);
+        simulation
+    }
+
+    fn add_practice<'a>(
+        simulation: &mut Simulation<'a, u32>,
+        template: &'a SimplePracticeTemplate<ExecutionContext<u32>>,
+    ) {
+        let practice = SimplePractice::new(0, hashmap! { SPEAKER => 10, LISTENER => 11 }, template);
+        simulation.add_practice(Box::new(practice));
+    }
+
+    fn create_random_template() -> SimplePracticeTemplate<ExecutionContext<u32>> {
+        let role_names = hashmap! {
+            SPEAKER => "Speaker".to_string(),
+            LISTENER => "Listener".to_string(),
+        };
+        let actions = hashmap! {
+            SPEAKER => vec![create_action(1, 1), create_action(3, 2)],
+            LISTENER => vec![create_action(5, 10), create_action(5, 20)],
+        };
+
+        SimplePracticeTemplate::new(0, "talk".to_string(), role_names, actions)
+    }
+
+    fn create_other_template() -> SimplePracticeTemplate<ExecutionContext<u32>> {
+        let role_names = hashmap! {
+            SPEAKER => "Speaker".to_string(),
+            LISTENER => "Listener".to_string(),
+        };
+        let actions = hashmap! {
+            SPEAKER => vec![create_action(1, 3)],
+            LISTENER => vec![create_action(5, 10)],
+        };
+
+        SimplePracticeTemplate::new(0, "talk".to_string(), role_names, actions)
+    }
+
+    fn create_simulation<'a>(
+        template: &'a SimplePracticeTemplate<ExecutionContext<u32>>,
+        policy: Box<dyn TurnPolicy>,
+    ) -> Simulation<'a, u32> {
+        let practice = SimplePractice::new(0, hashmap! { SPEAKER => 10, LISTENER => 11 }, template);
+        let mut simulation = Simulation::new(0, Box::new(ArgMaxSelector), 0);
+        simulation.add_practice(Box::new(TurnTakingPractice::new(
+            Box::new(practice),
+            policy,
+        )));
+        simulation
+    }
+
+    fn create_template() -> SimplePracticeTemplate<ExecutionContext<u32>> {
+        create_template_with_id(0)
+    }
+
+    fn create_template_with_id(id: u32) -> SimplePracticeTemplate<ExecutionContext<u32>> {
+        let role_names = hashmap! {
+            SPEAKER => "Speaker".to_string(),
+            LISTENER => "Listener".to_string(),
+        };
+        let actions = hashmap! {
+            SPEAKER => vec![create_action(1, 1)],
+            LISTENER => vec![create_action(5, 10)],
+        };
+
+        SimplePracticeTemplate::new(id, "talk".to_string(), role_names, actions)
+    }
+
+    fn create_template_with_speaker_condition(
+        is_available: bool,
+    ) -> SimplePracticeTemplate<ExecutionContext<u32>> {
+        let role_names = hashmap! {
+            SPEAKER => "Speaker".to_string(),
+            LISTENER => "Listener".to_string(),
+        };
+        let actions = hashmap! {
+            SPEAKER => vec![create_conditional_action(is_available, 1, 1)],
+            LISTENER => vec![create_action(5, 10)],
+        };
+
+        SimplePracticeTemplate::new(0, "talk".to_string(), role_names, actions)
+    }
+
+    fn create_action(utility: i32, value: u32) -> Box<dyn Action<ExecutionContext<u32>>> {
+        create_conditional_action(true, utility, value)
+    }
+
+    fn create_conditional_action(
+        is_available: bool,
+        utility: i32,
+        value: u32,
+    ) -> Box<dyn Action<ExecutionContext<u32>>> {
+        Box::new(SimpleAction::new(
+            format!("add{}", value),
+            Box::new(MockCondition::new(is_available)),
+            Box::new(FixedUtility::new(Utility::from_integer(utility))),
+            Box::new(Unbound::new(MockEffect::new(value))),
+        ))
+    }
+}
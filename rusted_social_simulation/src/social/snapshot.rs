@@ -0,0 +1,223 @@
+use crate::social::error::SocialError;
+use crate::social::event::EventLog;
+use crate::social::practice::participants::Participants;
+use crate::social::practice::role::Role;
+use crate::social::practice::turn::{
+    AddressedReplies, FreeForAll, InitiativeByUtility, RoundRobin, TurnPolicy,
+};
+use crate::social::practice::{Practice, PracticeTemplate};
+use serde::{Deserialize, Serialize};
+
+/// A built-in turn policy with its state, that can be saved & restored.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum TurnPolicySnapshot {
+    RoundRobin(RoundRobin),
+    AddressedReplies(AddressedReplies),
+    FreeForAll(FreeForAll),
+    InitiativeByUtility,
+}
+
+impl TurnPolicySnapshot {
+    /// Creates the turn policy with the saved state.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::practice::role::Role;
+    ///# use rusted_social_simulation::social::practice::turn::{RoundRobin, TurnPolicy};
+    /// let speaker = Role::Character { id: 0 };
+    /// let listener = Role::Character { id: 1 };
+    /// let participants = vec![(speaker, 10), (listener, 11)].into_iter().collect();
+    /// let mut policy = RoundRobin::new(vec![speaker, listener]);
+    /// policy.end_turn(10, &[], &participants);
+    ///
+    /// let restored = policy.save().unwrap().restore();
+    ///
+    /// assert_eq!(restored.get_current(&participants, &[]), vec![11]);
+    /// ```
+    pub fn restore(&self) -> Box<dyn TurnPolicy> {
+        match self {
+            TurnPolicySnapshot::RoundRobin(policy) => Box::new(policy.clone()),
+            TurnPolicySnapshot::AddressedReplies(policy) => Box::new(policy.clone()),
+            TurnPolicySnapshot::FreeForAll(policy) => Box::new(policy.clone()),
+            TurnPolicySnapshot::InitiativeByUtility => Box::new(InitiativeByUtility),
+        }
+    }
+}
+
+/// The state of a practice, that can be saved & restored.
+///
+/// The template isn't part of it & is only referenced by its id.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PracticeSnapshot {
+    id: u32,
+    template: u32,
+    participants: Vec<(Role, u32)>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    stage: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    turn_policy: Option<TurnPolicySnapshot>,
+}
+
+impl PracticeSnapshot {
+    pub fn new(
+        id: u32,
+        template: u32,
+        participants: Participants,
+        stage: Option<String>,
+    ) -> PracticeSnapshot {
+        PracticeSnapshot {
+            id,
+            template,
+            participants: participants.get_pairs(),
+            stage,
+            turn_policy: None,
+        }
+    }
+
+    /// Saves the state of a practice.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::practice::role::Role;
+    ///# use rusted_social_simulation::social::practice::staged::{create_test_staged_template, create_test_staged_practice};
+    ///# use rusted_social_simulation::social::snapshot::PracticeSnapshot;
+    /// let template = create_test_staged_template();
+    /// let practice = create_test_staged_practice(&template);
+    ///
+    /// let snapshot = PracticeSnapshot::save(&practice);
+    ///
+    /// assert_eq!(snapshot.get_id(), 5);
+    /// assert_eq!(snapshot.get_template(), 7);
+    /// assert_eq!(snapshot.get_participants().get_entity(Role::Character { id: 1 }), Some(11));
+    /// assert_eq!(snapshot.get_stage(), Some("greeting"));
+    /// ```
+    pub fn save<T>(practice: &dyn Practice<T>) -> PracticeSnapshot {
+        let mut snapshot = PracticeSnapshot::new(
+            practice.get_id(),
+            practice.get_template().get_id(),
+            practice.get_participants(),
+            practice.get_stage().map(|stage| stage.to_string()),
+        );
+        snapshot.turn_policy = practice.save_turn_policy();
+        snapshot
+    }
+
+    /// Gets the id of the practice.
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    /// Gets the id of the practice's template.
+    pub fn get_template(&self) -> u32 {
+        self.template
+    }
+
+    /// Gets which entity has which role in the practice.
+    pub fn get_participants(&self) -> Participants {
+        self.participants.iter().copied().collect()
+    }
+
+    /// Gets the name of the current stage, if the practice has stages.
+    pub fn get_stage(&self) -> Option<&str> {
+        self.stage.as_deref()
+    }
+
+    /// Gets the turn policy & its state, if the practice has a serializable one.
+    pub fn get_turn_policy(&self) -> Option<&TurnPolicySnapshot> {
+        self.turn_policy.as_ref()
+    }
+
+    /// Checks, if the template is the one referenced by the snapshot.
+    pub fn check_template<T>(&self, template: &dyn PracticeTemplate<T>) -> Result<(), SocialError> {
+        if template.get_id() == self.template {
+            Ok(())
+        } else {
+            Err(SocialError::TemplateMismatch {
+                expected: self.template,
+                actual: template.get_id(),
+            })
+        }
+    }
+}
+
+/// Finds the template a snapshot refers to by its id.
+///
+/// ```
+///# use rusted_social_simulation::social::error::SocialError;
+///# use rusted_social_simulation::social::practice::simple::create_test_template;
+///# use rusted_social_simulation::social::practice::PracticeTemplate;
+///# use rusted_social_simulation::social::snapshot::find_template;
+/// let templates = vec![create_test_template()];
+///
+/// assert_eq!(find_template(&templates, 42).map(|t| t.get_name()), Ok("template0"));
+/// assert_eq!(find_template(&templates, 3).err(), Some(SocialError::MissingTemplate { id: 3 }));
+/// ```
+pub fn find_template<T, P: PracticeTemplate<T>>(
+    templates: &[P],
+    id: u32,
+) -> Result<&P, SocialError> {
+    templates
+        .iter()
+        .find(|template| template.get_id() == id)
+        .ok_or(SocialError::MissingTemplate { id })
+}
+
+/// The state of a simulation, that can be saved & restored.
+///
+/// The practices are saved as [`PracticeSnapshot`]s, that need to be restored with their templates.
+/// The random number generator is saved as its seed & its position in the random stream.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SimulationSnapshot<T> {
+    seed: u64,
+    position: u128,
+    tick: u32,
+    context: T,
+    events: EventLog,
+    practices: Vec<PracticeSnapshot>,
+}
+
+impl<T> SimulationSnapshot<T> {
+    pub fn new(
+        seed: u64,
+        position: u128,
+        tick: u32,
+        context: T,
+        events: EventLog,
+        practices: Vec<PracticeSnapshot>,
+    ) -> SimulationSnapshot<T> {
+        SimulationSnapshot {
+            seed,
+            position,
+            tick,
+            context,
+            events,
+            practices,
+        }
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Gets how many random words were used since the random number generator was seeded.
+    pub fn get_position(&self) -> u128 {
+        self.position
+    }
+
+    /// Gets the number of ticks that were simulated before the snapshot.
+    pub fn get_tick(&self) -> u32 {
+        self.tick
+    }
+
+    pub fn get_context(&self) -> &T {
+        &self.context
+    }
+
+    /// Gets the log of all executed actions.
+    pub fn get_event_log(&self) -> &EventLog {
+        &self.events
+    }
+
+    /// Gets the snapshots of all active practices.
+    pub fn get_practices(&self) -> &[PracticeSnapshot] {
+        &self.practices
+    }
+}
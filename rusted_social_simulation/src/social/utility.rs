@@ -1,12 +1,296 @@
 use crate::social::condition::Condition;
+use crate::social::explanation::{get_type_name, Explanation, Outcome};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
 
 /// The utility of something determines how useful it us under the current context.
 /// A higher value is better.
-pub type Utility = i32;
+///
+/// By default it is a fixed-point number with 2 decimal places.
+/// The feature *float_utility* switches to a floating-point number.
+/// Both have the same interface & scale, so the feature only changes the precision.
+///
+/// ```
+///# use rusted_social_simulation::social::utility::Utility;
+/// let half = Utility::from_normalized(0.5);
+///
+/// assert_eq!(Utility::from_integer(3).saturating_mul(half), Utility::from_f32(1.5));
+/// assert_eq!(Utility::from_integer(3).divide(Utility::from_integer(2)), Utility::from_f32(1.5));
+/// assert!(Utility::from_integer(1) > half);
+/// assert_eq!(Utility::from_integer(2).to_string(), "2");
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Utility(Raw);
+
+#[cfg(not(feature = "float_utility"))]
+type Raw = i32;
+
+#[cfg(feature = "float_utility")]
+type Raw = f32;
+
+impl Utility {
+    /// The utility of a fully satisfied consideration. Normalized utilities are between 0 & it.
+    pub const ONE: Utility = Utility(Raw::ONE);
+
+    pub const MIN: Utility = Utility(Raw::MIN);
+
+    pub const MAX: Utility = Utility(Raw::MAX);
+
+    /// Converts a float & rounds it to the precision of the backend.
+    pub fn from_f32(value: f32) -> Utility {
+        Utility::new(Raw::from_f32(value))
+    }
+
+    /// Converts an integer. The result saturates instead of overflowing.
+    pub fn from_integer(value: i32) -> Utility {
+        Utility::new(Raw::from_integer(value))
+    }
+
+    pub fn to_f32(self) -> f32 {
+        self.0.to_f32()
+    }
+
+    /// Converts a value between 0 & 1 to an utility based on [`Utility::ONE`].
+    pub fn from_normalized(value: f32) -> Utility {
+        Utility::from_f32(value)
+    }
+
+    /// Converts to a value between 0 & 1 based on [`Utility::ONE`].
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::utility::Utility;
+    /// assert_eq!(Utility::ONE.to_normalized(), 1.0);
+    /// assert_eq!(Utility::from_integer(3).to_normalized(), 1.0);
+    /// assert_eq!(Utility::default().to_normalized(), 0.0);
+    /// ```
+    pub fn to_normalized(self) -> f32 {
+        self.to_f32().clamp(0.0, 1.0)
+    }
+
+    /// Adds & stays within the finite range of the backend.
+    pub fn saturating_add(self, other: Utility) -> Utility {
+        Utility::new(Backend::saturating_add(self.0, other.0))
+    }
+
+    /// Subtracts & stays within the finite range of the backend.
+    pub fn saturating_sub(self, other: Utility) -> Utility {
+        Utility::new(Backend::saturating_sub(self.0, other.0))
+    }
+
+    /// Multiplies & stays within the finite range of the backend.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::utility::Utility;
+    /// assert_eq!(Utility::MAX.saturating_mul(Utility::from_integer(2)), Utility::MAX);
+    /// assert_eq!(Utility::MAX.saturating_mul(Utility::from_integer(-2)), Utility::MIN);
+    /// ```
+    pub fn saturating_mul(self, other: Utility) -> Utility {
+        Utility::new(Backend::saturating_mul(self.0, other.0))
+    }
+
+    /// Divides & stays within the finite range of the backend. A division by 0 returns 0.
+    pub fn divide(self, other: Utility) -> Utility {
+        Utility::new(Backend::divide(self.0, other.0))
+    }
+
+    fn new(raw: Raw) -> Utility {
+        Utility(raw.canonicalize())
+    }
+}
+
+impl PartialEq for Utility {
+    fn eq(&self, other: &Utility) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Utility {}
+
+impl PartialOrd for Utility {
+    fn partial_cmp(&self, other: &Utility) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Utility {
+    fn cmp(&self, other: &Utility) -> Ordering {
+        self.0.compare(&other.0)
+    }
+}
+
+impl Hash for Utility {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Backend::to_bits(self.0).hash(state);
+    }
+}
+
+impl fmt::Display for Utility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_f32())
+    }
+}
+
+impl Serialize for Utility {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f32(self.to_f32())
+    }
+}
+
+impl<'de> Deserialize<'de> for Utility {
+    /// Accepts integers & floats, e.g. `10` or `0.5`.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Utility, D::Error> {
+        f32::deserialize(deserializer).map(Utility::from_f32)
+    }
+}
+
+/// The operations the backends of [`Utility`] need to support.
+trait Backend: Copy {
+    const ONE: Self;
+
+    fn from_f32(value: f32) -> Self;
+
+    fn from_integer(value: i32) -> Self;
+
+    fn to_f32(self) -> f32;
+
+    fn to_bits(self) -> u32;
+
+    /// Maps equal values to the same representation, e.g. -0.0 to 0.0.
+    fn canonicalize(self) -> Self;
+
+    /// Compares 2 values with a total order.
+    fn compare(&self, other: &Self) -> Ordering;
+
+    fn saturating_add(self, other: Self) -> Self;
+
+    fn saturating_sub(self, other: Self) -> Self;
+
+    fn saturating_mul(self, other: Self) -> Self;
+
+    fn divide(self, other: Self) -> Self;
+}
+
+/// A fixed-point number, where 100 is 1.
+impl Backend for i32 {
+    const ONE: i32 = 100;
+
+    fn from_f32(value: f32) -> i32 {
+        (value * Self::ONE as f32).round() as i32
+    }
+
+    fn from_integer(value: i32) -> i32 {
+        value.saturating_mul(Self::ONE)
+    }
+
+    fn to_f32(self) -> f32 {
+        self as f32 / Self::ONE as f32
+    }
+
+    fn to_bits(self) -> u32 {
+        self as u32
+    }
+
+    fn canonicalize(self) -> i32 {
+        self
+    }
+
+    fn compare(&self, other: &i32) -> Ordering {
+        self.cmp(other)
+    }
+
+    fn saturating_add(self, other: i32) -> i32 {
+        i32::saturating_add(self, other)
+    }
+
+    fn saturating_sub(self, other: i32) -> i32 {
+        i32::saturating_sub(self, other)
+    }
+
+    fn saturating_mul(self, other: i32) -> i32 {
+        to_i32(self as i64 * other as i64 / Self::ONE as i64)
+    }
+
+    fn divide(self, other: i32) -> i32 {
+        if other == 0 {
+            0
+        } else {
+            to_i32(self as i64 * Self::ONE as i64 / other as i64)
+        }
+    }
+}
+
+fn to_i32(value: i64) -> i32 {
+    value.clamp(i32::MIN as i64, i32::MAX as i64) as i32
+}
+
+impl Backend for f32 {
+    const ONE: f32 = 1.0;
+
+    fn from_f32(value: f32) -> f32 {
+        value.clamp(f32::MIN, f32::MAX)
+    }
+
+    fn from_integer(value: i32) -> f32 {
+        value as f32
+    }
+
+    fn to_f32(self) -> f32 {
+        self
+    }
+
+    fn to_bits(self) -> u32 {
+        f32::to_bits(self)
+    }
+
+    fn canonicalize(self) -> f32 {
+        if self == 0.0 || self.is_nan() {
+            0.0
+        } else {
+            self
+        }
+    }
+
+    fn compare(&self, other: &f32) -> Ordering {
+        self.total_cmp(other)
+    }
+
+    fn saturating_add(self, other: f32) -> f32 {
+        (self + other).clamp(f32::MIN, f32::MAX)
+    }
+
+    fn saturating_sub(self, other: f32) -> f32 {
+        (self - other).clamp(f32::MIN, f32::MAX)
+    }
+
+    fn saturating_mul(self, other: f32) -> f32 {
+        (self * other).clamp(f32::MIN, f32::MAX)
+    }
+
+    fn divide(self, other: f32) -> f32 {
+        if other == 0.0 {
+            0.0
+        } else {
+            (self / other).clamp(f32::MIN, f32::MAX)
+        }
+    }
+}
 
 /// A utility rule can be used to calculate the utility of something (e.g. an action) for a given context.
-pub trait UtilityRule<T> {
+pub trait UtilityRule<T>: Send + Sync {
     fn calculate_utility(&self, context: &T) -> Utility;
+
+    /// Explains the contribution of the rule & its sub-rules to the final utility.
+    ///
+    /// Rules without sub-rules can rely on the default, which uses the name of the type.
+    fn explain(&self, context: &T) -> Explanation {
+        Explanation::utility(
+            get_type_name::<Self>(),
+            self.calculate_utility(context),
+            Vec::new(),
+        )
+    }
 }
 
 /// An utility rule that has a fixed utility.
@@ -24,12 +308,41 @@ impl<T> UtilityRule<T> for FixedUtility {
     /// Always returns a fixed utility.
     ///
     /// ```
-    ///# use rusted_social_simulation::social::utility::{FixedUtility, UtilityRule};
-    /// assert_eq!(FixedUtility::new(9).calculate_utility(&42), 9);
+    ///# use rusted_social_simulation::social::utility::{FixedUtility, Utility, UtilityRule};
+    /// assert_eq!(FixedUtility::new(Utility::from_integer(9)).calculate_utility(&42), Utility::from_integer(9));
     /// ```
     fn calculate_utility(&self, _: &T) -> Utility {
         self.utility
     }
+
+    fn explain(&self, context: &T) -> Explanation {
+        Explanation::utility("fixed", self.calculate_utility(context), Vec::new())
+    }
+}
+
+/// An utility rule that calculates the utility with a closure, e.g. for prototyping.
+pub struct FnUtility<F> {
+    function: F,
+}
+
+impl<F> FnUtility<F> {
+    pub fn new(function: F) -> FnUtility<F> {
+        FnUtility { function }
+    }
+}
+
+impl<T, F: Fn(&T) -> Utility + Send + Sync> UtilityRule<T> for FnUtility<F> {
+    /// Returns the result of the closure.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::utility::{FnUtility, Utility, UtilityRule};
+    /// let half = FnUtility::new(|context: &i32| Utility::from_integer(context / 2));
+    ///
+    /// assert_eq!(half.calculate_utility(&42), Utility::from_integer(21));
+    /// ```
+    fn calculate_utility(&self, context: &T) -> Utility {
+        (self.function)(context)
+    }
 }
 
 /// An utility rule with an utility based on a condition.
@@ -49,20 +362,39 @@ impl<T> UtilityRule<T> for ConditionalUtility<T> {
     ///
     /// ```
     ///# use rusted_social_simulation::social::condition::*;
-    ///# use rusted_social_simulation::social::utility::{ConditionalUtility, UtilityRule};
-    /// let with_false = ConditionalUtility::new(Box::new(MockCondition::new(false)), 35);
-    /// let with_true = ConditionalUtility::new(Box::new(MockCondition::new(true)), 78);
+    ///# use rusted_social_simulation::social::utility::{ConditionalUtility, Utility, UtilityRule};
+    /// let with_false = ConditionalUtility::new(Box::new(MockCondition::new(false)), Utility::from_integer(35));
+    /// let with_true = ConditionalUtility::new(Box::new(MockCondition::new(true)), Utility::from_integer(78));
     ///
-    /// assert_eq!(with_false.calculate_utility(&42), 0);
-    /// assert_eq!(with_true.calculate_utility(&42), 78);
+    /// assert_eq!(with_false.calculate_utility(&42), Utility::default());
+    /// assert_eq!(with_true.calculate_utility(&42), Utility::from_integer(78));
     /// ```
     fn calculate_utility(&self, context: &T) -> Utility {
         if self.condition.evaluate(context) {
             self.utility
         } else {
-            0
+            Utility::default()
         }
     }
+
+    /// Explains the condition & the resulting utility.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::condition::*;
+    ///# use rusted_social_simulation::social::utility::{ConditionalUtility, Utility, UtilityRule};
+    /// let rule = ConditionalUtility::new(Box::new(MockCondition::new(false)), Utility::from_integer(35));
+    ///
+    /// assert_eq!(rule.explain(&42).to_text(), "conditional = 0\n  MockCondition = false\n");
+    /// ```
+    fn explain(&self, context: &T) -> Explanation {
+        let condition = self.condition.explain(context);
+        let utility = if condition.get_outcome() == Outcome::Condition(true) {
+            self.utility
+        } else {
+            Utility::default()
+        };
+        Explanation::utility("conditional", utility, vec![condition])
+    }
 }
 
 /// The sum of multiple utility rules.
@@ -81,16 +413,39 @@ impl<T> UtilityRule<T> for TotalUtility<T> {
     ///
     /// ```
     ///# use rusted_social_simulation::social::condition::*;
-    ///# use rusted_social_simulation::social::utility::{UtilityRule, TotalUtility, FixedUtility};
-    /// let sum = TotalUtility::new(vec![Box::new(FixedUtility::new(9)), Box::new(FixedUtility::new(5))]);
+    ///# use rusted_social_simulation::social::utility::{FixedUtility, TotalUtility, Utility, UtilityRule};
+    /// let sum = TotalUtility::new(vec![
+    ///     Box::new(FixedUtility::new(Utility::from_integer(9))),
+    ///     Box::new(FixedUtility::new(Utility::from_integer(5))),
+    /// ]);
     ///
-    /// assert_eq!(sum.calculate_utility(&42), 14);
+    /// assert_eq!(sum.calculate_utility(&42), Utility::from_integer(14));
     /// ```
     fn calculate_utility(&self, context: &T) -> Utility {
         self.rules
             .iter()
             .map(|r| r.calculate_utility(context))
-            .sum()
+            .fold(Utility::default(), Utility::saturating_add)
+    }
+
+    /// Explains the contribution of each rule to the sum.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::utility::{FixedUtility, TotalUtility, Utility, UtilityRule};
+    /// let sum = TotalUtility::new(vec![
+    ///     Box::new(FixedUtility::new(Utility::from_integer(9))),
+    ///     Box::new(FixedUtility::new(Utility::from_integer(5))),
+    /// ]);
+    ///
+    /// assert_eq!(sum.explain(&42).to_text(), "total = 14\n  fixed = 9\n  fixed = 5\n");
+    /// ```
+    fn explain(&self, context: &T) -> Explanation {
+        let children = explain_all(&self.rules, context);
+        let utility = children
+            .iter()
+            .map(get_utility)
+            .fold(Utility::default(), Utility::saturating_add);
+        Explanation::utility("total", utility, children)
     }
 }
 
@@ -110,16 +465,261 @@ impl<T> UtilityRule<T> for MaxUtility<T> {
     ///
     /// ```
     ///# use rusted_social_simulation::social::condition::*;
-    ///# use rusted_social_simulation::social::utility::{UtilityRule, MaxUtility, FixedUtility};
-    /// let sum = MaxUtility::new(vec![Box::new(FixedUtility::new(9)), Box::new(FixedUtility::new(5))]);
+    ///# use rusted_social_simulation::social::utility::{FixedUtility, MaxUtility, Utility, UtilityRule};
+    /// let sum = MaxUtility::new(vec![
+    ///     Box::new(FixedUtility::new(Utility::from_integer(9))),
+    ///     Box::new(FixedUtility::new(Utility::from_integer(5))),
+    /// ]);
     ///
-    /// assert_eq!(sum.calculate_utility(&42), 9);
+    /// assert_eq!(sum.calculate_utility(&42), Utility::from_integer(9));
     /// ```
     fn calculate_utility(&self, context: &T) -> Utility {
         self.rules
             .iter()
             .map(|r| r.calculate_utility(context))
             .max()
-            .unwrap_or(0)
+            .unwrap_or_default()
+    }
+
+    fn explain(&self, context: &T) -> Explanation {
+        let children = explain_all(&self.rules, context);
+        let utility = children.iter().map(get_utility).max().unwrap_or_default();
+        Explanation::utility("max", utility, children)
+    }
+}
+
+/// The minimum of multiple utility rules, e.g. to veto an action with a single bad consideration.
+pub struct MinUtility<T> {
+    rules: Vec<Box<dyn UtilityRule<T>>>,
+}
+
+impl<T> MinUtility<T> {
+    pub fn new(rules: Vec<Box<dyn UtilityRule<T>>>) -> MinUtility<T> {
+        MinUtility { rules }
+    }
+}
+
+impl<T> UtilityRule<T> for MinUtility<T> {
+    /// Returns the minimum of multiple utility rules.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::utility::{FixedUtility, MinUtility, Utility, UtilityRule};
+    /// let min = MinUtility::new(vec![
+    ///     Box::new(FixedUtility::new(Utility::from_integer(9))),
+    ///     Box::new(FixedUtility::new(Utility::from_integer(5))),
+    /// ]);
+    ///
+    /// assert_eq!(min.calculate_utility(&42), Utility::from_integer(5));
+    /// ```
+    fn calculate_utility(&self, context: &T) -> Utility {
+        self.rules
+            .iter()
+            .map(|r| r.calculate_utility(context))
+            .min()
+            .unwrap_or_default()
+    }
+
+    fn explain(&self, context: &T) -> Explanation {
+        let children = explain_all(&self.rules, context);
+        let utility = children.iter().map(get_utility).min().unwrap_or_default();
+        Explanation::utility("min", utility, children)
+    }
+}
+
+/// The product of multiple considerations, as used by the Infinite Axis Utility System.
+///
+/// Each consideration is normalized to a score between 0 & 1 based on [`Utility::ONE`].
+/// The product is converted back, so it is [`Utility::ONE`], if all considerations are fully satisfied.
+///
+/// Because each additional consideration can only lower the product,
+/// an optional compensation factor raises each score depending on the number of considerations.
+pub struct ProductUtility<T> {
+    rules: Vec<Box<dyn UtilityRule<T>>>,
+    compensation: bool,
+}
+
+impl<T> ProductUtility<T> {
+    pub fn new(rules: Vec<Box<dyn UtilityRule<T>>>) -> ProductUtility<T> {
+        ProductUtility {
+            rules,
+            compensation: false,
+        }
+    }
+
+    /// Compensates for the number of considerations.
+    pub fn set_compensation(&mut self, compensation: bool) {
+        self.compensation = compensation;
+    }
+
+    fn multiply(&self, utilities: &[Utility]) -> Utility {
+        if utilities.is_empty() {
+            return Utility::default();
+        }
+
+        let modification = 1.0 - 1.0 / utilities.len() as f32;
+
+        let product: f32 = utilities
+            .iter()
+            .map(|utility| {
+                let score = utility.to_normalized();
+
+                if self.compensation {
+                    score + (1.0 - score) * modification * score
+                } else {
+                    score
+                }
+            })
+            .product();
+
+        Utility::from_normalized(product)
+    }
+}
+
+impl<T> UtilityRule<T> for ProductUtility<T> {
+    /// Returns the product of the normalized utility rules.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::utility::{FixedUtility, ProductUtility, Utility, UtilityRule};
+    /// let half = Utility::from_normalized(0.5);
+    /// let mut product = ProductUtility::new(vec![Box::new(FixedUtility::new(half)), Box::new(FixedUtility::new(half))]);
+    ///
+    /// assert_eq!(product.calculate_utility(&42), Utility::from_normalized(0.25));
+    ///
+    /// product.set_compensation(true);
+    ///
+    /// assert_eq!(product.calculate_utility(&42), Utility::from_normalized(0.390625));
+    /// ```
+    fn calculate_utility(&self, context: &T) -> Utility {
+        let utilities: Vec<Utility> = self
+            .rules
+            .iter()
+            .map(|r| r.calculate_utility(context))
+            .collect();
+        self.multiply(&utilities)
+    }
+
+    fn explain(&self, context: &T) -> Explanation {
+        let children = explain_all(&self.rules, context);
+        let utilities: Vec<Utility> = children.iter().map(get_utility).collect();
+        Explanation::utility("product", self.multiply(&utilities), children)
+    }
+}
+
+/// The weighted average of multiple utility rules.
+pub struct WeightedAverageUtility<T> {
+    rules: Vec<(f32, Box<dyn UtilityRule<T>>)>,
+}
+
+impl<T> WeightedAverageUtility<T> {
+    pub fn new(rules: Vec<(f32, Box<dyn UtilityRule<T>>)>) -> WeightedAverageUtility<T> {
+        WeightedAverageUtility { rules }
+    }
+
+    fn average(&self, utilities: &[Utility]) -> Utility {
+        let total_weight: f32 = self.rules.iter().map(|(weight, _)| weight).sum();
+
+        if total_weight == 0.0 {
+            return Utility::default();
+        }
+
+        let sum: f32 = self
+            .rules
+            .iter()
+            .zip(utilities)
+            .map(|((weight, _), utility)| weight * utility.to_f32())
+            .sum();
+
+        Utility::from_f32(sum / total_weight)
+    }
+}
+
+impl<T> UtilityRule<T> for WeightedAverageUtility<T> {
+    /// Returns the weighted average of multiple utility rules. Without any weight it returns 0.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::utility::{FixedUtility, Utility, UtilityRule, WeightedAverageUtility};
+    /// let average = WeightedAverageUtility::new(vec![
+    ///     (3.0, Box::new(FixedUtility::new(Utility::from_integer(10)))),
+    ///     (1.0, Box::new(FixedUtility::new(Utility::from_integer(50)))),
+    /// ]);
+    ///
+    /// assert_eq!(average.calculate_utility(&42), Utility::from_integer(20));
+    /// ```
+    fn calculate_utility(&self, context: &T) -> Utility {
+        let utilities: Vec<Utility> = self
+            .rules
+            .iter()
+            .map(|(_, rule)| rule.calculate_utility(context))
+            .collect();
+        self.average(&utilities)
+    }
+
+    fn explain(&self, context: &T) -> Explanation {
+        let children: Vec<Explanation> = self
+            .rules
+            .iter()
+            .map(|(_, rule)| rule.explain(context))
+            .collect();
+        let utilities: Vec<Utility> = children.iter().map(get_utility).collect();
+        Explanation::utility("weighted average", self.average(&utilities), children)
+    }
+}
+
+/// Maps the utility of a rule from a range to a normalized utility between 0 & [`Utility::ONE`].
+pub struct NormalizedUtility<T> {
+    rule: Box<dyn UtilityRule<T>>,
+    min: Utility,
+    max: Utility,
+}
+
+impl<T> NormalizedUtility<T> {
+    pub fn new(rule: Box<dyn UtilityRule<T>>, min: Utility, max: Utility) -> NormalizedUtility<T> {
+        NormalizedUtility { rule, min, max }
+    }
+
+    fn normalize(&self, utility: Utility) -> Utility {
+        let range = self.max.to_f32() - self.min.to_f32();
+
+        if range == 0.0 {
+            return Utility::default();
+        }
+
+        let value = (utility.to_f32() - self.min.to_f32()) / range;
+        Utility::from_normalized(value.clamp(0.0, 1.0))
+    }
+}
+
+impl<T> UtilityRule<T> for NormalizedUtility<T> {
+    /// Returns the normalized utility of the rule. Utilities outside the range are clamped.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::utility::{FixedUtility, NormalizedUtility, Utility, UtilityRule};
+    /// let rule = NormalizedUtility::new(
+    ///     Box::new(FixedUtility::new(Utility::from_integer(15))),
+    ///     Utility::from_integer(10),
+    ///     Utility::from_integer(30),
+    /// );
+    ///
+    /// assert_eq!(rule.calculate_utility(&42), Utility::from_normalized(0.25));
+    /// ```
+    fn calculate_utility(&self, context: &T) -> Utility {
+        self.normalize(self.rule.calculate_utility(context))
+    }
+
+    fn explain(&self, context: &T) -> Explanation {
+        let child = self.rule.explain(context);
+        let utility = self.normalize(get_utility(&child));
+        Explanation::utility("normalized", utility, vec![child])
+    }
+}
+
+fn explain_all<T>(rules: &[Box<dyn UtilityRule<T>>], context: &T) -> Vec<Explanation> {
+    rules.iter().map(|r| r.explain(context)).collect()
+}
+
+fn get_utility(explanation: &Explanation) -> Utility {
+    match explanation.get_outcome() {
+        Outcome::Utility(utility) => utility,
+        Outcome::Condition(_) => Utility::default(),
     }
 }
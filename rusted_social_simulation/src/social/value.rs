@@ -0,0 +1,259 @@
+use crate::social::explanation::Explanation;
+use crate::social::utility::{Utility, UtilityRule};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Gets a value from the context, e.g. the money of the actor.
+pub type Getter<T> = Box<dyn Fn(&T) -> Utility + Send + Sync>;
+
+/// Compares 2 values.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Comparison {
+    Less,
+    LessOrEqual,
+    Equal,
+    NotEqual,
+    GreaterOrEqual,
+    Greater,
+}
+
+impl Comparison {
+    /// Is the comparison of both values true?
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::utility::Utility;
+    ///# use rusted_social_simulation::social::value::Comparison;
+    /// let (two, three) = (Utility::from_integer(2), Utility::from_integer(3));
+    ///
+    /// assert!(Comparison::Less.compare(two, three));
+    /// assert!(!Comparison::Greater.compare(two, three));
+    /// ```
+    pub fn compare(&self, left: Utility, right: Utility) -> bool {
+        match self {
+            Comparison::Less => left < right,
+            Comparison::LessOrEqual => left <= right,
+            Comparison::Equal => left == right,
+            Comparison::NotEqual => left != right,
+            Comparison::GreaterOrEqual => left >= right,
+            Comparison::Greater => left > right,
+        }
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Comparison::Less => write!(f, "<"),
+            Comparison::LessOrEqual => write!(f, "<="),
+            Comparison::Equal => write!(f, "=="),
+            Comparison::NotEqual => write!(f, "!="),
+            Comparison::GreaterOrEqual => write!(f, ">="),
+            Comparison::Greater => write!(f, ">"),
+        }
+    }
+}
+
+/// Combines 2 values.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+impl Operator {
+    /// Combines both values. The result saturates instead of overflowing & a division by 0 returns 0.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::utility::Utility;
+    ///# use rusted_social_simulation::social::value::Operator;
+    /// let [zero, two, three, six] = [0, 2, 3, 6].map(Utility::from_integer);
+    ///
+    /// assert_eq!(Operator::Subtract.apply(two, three), Utility::from_integer(-1));
+    /// assert_eq!(Operator::Divide.apply(six, two), three);
+    /// assert_eq!(Operator::Divide.apply(six, zero), zero);
+    /// ```
+    pub fn apply(&self, left: Utility, right: Utility) -> Utility {
+        match self {
+            Operator::Add => left.saturating_add(right),
+            Operator::Subtract => left.saturating_sub(right),
+            Operator::Multiply => left.saturating_mul(right),
+            Operator::Divide => left.divide(right),
+        }
+    }
+}
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operator::Add => write!(f, "+"),
+            Operator::Subtract => write!(f, "-"),
+            Operator::Multiply => write!(f, "*"),
+            Operator::Divide => write!(f, "/"),
+        }
+    }
+}
+
+/// A source of a numeric value, that can be compared by a [`CompareCondition`](crate::social::condition::CompareCondition).
+///
+/// Values have the same type as utilities, so both can be mixed.
+///
+/// ```
+///# use rusted_social_simulation::social::utility::{FixedUtility, Utility};
+///# use rusted_social_simulation::social::value::{Operator, Value};
+/// let value = Value::arithmetic(
+///     Value::getter(|context: &i32| Utility::from_integer(*context)),
+///     Operator::Multiply,
+///     Value::arithmetic(
+///         Value::Constant(Utility::from_integer(2)),
+///         Operator::Add,
+///         Value::utility(FixedUtility::new(Utility::from_integer(1))),
+///     ),
+/// );
+///
+/// assert_eq!(value.get_value(&4), Utility::from_integer(12));
+/// ```
+pub enum Value<T> {
+    Constant(Utility),
+    /// A value calculated by a closure, e.g. a domain-specific property.
+    Getter(Getter<T>),
+    /// The utility calculated by a rule.
+    Utility(Box<dyn UtilityRule<T>>),
+    Arithmetic {
+        left: Box<Value<T>>,
+        operator: Operator,
+        right: Box<Value<T>>,
+    },
+}
+
+impl<T> Value<T> {
+    pub fn getter<F: Fn(&T) -> Utility + Send + Sync + 'static>(getter: F) -> Value<T> {
+        Value::Getter(Box::new(getter))
+    }
+
+    pub fn utility<R: UtilityRule<T> + 'static>(rule: R) -> Value<T> {
+        Value::Utility(Box::new(rule))
+    }
+
+    pub fn arithmetic(left: Value<T>, operator: Operator, right: Value<T>) -> Value<T> {
+        Value::Arithmetic {
+            left: Box::new(left),
+            operator,
+            right: Box::new(right),
+        }
+    }
+
+    /// Gets the value for the context.
+    pub fn get_value(&self, context: &T) -> Utility {
+        match self {
+            Value::Constant(value) => *value,
+            Value::Getter(getter) => getter(context),
+            Value::Utility(rule) => rule.calculate_utility(context),
+            Value::Arithmetic {
+                left,
+                operator,
+                right,
+            } => operator.apply(left.get_value(context), right.get_value(context)),
+        }
+    }
+
+    /// Explains how the value was calculated.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::utility::{FixedUtility, Utility};
+    ///# use rusted_social_simulation::social::value::{Operator, Value};
+    /// let value = Value::arithmetic(
+    ///     Value::Constant(Utility::from_integer(2)),
+    ///     Operator::Add,
+    ///     Value::utility(FixedUtility::new(Utility::from_integer(1))),
+    /// );
+    ///
+    /// assert_eq!(value.explain(&42).to_text(), "+ = 3\n  constant = 2\n  fixed = 1\n");
+    /// ```
+    pub fn explain(&self, context: &T) -> Explanation {
+        match self {
+            Value::Constant(value) => Explanation::utility("constant", *value, Vec::new()),
+            Value::Getter(getter) => Explanation::utility("getter", getter(context), Vec::new()),
+            Value::Utility(rule) => rule.explain(context),
+            Value::Arithmetic {
+                left,
+                operator,
+                right,
+            } => Explanation::utility(
+                &operator.to_string(),
+                self.get_value(context),
+                vec![left.explain(context), right.explain(context)],
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare() {
+        let comparisons = [
+            (Comparison::Less, [true, false, false]),
+            (Comparison::LessOrEqual, [true, true, false]),
+            (Comparison::Equal, [false, true, false]),
+            (Comparison::NotEqual, [true, false, true]),
+            (Comparison::GreaterOrEqual, [false, true, true]),
+            (Comparison::Greater, [false, false, true]),
+        ];
+
+        let [one, two, three] = [1, 2, 3].map(Utility::from_integer);
+
+        for (comparison, results) in comparisons.iter() {
+            assert_eq!(
+                comparison.compare(one, two),
+                results[0],
+                "{} 1 2",
+                comparison
+            );
+            assert_eq!(
+                comparison.compare(two, two),
+                results[1],
+                "{} 2 2",
+                comparison
+            );
+            assert_eq!(
+                comparison.compare(three, two),
+                results[2],
+                "{} 3 2",
+                comparison
+            );
+        }
+    }
+
+    #[test]
+    fn test_apply() {
+        let [two, three, six] = [2, 3, 6].map(Utility::from_integer);
+
+        assert_eq!(Operator::Add.apply(six, two), Utility::from_integer(8));
+        assert_eq!(Operator::Subtract.apply(six, two), Utility::from_integer(4));
+        assert_eq!(
+            Operator::Multiply.apply(six, two),
+            Utility::from_integer(12)
+        );
+        assert_eq!(Operator::Divide.apply(six, two), three);
+    }
+
+    #[test]
+    fn test_apply_saturates() {
+        let one = Utility::from_integer(1);
+
+        assert_eq!(Operator::Add.apply(Utility::MAX, one), Utility::MAX);
+        assert_eq!(Operator::Subtract.apply(Utility::MIN, one), Utility::MIN);
+        assert_eq!(
+            Operator::Multiply.apply(Utility::MAX, Utility::from_integer(-2)),
+            Utility::MIN
+        );
+        assert_eq!(
+            Operator::Divide.apply(Utility::MIN, Utility::from_integer(-1)),
+            Utility::MAX
+        );
+    }
+}
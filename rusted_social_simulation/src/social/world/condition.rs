@@ -0,0 +1,126 @@
+use crate::social::condition::Condition;
+use crate::social::practice::role::Target;
+use crate::social::practice::RoleContext;
+use crate::social::world::WorldContext;
+
+/// A condition that checks, if an attribute of all entities of a target has at least a certain value.
+pub struct AttributeAtLeast {
+    target: Target,
+    attribute: String,
+    threshold: i32,
+}
+
+impl AttributeAtLeast {
+    pub fn new(target: Target, attribute: &str, threshold: i32) -> AttributeAtLeast {
+        AttributeAtLeast {
+            target,
+            attribute: attribute.to_string(),
+            threshold,
+        }
+    }
+}
+
+impl<T: WorldContext + RoleContext> Condition<T> for AttributeAtLeast {
+    /// Returns true, if the attributes are greater or equal to the threshold.
+    /// Returns false, if the target refers to no entity.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::condition::Condition;
+    ///# use rusted_social_simulation::social::execution::ExecutionContext;
+    ///# use rusted_social_simulation::social::practice::role::Target;
+    ///# use rusted_social_simulation::social::world::condition::AttributeAtLeast;
+    ///# use rusted_social_simulation::social::world::World;
+    /// let mut world = World::new();
+    /// world.set_attribute(3, "health", 10);
+    /// let context = ExecutionContext::new(world);
+    ///
+    /// assert!(AttributeAtLeast::new(Target::Entity(3), "health", 9).evaluate(&context));
+    /// assert!(AttributeAtLeast::new(Target::Entity(3), "health", 10).evaluate(&context));
+    /// assert!(!AttributeAtLeast::new(Target::Entity(3), "health", 11).evaluate(&context));
+    /// assert!(!AttributeAtLeast::new(Target::Actor, "health", 0).evaluate(&context));
+    /// ```
+    fn evaluate(&self, context: &T) -> bool {
+        let entities = context.resolve(self.target);
+        let world = context.get_world();
+        !entities.is_empty()
+            && entities
+                .iter()
+                .all(|entity| world.get_attribute(*entity, &self.attribute) >= self.threshold)
+    }
+}
+
+/// A condition that checks, if a flag of all entities of a target is set.
+pub struct FlagIsSet {
+    target: Target,
+    flag: String,
+}
+
+impl FlagIsSet {
+    pub fn new(target: Target, flag: &str) -> FlagIsSet {
+        FlagIsSet {
+            target,
+            flag: flag.to_string(),
+        }
+    }
+}
+
+impl<T: WorldContext + RoleContext> Condition<T> for FlagIsSet {
+    /// Returns true, if the flags are set.
+    /// Returns false, if the target refers to no entity.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::condition::Condition;
+    ///# use rusted_social_simulation::social::execution::ExecutionContext;
+    ///# use rusted_social_simulation::social::practice::role::Target;
+    ///# use rusted_social_simulation::social::world::condition::FlagIsSet;
+    ///# use rusted_social_simulation::social::world::World;
+    /// let mut world = World::new();
+    /// world.set_flag(3, "is_hungry", true);
+    /// let context = ExecutionContext::new(world);
+    ///
+    /// assert!(FlagIsSet::new(Target::Entity(3), "is_hungry").evaluate(&context));
+    /// assert!(!FlagIsSet::new(Target::Entity(3), "is_tired").evaluate(&context));
+    /// assert!(!FlagIsSet::new(Target::Actor, "is_hungry").evaluate(&context));
+    /// ```
+    fn evaluate(&self, context: &T) -> bool {
+        let entities = context.resolve(self.target);
+        let world = context.get_world();
+        !entities.is_empty()
+            && entities
+                .iter()
+                .all(|entity| world.get_flag(*entity, &self.flag))
+    }
+}
+
+/// A condition that checks, if a global variable has at least a certain value.
+pub struct VariableAtLeast {
+    variable: String,
+    threshold: i32,
+}
+
+impl VariableAtLeast {
+    pub fn new(variable: &str, threshold: i32) -> VariableAtLeast {
+        VariableAtLeast {
+            variable: variable.to_string(),
+            threshold,
+        }
+    }
+}
+
+impl<T: WorldContext> Condition<T> for VariableAtLeast {
+    /// Returns true, if the variable is greater or equal to the threshold.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::condition::Condition;
+    ///# use rusted_social_simulation::social::world::condition::VariableAtLeast;
+    ///# use rusted_social_simulation::social::world::World;
+    /// let mut world = World::new();
+    /// world.set_variable("time", 12);
+    ///
+    /// assert!(VariableAtLeast::new("time", 12).evaluate(&world));
+    /// assert!(!VariableAtLeast::new("time", 13).evaluate(&world));
+    /// ```
+    fn evaluate(&self, context: &T) -> bool {
+        context.get_world().get_variable(&self.variable) >= self.threshold
+    }
+}
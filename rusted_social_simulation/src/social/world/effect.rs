@@ -0,0 +1,127 @@
+use crate::social::effect::Effect;
+use crate::social::practice::role::Target;
+use crate::social::practice::RoleContext;
+use crate::social::world::WorldContext;
+
+/// An effect that adds a value to an attribute of all entities of a target.
+pub struct ModifyAttribute {
+    target: Target,
+    attribute: String,
+    delta: i32,
+}
+
+impl ModifyAttribute {
+    pub fn new(target: Target, attribute: &str, delta: i32) -> ModifyAttribute {
+        ModifyAttribute {
+            target,
+            attribute: attribute.to_string(),
+            delta,
+        }
+    }
+}
+
+impl<T: WorldContext + RoleContext> Effect<T> for ModifyAttribute {
+    /// Adds a value to the attributes. The results saturate instead of overflowing.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::effect::Effect;
+    ///# use rusted_social_simulation::social::execution::ExecutionContext;
+    ///# use rusted_social_simulation::social::practice::role::Target;
+    ///# use rusted_social_simulation::social::world::effect::ModifyAttribute;
+    ///# use rusted_social_simulation::social::world::World;
+    /// let mut world = World::new();
+    /// world.set_attribute(3, "health", 10);
+    /// let mut context = ExecutionContext::new(world);
+    ///
+    /// ModifyAttribute::new(Target::Entity(3), "health", -4).apply(&mut context);
+    ///
+    /// assert_eq!(context.get_context().get_attribute(3, "health"), 6);
+    /// ```
+    fn apply(&self, context: &mut T) {
+        for entity in context.resolve(self.target) {
+            let world = context.get_world_mut();
+            let value = world.get_attribute(entity, &self.attribute);
+            world.set_attribute(entity, &self.attribute, value.saturating_add(self.delta));
+        }
+    }
+}
+
+/// An effect that sets a flag of all entities of a target.
+pub struct SetFlag {
+    target: Target,
+    flag: String,
+    value: bool,
+}
+
+impl SetFlag {
+    pub fn new(target: Target, flag: &str, value: bool) -> SetFlag {
+        SetFlag {
+            target,
+            flag: flag.to_string(),
+            value,
+        }
+    }
+}
+
+impl<T: WorldContext + RoleContext> Effect<T> for SetFlag {
+    /// Sets the flags to a fixed value.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::effect::Effect;
+    ///# use rusted_social_simulation::social::execution::ExecutionContext;
+    ///# use rusted_social_simulation::social::practice::role::Target;
+    ///# use rusted_social_simulation::social::world::effect::SetFlag;
+    ///# use rusted_social_simulation::social::world::World;
+    /// let mut context = ExecutionContext::new(World::new());
+    ///
+    /// SetFlag::new(Target::Entity(3), "greeted", true).apply(&mut context);
+    ///
+    /// assert!(context.get_context().get_flag(3, "greeted"));
+    /// ```
+    fn apply(&self, context: &mut T) {
+        for entity in context.resolve(self.target) {
+            context
+                .get_world_mut()
+                .set_flag(entity, &self.flag, self.value);
+        }
+    }
+}
+
+/// An effect that adds a value to a global variable.
+pub struct ModifyVariable {
+    variable: String,
+    delta: i32,
+}
+
+impl ModifyVariable {
+    pub fn new(variable: &str, delta: i32) -> ModifyVariable {
+        ModifyVariable {
+            variable: variable.to_string(),
+            delta,
+        }
+    }
+}
+
+impl<T: WorldContext> Effect<T> for ModifyVariable {
+    /// Adds a value to the variable. The result saturates instead of overflowing.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::effect::Effect;
+    ///# use rusted_social_simulation::social::world::effect::ModifyVariable;
+    ///# use rusted_social_simulation::social::world::World;
+    /// let mut world = World::new();
+    ///
+    /// ModifyVariable::new("time", 2).apply(&mut world);
+    ///
+    /// assert_eq!(world.get_variable("time"), 2);
+    ///
+    /// ModifyVariable::new("time", i32::MAX).apply(&mut world);
+    ///
+    /// assert_eq!(world.get_variable("time"), i32::MAX);
+    /// ```
+    fn apply(&self, context: &mut T) {
+        let world = context.get_world_mut();
+        let value = world.get_variable(&self.variable);
+        world.set_variable(&self.variable, value.saturating_add(self.delta));
+    }
+}
@@ -0,0 +1,245 @@
+use crate::social::emotion::{EmotionContext, Emotions};
+use crate::social::execution::ExecutionContext;
+use crate::social::personality::{Personalities, Personality, PersonalityContext};
+use crate::social::relationship::{RelationshipContext, Relationships};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+pub mod condition;
+pub mod effect;
+pub mod utility;
+
+/// A ready-made context for simulations that need no custom one.
+///
+/// It contains entities with named numeric attributes & boolean flags,
+/// their personalities & emotions, the relationships between them and global numeric variables.
+/// Missing attributes & variables are 0, and missing flags are false.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct World {
+    entities: BTreeMap<u32, EntityState>,
+    #[serde(default)]
+    emotions: Emotions,
+    #[serde(default)]
+    personalities: Personalities,
+    relationships: Relationships,
+    variables: BTreeMap<String, i32>,
+}
+
+/// The state of an entity in the world.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+struct EntityState {
+    attributes: BTreeMap<String, i32>,
+    flags: BTreeMap<String, bool>,
+}
+
+impl World {
+    pub fn new() -> World {
+        World::default()
+    }
+
+    /// Adds an entity, if it doesn't exist yet.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::world::World;
+    /// let mut world = World::new();
+    ///
+    /// world.add_entity(3);
+    ///
+    /// assert!(world.has_entity(3));
+    /// assert!(!world.has_entity(4));
+    /// ```
+    pub fn add_entity(&mut self, entity: u32) {
+        self.entities.entry(entity).or_default();
+    }
+
+    /// Removes an entity with all its attributes, flags, its personality, its emotions
+    /// & its relationships from & towards other entities. The emotions of others toward it are removed too.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::emotion::EmotionContext;
+    ///# use rusted_social_simulation::social::relationship::RelationshipContext;
+    ///# use rusted_social_simulation::social::world::World;
+    /// let mut world = World::new();
+    /// world.set_attribute(3, "health", 10);
+    /// world.get_relationships_mut().set(3, 4, "affinity", 20);
+    /// world.get_relationships_mut().set(4, 3, "affinity", 30);
+    /// world.get_emotions_mut().trigger(4, "joy", 3, 50);
+    ///
+    /// world.remove_entity(3);
+    ///
+    /// assert_eq!(world.get_emotions().get(4, "joy", 3), 0);
+    /// assert!(!world.has_entity(3));
+    /// assert_eq!(world.get_attribute(3, "health"), 0);
+    /// assert_eq!(world.get_relationships().get(3, 4, "affinity"), 0);
+    /// assert_eq!(world.get_relationships().get(4, 3, "affinity"), 0);
+    /// ```
+    pub fn remove_entity(&mut self, entity: u32) {
+        self.entities.remove(&entity);
+        self.relationships.remove(entity);
+        self.personalities.remove(entity);
+        self.emotions.remove(entity);
+    }
+
+    /// Sets the personality of an entity & adds the entity, if it doesn't exist yet.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::personality::{Personality, PersonalityContext, EXTRAVERSION};
+    ///# use rusted_social_simulation::social::world::World;
+    /// let mut world = World::new();
+    ///
+    /// world.set_personality(3, Personality::big_five(0, 0, 70, 0, 0));
+    ///
+    /// assert!(world.has_entity(3));
+    /// assert_eq!(world.get_personalities().get(3).get(EXTRAVERSION), 70);
+    /// ```
+    pub fn set_personality(&mut self, entity: u32, personality: Personality) {
+        self.add_entity(entity);
+        self.personalities.set(entity, personality);
+    }
+
+    /// Does the entity exist?
+    pub fn has_entity(&self, entity: u32) -> bool {
+        self.entities.contains_key(&entity)
+    }
+
+    /// Gets all entities sorted by their id.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::world::World;
+    /// let mut world = World::new();
+    /// world.add_entity(8);
+    /// world.add_entity(2);
+    ///
+    /// assert_eq!(world.get_entities(), vec![2, 8]);
+    /// ```
+    pub fn get_entities(&self) -> Vec<u32> {
+        self.entities.keys().copied().collect()
+    }
+
+    /// Gets a numeric attribute of an entity.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::world::World;
+    /// let mut world = World::new();
+    /// world.set_attribute(3, "health", 10);
+    ///
+    /// assert_eq!(world.get_attribute(3, "health"), 10);
+    /// assert_eq!(world.get_attribute(3, "mana"), 0);
+    /// assert_eq!(world.get_attribute(4, "health"), 0);
+    /// ```
+    pub fn get_attribute(&self, entity: u32, attribute: &str) -> i32 {
+        self.entities
+            .get(&entity)
+            .and_then(|state| state.attributes.get(attribute))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Sets a numeric attribute of an entity. Adds the entity, if needed.
+    pub fn set_attribute(&mut self, entity: u32, attribute: &str, value: i32) {
+        self.entities
+            .entry(entity)
+            .or_default()
+            .attributes
+            .insert(attribute.to_string(), value);
+    }
+
+    /// Gets a boolean flag of an entity.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::world::World;
+    /// let mut world = World::new();
+    /// world.set_flag(3, "is_hungry", true);
+    ///
+    /// assert!(world.get_flag(3, "is_hungry"));
+    /// assert!(!world.get_flag(3, "is_tired"));
+    /// ```
+    pub fn get_flag(&self, entity: u32, flag: &str) -> bool {
+        self.entities
+            .get(&entity)
+            .and_then(|state| state.flags.get(flag))
+            .copied()
+            .unwrap_or(false)
+    }
+
+    /// Sets a boolean flag of an entity. Adds the entity, if needed.
+    pub fn set_flag(&mut self, entity: u32, flag: &str, value: bool) {
+        self.entities
+            .entry(entity)
+            .or_default()
+            .flags
+            .insert(flag.to_string(), value);
+    }
+
+    /// Gets a global variable.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::world::World;
+    /// let mut world = World::new();
+    /// world.set_variable("time", 12);
+    ///
+    /// assert_eq!(world.get_variable("time"), 12);
+    /// assert_eq!(world.get_variable("weather"), 0);
+    /// ```
+    pub fn get_variable(&self, variable: &str) -> i32 {
+        self.variables.get(variable).copied().unwrap_or(0)
+    }
+
+    /// Sets a global variable.
+    pub fn set_variable(&mut self, variable: &str, value: i32) {
+        self.variables.insert(variable.to_string(), value);
+    }
+}
+
+impl EmotionContext for World {
+    fn get_emotions(&self) -> &Emotions {
+        &self.emotions
+    }
+
+    fn get_emotions_mut(&mut self) -> &mut Emotions {
+        &mut self.emotions
+    }
+}
+
+impl PersonalityContext for World {
+    fn get_personalities(&self) -> &Personalities {
+        &self.personalities
+    }
+}
+
+impl RelationshipContext for World {
+    fn get_relationships(&self) -> &Relationships {
+        &self.relationships
+    }
+
+    fn get_relationships_mut(&mut self) -> &mut Relationships {
+        &mut self.relationships
+    }
+}
+
+/// A context, that contains a [`World`].
+pub trait WorldContext {
+    fn get_world(&self) -> &World;
+
+    fn get_world_mut(&mut self) -> &mut World;
+}
+
+impl WorldContext for World {
+    fn get_world(&self) -> &World {
+        self
+    }
+
+    fn get_world_mut(&mut self) -> &mut World {
+        self
+    }
+}
+
+impl<T: WorldContext> WorldContext for ExecutionContext<T> {
+    fn get_world(&self) -> &World {
+        self.get_context().get_world()
+    }
+
+    fn get_world_mut(&mut self) -> &mut World {
+        self.get_context_mut().get_world_mut()
+    }
+}
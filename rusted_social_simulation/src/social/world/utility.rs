@@ -0,0 +1,85 @@
+use crate::social::practice::role::Target;
+use crate::social::practice::RoleContext;
+use crate::social::utility::{Utility, UtilityRule};
+use crate::social::world::WorldContext;
+
+/// An utility rule that scales an attribute of all entities of a target.
+pub struct AttributeScaledUtility {
+    target: Target,
+    attribute: String,
+    factor: Utility,
+}
+
+impl AttributeScaledUtility {
+    pub fn new(target: Target, attribute: &str, factor: Utility) -> AttributeScaledUtility {
+        AttributeScaledUtility {
+            target,
+            attribute: attribute.to_string(),
+            factor,
+        }
+    }
+}
+
+impl<T: WorldContext + RoleContext> UtilityRule<T> for AttributeScaledUtility {
+    /// Returns the sum of the attributes multiplied with a factor.
+    /// Returns 0, if the target refers to no entity. The result saturates instead of overflowing.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::execution::ExecutionContext;
+    ///# use rusted_social_simulation::social::practice::role::Target;
+    ///# use rusted_social_simulation::social::utility::{Utility, UtilityRule};
+    ///# use rusted_social_simulation::social::world::utility::AttributeScaledUtility;
+    ///# use rusted_social_simulation::social::world::World;
+    /// let mut world = World::new();
+    /// world.set_attribute(3, "hunger", 7);
+    /// let context = ExecutionContext::new(world);
+    /// let rule = AttributeScaledUtility::new(Target::Entity(3), "hunger", Utility::from_integer(-2));
+    ///
+    /// assert_eq!(rule.calculate_utility(&context), Utility::from_integer(-14));
+    /// ```
+    fn calculate_utility(&self, context: &T) -> Utility {
+        let world = context.get_world();
+        context
+            .resolve(self.target)
+            .iter()
+            .map(|entity| {
+                Utility::from_integer(world.get_attribute(*entity, &self.attribute))
+                    .saturating_mul(self.factor)
+            })
+            .fold(Utility::default(), Utility::saturating_add)
+    }
+}
+
+/// An utility rule that scales a global variable.
+pub struct VariableScaledUtility {
+    variable: String,
+    factor: Utility,
+}
+
+impl VariableScaledUtility {
+    pub fn new(variable: &str, factor: Utility) -> VariableScaledUtility {
+        VariableScaledUtility {
+            variable: variable.to_string(),
+            factor,
+        }
+    }
+}
+
+impl<T: WorldContext> UtilityRule<T> for VariableScaledUtility {
+    /// Returns the variable multiplied with a factor. The result saturates instead of overflowing.
+    ///
+    /// ```
+    ///# use rusted_social_simulation::social::utility::{Utility, UtilityRule};
+    ///# use rusted_social_simulation::social::world::utility::VariableScaledUtility;
+    ///# use rusted_social_simulation::social::world::World;
+    /// let mut world = World::new();
+    /// world.set_variable("danger", 4);
+    /// let rule = VariableScaledUtility::new("danger", Utility::from_integer(3));
+    ///
+    /// assert_eq!(rule.calculate_utility(&world), Utility::from_integer(12));
+    /// ```
+    fn calculate_utility(&self, context: &T) -> Utility {
+        Utility::from_integer(context.get_world().get_variable(&self.variable))
+            .saturating_mul(self.factor)
+    }
+}